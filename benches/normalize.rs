@@ -0,0 +1,24 @@
+//! Time how long it takes to normalize the scores of large populations.
+//!
+//! Run with: `cargo bench --bench normalize`, and compare with
+//! `cargo bench --bench normalize --features simd`
+
+use mate_selection::{MateSelection, Normalized, Proportional};
+use std::time::Instant;
+
+fn main() {
+    for population in [1_000_000, 10_000_000] {
+        let scores: Vec<f64> = (0..population).map(|_| rand::random()).collect();
+        let methods: [(&str, &dyn MateSelection); 2] = [
+            ("Normalized(0.0)", &Normalized(0.0)),
+            ("Proportional", &Proportional),
+        ];
+        for (name, method) in methods {
+            let start = Instant::now();
+            let pdf = method.pdf_slice(&scores);
+            let elapsed = start.elapsed();
+            assert_eq!(pdf.len(), population);
+            println!("{name:<16} pdf {population:>8} scores: {elapsed:?}");
+        }
+    }
+}
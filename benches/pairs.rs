@@ -0,0 +1,42 @@
+//! Time how long it takes to choose pairs from a heavily skewed distribution,
+//! where most of the pairs need to be rearranged to avoid self mating.
+//!
+//! Also time the truncation methods on large populations, where finding the
+//! cutoff dominates the cost of choosing a few pairs.
+//!
+//! Run with: `cargo bench --bench pairs`
+
+use mate_selection::{Best, MateSelection, Percentile, RankedExponentialBase};
+use std::time::Instant;
+
+fn main() {
+    let rng = &mut rand::rng();
+    for population in [1_000, 10_000, 100_000] {
+        let scores: Vec<f64> = (0..population).map(|x| x as f64).collect();
+        let methods: [(&str, &dyn MateSelection<_>); 2] = [
+            ("Percentile(0.99)", &Percentile(0.99)),
+            ("RankedExponentialBase(0.3)", &RankedExponentialBase(0.3)),
+        ];
+        for (name, method) in methods {
+            let start = Instant::now();
+            let pairs = method.pairs(rng, population, scores.clone());
+            let elapsed = start.elapsed();
+            assert_eq!(pairs.len(), population);
+            println!("{name:<28} {population:>7} pairs: {elapsed:?}");
+        }
+    }
+    for population in [1_000_000, 10_000_000] {
+        let scores: Vec<f64> = (0..population).map(|_| rand::random()).collect();
+        let methods: [(&str, &dyn MateSelection<_>); 2] = [
+            ("Percentile(0.5)", &Percentile(0.5)),
+            ("Best(100)", &Best(100)),
+        ];
+        for (name, method) in methods {
+            let start = Instant::now();
+            let pairs = method.pairs_slice(rng, 100, &scores);
+            let elapsed = start.elapsed();
+            assert_eq!(pairs.len(), 100);
+            println!("{name:<28} {population:>8} scores: {elapsed:?}");
+        }
+    }
+}
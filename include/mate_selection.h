@@ -0,0 +1,66 @@
+/* C interface to the mate_selection library.
+ *
+ * Build the library with the "ffi" feature:
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ * and link against "target/release/libmate_selection.so", or use
+ * "--crate-type staticlib" for the static library. See "src/ffi.rs" for the implementation.
+ *
+ * Keep this header in sync with "src/ffi.rs".
+ */
+#ifndef MATE_SELECTION_H
+#define MATE_SELECTION_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque handle to a selection method and its random number generator. */
+typedef struct MsSelection MsSelection;
+
+/* Result of a call into this library. */
+typedef enum MsStatus {
+    MS_STATUS_OK = 0,
+    /* A required pointer argument was null. */
+    MS_STATUS_NULL_POINTER = 1,
+    /* The arguments or the scores are invalid. */
+    MS_STATUS_INVALID_ARGUMENT = 2,
+    /* The selection method panicked. */
+    MS_STATUS_PANIC = 3,
+} MsStatus;
+
+/* Create a selection method from its text specification, for example
+ * "ranked_linear(0.5)" or "elitist(1, tournament(2))". Returns NULL if the
+ * specification is invalid. The handle must be freed with ms_selection_free.
+ */
+MsSelection *ms_selection_new(const char *spec, uint64_t seed);
+
+/* Free a selection method. NULL handles are ignored. */
+void ms_selection_free(MsSelection *handle);
+
+/* Restart the random number generator from the given seed. */
+MsStatus ms_selection_seed(MsSelection *handle, uint64_t seed);
+
+/* Choose multiple weighted pairs. Writes 2 * amount indices into out_pairs,
+ * with the two parents of each pair next to each other.
+ */
+MsStatus ms_selection_pairs(MsSelection *handle, const double *scores, size_t num_scores,
+                            size_t amount, size_t *out_pairs);
+
+/* Choose multiple weighted. Writes amount indices into out_selected. */
+MsStatus ms_selection_select(MsSelection *handle, const double *scores, size_t num_scores,
+                             size_t amount, size_t *out_selected);
+
+/* Probability distribution function. Writes num_scores probabilities into
+ * out_pdf.
+ */
+MsStatus ms_selection_pdf(const MsSelection *handle, const double *scores, size_t num_scores,
+                          double *out_pdf);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MATE_SELECTION_H */
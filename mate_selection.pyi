@@ -0,0 +1,422 @@
+"""A collection of mate selection methods for evolutionary algorithms"""
+
+# Type stubs for the compiled extension module, see "src/lib.rs" (mod python).
+# Keep these in sync with the Python bindings.
+
+import array
+from collections.abc import Sequence
+from typing import overload
+
+import numpy as np
+import numpy.typing as npt
+
+# NumPy arrays of scores, which return NumPy arrays.
+_Array = npt.NDArray[np.float64] | npt.NDArray[np.float32]
+# Other scores, which return lists.
+_Scores = Sequence[float] | array.array[float] | memoryview
+
+def seed(seed: int | None = None) -> None: ...
+
+class Random:
+    def __init__(self) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Proportional:
+    def __init__(self) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class PowerScaled:
+    def __init__(self, exponent: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Normalized:
+    def __init__(self, cutoff: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Best:
+    def __init__(self, number: int) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Percentile:
+    def __init__(self, percentile: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class RankedLinear:
+    def __init__(self, selection_pressure: float) -> None: ...
+    @staticmethod
+    def from_sp(sp: float) -> RankedLinear: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class RankedExponential:
+    def __init__(self, median: int) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class LinearScaled:
+    def __init__(self, multiple: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class RankedExponentialBase:
+    def __init__(self, base: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Windowed:
+    def __init__(self, window: int = 0) -> None: ...
+    def record(self, scores: _Scores) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Tournament:
+    def __init__(self, size: int) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class ProbabilisticTournament:
+    def __init__(self, size: int, probability: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class RankedGeometric:
+    def __init__(self, q: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class RankedGaussian:
+    def __init__(self, sigma: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Boltzmann:
+    def __init__(self, temperature: float) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class AnnealedBoltzmann:
+    def __init__(self, initial_temperature: float, decay: float, generation: int = 0) -> None: ...
+    def set_generation(self, generation: int) -> None: ...
+    def temperature(self) -> float: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class UnbiasedTournament:
+    def __init__(self, size: int) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
+
+class Disruptive:
+    def __init__(self) -> None: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def pairs(
+        self, amount: int, scores: _Scores, seed: int | None = None
+    ) -> list[list[int]]: ...
+    @overload
+    def select(
+        self, amount: int, scores: _Array, seed: int | None = None
+    ) -> npt.NDArray[np.uintp]: ...
+    @overload
+    def select(self, amount: int, scores: _Scores, seed: int | None = None) -> list[int]: ...
+    @overload
+    def pdf(self, scores: _Array) -> npt.NDArray[np.float64]: ...
+    @overload
+    def pdf(self, scores: _Scores) -> list[float]: ...
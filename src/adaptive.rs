@@ -0,0 +1,193 @@
+//! Adjust the selection pressure each generation to track a target
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{
+    Boltzmann, MateSelection, Percentile, PowerScaled, RankedGaussian, RankedGeometric,
+    RankedLinear, SelectionError, Tournament,
+};
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Selection methods with a single numeric parameter which controls their
+/// selection pressure. Larger values always mean more selection pressure.
+pub trait Pressure {
+    /// The current value of the pressure parameter.
+    fn pressure(&self) -> f64;
+
+    /// Change the pressure parameter. The value is within the bounds.
+    fn set_pressure(&mut self, pressure: f64);
+
+    /// The smallest and largest valid values of the pressure parameter.
+    fn pressure_bounds(&self) -> (f64, f64);
+}
+
+/// The tournament size, rounded to the nearest whole number.
+impl Pressure for Tournament {
+    fn pressure(&self) -> f64 {
+        self.0 as f64
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure.round() as usize;
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (1.0, f64::INFINITY)
+    }
+}
+
+/// The fraction of the population which is denied the chance to mate.
+impl Pressure for Percentile {
+    fn pressure(&self) -> f64 {
+        self.0
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure;
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+/// The selection pressure argument.
+impl Pressure for RankedLinear {
+    fn pressure(&self) -> f64 {
+        self.0
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure;
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+/// The probability of selecting the best individual, which excludes the
+/// bounds zero and one.
+impl Pressure for RankedGeometric {
+    fn pressure(&self) -> f64 {
+        self.0
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+/// The inverse of the standard deviation.
+impl Pressure for RankedGaussian {
+    fn pressure(&self) -> f64 {
+        self.0.recip()
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure.max(f64::MIN_POSITIVE).recip().min(f64::MAX);
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+/// The inverse of the temperature.
+impl Pressure for Boltzmann {
+    fn pressure(&self) -> f64 {
+        self.0.recip()
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure.max(f64::MIN_POSITIVE).recip().min(f64::MAX);
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+/// The exponent which the scores are raised to.
+impl Pressure for PowerScaled {
+    fn pressure(&self) -> f64 {
+        self.0
+    }
+    fn set_pressure(&mut self, pressure: f64) {
+        self.0 = pressure.clamp(f64::MIN_POSITIVE, f64::MAX);
+    }
+    fn pressure_bounds(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+/// Feedback controller which adjusts the selection pressure of the inner
+/// method once per generation, so that an observed signal tracks a target.
+///
+/// The signal is measured by the caller, and it must increase with the
+/// selection pressure, for example the [loss of diversity](MateSelection::loss_of_diversity)
+/// or the [selection intensity](MateSelection::selection_intensity). For
+/// signals which decrease with the selection pressure, such as the diversity
+/// of the population, negate both the signal and the target.
+///
+/// The pressure is changed in proportion to the error between the target and
+/// the signal. It is stored separately from the inner method, so that small
+/// changes accumulate even when the inner method rounds its parameter.
+///
+/// Argument "**inner**" is the method for selecting parents.
+///
+/// Argument "**target**" is the desired value of the signal.
+///
+/// Argument "**gain**" is the change in the pressure per unit of error.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AdaptivePressure<M> {
+    pub inner: M,
+    pub target: f64,
+    pub gain: f64,
+    level: f64,
+}
+
+impl<M: Pressure> AdaptivePressure<M> {
+    pub fn new(inner: M, target: f64, gain: f64) -> Self {
+        Self {
+            level: inner.pressure(),
+            inner,
+            target,
+            gain,
+        }
+    }
+
+    /// The current selection pressure, before rounding by the inner method.
+    pub fn pressure(&self) -> f64 {
+        self.level
+    }
+
+    /// Adjust the selection pressure after observing the signal for the
+    /// latest generation. Non-finite signals are ignored.
+    pub fn update(&mut self, signal: f64) {
+        if !signal.is_finite() {
+            return;
+        }
+        let (min, max) = self.inner.pressure_bounds();
+        self.level = (self.level + self.gain * (self.target - signal)).clamp(min, max);
+        self.inner.set_pressure(self.level);
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for AdaptivePressure<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.inner.select(rng, amount, scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        self.inner.select_slice(rng, amount, scores)
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.expected_offspring(amount, scores)
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        self.inner.loss_of_diversity(amount, scores)
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        self.inner.sample_weight_in_place(scores)
+    }
+}
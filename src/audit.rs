@@ -0,0 +1,109 @@
+//! Record every selection, so that a run can be replayed for debugging
+
+use crate::{MateSelection, MateSelectionKind};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::rngs::ChaCha12Rng;
+use rand::{RngExt, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Wrap a selection method and keep a log of every call to it, with enough
+/// information to replay the call later and get the same pairs.
+///
+/// Each call gets its own seed, which is drawn from the recorder's random
+/// number generator. The log does not contain the scores, only their hash,
+/// so the scores must be saved separately in order to replay a call.
+///
+/// Argument "**method**" is the method for selecting parents.
+///
+/// Argument "**seed**" initializes the random number generator.
+#[derive(Debug)]
+pub struct Recorder {
+    pub method: MateSelectionKind,
+    rng: ChaCha12Rng,
+    records: Vec<AuditRecord>,
+}
+
+/// A single call to [Recorder::pairs].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    /// Selection method, including its runtime state, from before the call.
+    pub method: MateSelectionKind,
+    /// Seed of the random number generator for this call.
+    pub seed: u64,
+    /// Number of pairs which were requested.
+    pub amount: usize,
+    /// Hash of the scores, see [hash_scores].
+    pub scores_hash: u64,
+    /// The pairs which were returned.
+    pub pairs: Vec<[usize; 2]>,
+}
+
+impl Recorder {
+    pub fn new(method: MateSelectionKind, seed: u64) -> Self {
+        Self {
+            method,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            records: vec![],
+        }
+    }
+
+    /// Choose multiple weighted pairs, and record the call.
+    /// See [MateSelection::pairs].
+    pub fn pairs(&mut self, amount: usize, scores: &[f64]) -> Vec<[usize; 2]> {
+        let seed = self.rng.random();
+        let method = self.method.clone();
+        let rng = &mut ChaCha12Rng::seed_from_u64(seed);
+        let pairs = self.method.pairs_slice(rng, amount, scores);
+        self.records.push(AuditRecord {
+            method,
+            seed,
+            amount,
+            scores_hash: hash_scores(scores),
+            pairs: pairs.clone(),
+        });
+        pairs
+    }
+
+    /// All of the calls so far, from oldest to newest.
+    pub fn records(&self) -> &[AuditRecord] {
+        &self.records
+    }
+
+    /// Remove and return all of the records.
+    pub fn take_records(&mut self) -> Vec<AuditRecord> {
+        core::mem::take(&mut self.records)
+    }
+}
+
+impl AuditRecord {
+    /// Repeat the call with the same method, seed and scores.
+    ///
+    /// Returns None if the scores are not the same as the recorded scores.
+    pub fn replay(&self, scores: &[f64]) -> Option<Vec<[usize; 2]>> {
+        if hash_scores(scores) != self.scores_hash {
+            return None;
+        }
+        let rng = &mut ChaCha12Rng::seed_from_u64(self.seed);
+        Some(self.method.pairs_slice(rng, self.amount, scores))
+    }
+
+    /// Check that replaying the call returns the recorded pairs.
+    pub fn verify(&self, scores: &[f64]) -> bool {
+        self.replay(scores).as_ref() == Some(&self.pairs)
+    }
+}
+
+/// Hash a list of scores, using the 64-bit FNV-1a hash of their bits. Unlike
+/// the standard library's hasher, this is the same on every platform and in
+/// every version, so that saved logs can be checked later.
+pub fn hash_scores(scores: &[f64]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for x in scores {
+        for byte in x.to_bits().to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
@@ -0,0 +1,251 @@
+//! Command line tool for choosing parents, for use in shell pipelines.
+//!
+//! Install with: `cargo install mate_selection --features cli`
+
+use mate_selection::{MateSelection, MateSelectionKind};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io::Read;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: mate_selection <SPEC> <AMOUNT> [OPTIONS] < SCORES
+
+Read the scores from stdin, as CSV or as a JSON array of numbers, and print
+AMOUNT pairs of parents which are chosen by the selection method SPEC, for
+example \"ranked_linear(0.5)\" or \"elitist(1, tournament(2))\". The parents
+are printed as indices into the list of scores.
+
+Options:
+  --format <csv|json>  Output format, defaults to the input format
+  --seed <N>           Seed the random number generator
+  --select             Print AMOUNT individuals instead of pairs
+  -h, --help           Print this message
+";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+#[derive(Debug)]
+struct Args {
+    method: MateSelectionKind,
+    amount: usize,
+    format: Option<Format>,
+    seed: Option<u64>,
+    select: bool,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let mut input = String::new();
+    if let Err(error) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("error: {error}");
+        return ExitCode::FAILURE;
+    }
+    let (scores, input_format) = match parse_scores(&input) {
+        Ok(scores) => scores,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let rng = &mut match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => rand::make_rng(),
+    };
+    let format = args.format.unwrap_or(input_format);
+    let output = if args.select {
+        args.method
+            .try_select(rng, args.amount, scores)
+            .map(|selected| format_selected(&selected, format))
+    } else {
+        args.method
+            .try_pairs(rng, args.amount, scores)
+            .map(|pairs| format_pairs(&pairs, format))
+    };
+    match output {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns None if the user asked for help.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut positional = vec![];
+    let mut format = None;
+    let mut seed = None;
+    let mut select = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--select" => select = true,
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("csv") => Some(Format::Csv),
+                    Some("json") => Some(Format::Json),
+                    _ => return Err("--format must be csv or json".to_string()),
+                }
+            }
+            "--seed" => {
+                let value = args.next().unwrap_or_default();
+                let value = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed \"{value}\""))?;
+                seed = Some(value);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option \"{arg}\"")),
+            _ => positional.push(arg),
+        }
+    }
+    let [spec, amount] = positional.as_slice() else {
+        return Err("expected two arguments, SPEC and AMOUNT".to_string());
+    };
+    let method = spec.parse().map_err(|error| format!("{error}"))?;
+    let amount = amount
+        .parse()
+        .map_err(|_| format!("invalid amount \"{amount}\""))?;
+    Ok(Some(Args {
+        method,
+        amount,
+        format,
+        seed,
+        select,
+    }))
+}
+
+/// Parse a JSON array of numbers, or else numbers separated by commas and
+/// line breaks. The scores must be finite numbers, as in JSON.
+fn parse_scores(input: &str) -> Result<(Vec<f64>, Format), String> {
+    let input = input.trim();
+    let (items, format) = match input.strip_prefix('[') {
+        Some(array) => {
+            let array = array
+                .strip_suffix(']')
+                .ok_or("expected \"]\" at the end of the JSON array")?;
+            (array, Format::Json)
+        }
+        None => (input, Format::Csv),
+    };
+    let scores = items
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.parse::<f64>() {
+            Ok(score) if score.is_finite() => Ok(score),
+            _ => Err(format!("invalid score \"{item}\"")),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((scores, format))
+}
+
+fn format_pairs(pairs: &[[usize; 2]], format: Format) -> String {
+    match format {
+        Format::Csv => pairs.iter().map(|[a, b]| format!("{a},{b}\n")).collect(),
+        Format::Json => {
+            let pairs: Vec<String> = pairs.iter().map(|[a, b]| format!("[{a},{b}]")).collect();
+            format!("[{}]\n", pairs.join(","))
+        }
+    }
+}
+
+fn format_selected(selected: &[usize], format: Format) -> String {
+    match format {
+        Format::Csv => selected.iter().map(|x| format!("{x}\n")).collect(),
+        Format::Json => {
+            let selected: Vec<String> = selected.iter().map(usize::to_string).collect();
+            format!("[{}]\n", selected.join(","))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn arguments() {
+        let parsed = args(&["tournament(2)", "5", "--seed", "7", "--select"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.method, "tournament(2)".parse().unwrap());
+        assert_eq!(parsed.amount, 5);
+        assert_eq!(parsed.format, None);
+        assert_eq!(parsed.seed, Some(7));
+        assert!(parsed.select);
+        let parsed = args(&["random()", "1", "--format", "json"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.format, Some(Format::Json));
+        assert!(!parsed.select);
+        assert!(args(&["random()", "1", "--help"]).unwrap().is_none());
+        // Argument errors.
+        assert_eq!(
+            args(&["random()"]).unwrap_err(),
+            "expected two arguments, SPEC and AMOUNT"
+        );
+        assert_eq!(
+            args(&["random()", "x"]).unwrap_err(),
+            "invalid amount \"x\""
+        );
+        assert!(args(&["nonsense", "1"]).is_err());
+        assert_eq!(
+            args(&["random()", "1", "--seed"]).unwrap_err(),
+            "invalid seed \"\""
+        );
+        assert_eq!(
+            args(&["random()", "1", "--format", "xml"]).unwrap_err(),
+            "--format must be csv or json"
+        );
+        assert_eq!(
+            args(&["random()", "1", "--verbose"]).unwrap_err(),
+            "unknown option \"--verbose\""
+        );
+    }
+
+    #[test]
+    fn scores() {
+        assert_eq!(
+            parse_scores("1, 2.5\n-3\n"),
+            Ok((vec![1.0, 2.5, -3.0], Format::Csv))
+        );
+        assert_eq!(
+            parse_scores(" [1,2, 3e2]\n"),
+            Ok((vec![1.0, 2.0, 300.0], Format::Json))
+        );
+        assert_eq!(parse_scores("[]"), Ok((vec![], Format::Json)));
+        assert_eq!(
+            parse_scores("[1, 2"),
+            Err("expected \"]\" at the end of the JSON array".to_string())
+        );
+        assert_eq!(parse_scores("1, x"), Err("invalid score \"x\"".to_string()));
+        // Non-finite numbers are not valid JSON.
+        for token in ["NaN", "inf", "-infinity"] {
+            assert_eq!(
+                parse_scores(&format!("[1, {token}]")),
+                Err(format!("invalid score \"{token}\""))
+            );
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Reusable memory for choosing parents without allocating
+
+use crate::{reduce_repeats, MateSelection};
+use alloc::vec::Vec;
+use rand::prelude::*;
+
+/// Reusable memory for [MateSelection::select_into] and [MateSelection::pairs_into].
+///
+/// Keep one buffer for the whole run of the genetic algorithm. After the
+/// buffer has grown to the size of the population, the selection methods
+/// which sample by weight do not allocate any more memory.
+#[derive(Debug, Default, Clone)]
+pub struct SelectionBuffer {
+    weights: Vec<f64>,
+    order: Vec<usize>,
+    cumulative: Vec<f64>,
+    pub(crate) parents: Vec<usize>,
+}
+
+impl SelectionBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the sampling weights which were used by the most recent selection.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Transform the scores into sampling weights, in place in this buffer,
+    /// and then choose from them with stochastic universal sampling.
+    pub(crate) fn select_weighted<R, M>(
+        &mut self,
+        method: &M,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        output: &mut Vec<usize>,
+    ) where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        output.clear();
+        if amount == 0 {
+            return;
+        } else {
+            assert!(!scores.is_empty());
+        }
+        self.weights.clear();
+        self.weights.extend_from_slice(scores);
+        method.sample_weight_in_place(&mut self.weights);
+        self.choose_multiple_weighted(rng, amount, output);
+    }
+
+    /// Choose pairs from the parents of [MateSelection::select_into], reusing
+    /// the memory in this buffer.
+    pub(crate) fn pairs_from_select<R, M>(
+        &mut self,
+        method: &M,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        output: &mut Vec<[usize; 2]>,
+    ) where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        let mut parents = core::mem::take(&mut self.parents);
+        method.select_into(rng, amount * 2, scores, self, &mut parents);
+        reduce_repeats(&mut parents);
+        output.clear();
+        output.extend(parents.chunks_exact(2).map(|pair| [pair[0], pair[1]]));
+        self.parents = parents;
+    }
+
+    /// Copy the weights into this buffer and then choose from them, for builds
+    /// without std.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn choose_from_weights<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        amount: usize,
+        weights: &[f64],
+        output: &mut Vec<usize>,
+    ) {
+        self.weights.clear();
+        self.weights.extend_from_slice(weights);
+        self.choose_multiple_weighted(rng, amount, output);
+    }
+
+    /// Same as [stochastic_universal_sampling::choose_multiple_weighted] but
+    /// with the weights in this buffer.
+    fn choose_multiple_weighted<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        amount: usize,
+        output: &mut Vec<usize>,
+    ) {
+        let mut max_weight: f64 = 0.0;
+        for (i, &w) in self.weights.iter().enumerate() {
+            assert!(
+                w.is_finite() && w >= 0.0,
+                "invalid weight: weights[{i}] = {w} (must be finite and >= 0)"
+            );
+            max_weight = max_weight.max(w);
+        }
+        // If every weight is zero then fall back to uniform sampling.
+        if max_weight == 0.0 {
+            self.choose_multiple(rng, amount, self.weights.len(), output);
+            return;
+        }
+        // Shuffle the input order, so that the position of an individual does
+        // not influence which individuals are chosen together.
+        self.order.clear();
+        self.order.extend(0..self.weights.len());
+        self.order.shuffle(rng);
+        // Rescale so the largest weight is one, to avoid overflow and underflow.
+        self.cumulative.clear();
+        let mut running_total = 0.0;
+        for &index in &self.order {
+            running_total += self.weights[index] / max_weight;
+            self.cumulative.push(running_total);
+        }
+        // Discard any zero weight individuals at the end of the shuffled order.
+        let mut range_end = self.cumulative.len();
+        while range_end > 0 && self.cumulative[range_end - 1] >= running_total {
+            range_end -= 1;
+        }
+        let arm_spacing = running_total / (amount as f64);
+        let arm_offset = rng.random::<f64>() * arm_spacing;
+        let mut index = 0;
+        for arm in 0..amount {
+            let arm = (arm as f64) * arm_spacing + arm_offset;
+            while index < range_end && self.cumulative[index] <= arm {
+                index += 1;
+            }
+            output.push(self.order[index]);
+        }
+        // Break up the runs of repeated individuals.
+        output.shuffle(rng);
+    }
+
+    /// Same as [stochastic_universal_sampling::choose_multiple] but without
+    /// allocating.
+    pub(crate) fn choose_multiple<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        amount: usize,
+        items: usize,
+        output: &mut Vec<usize>,
+    ) {
+        while output.len() < amount {
+            let num_samples = amount - output.len();
+            if num_samples >= items {
+                output.extend(0..items);
+            } else {
+                // Partial Fisher-Yates shuffle, to choose without replacement.
+                self.order.clear();
+                self.order.extend(0..items);
+                for i in 0..num_samples {
+                    let j = rng.random_range(i..items);
+                    self.order.swap(i, j);
+                }
+                output.extend_from_slice(&self.order[..num_samples]);
+            }
+        }
+        output.shuffle(rng);
+    }
+}
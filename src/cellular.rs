@@ -0,0 +1,177 @@
+//! Cellular evolutionary algorithms, with spatially structured populations
+
+use crate::{sus, MateSelection, SelectionError};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Cellular selection places each individual on a cell of a spatial topology,
+/// and each individual only mates with its neighbors.
+///
+/// Every individual is paired with a mate which is chosen from its
+/// neighborhood using the inner method. If the number of pairs is not a
+/// multiple of the population size, then the remaining cells are chosen at
+/// random.
+///
+/// Argument "**inner**" is the method for selecting mates from each
+/// neighborhood.
+///
+/// Argument "**neighborhood**" defines which individuals are neighbors. This
+/// can be a [Grid] or a callback which returns the neighbors of an individual.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq)]
+pub struct Cellular<M, N = Grid> {
+    pub inner: M,
+    pub neighborhood: N,
+}
+
+/// Defines the spatial structure of the population.
+pub trait Neighborhood {
+    /// Find the neighbors of the given individual, not including itself.
+    fn neighbors(&self, individual: usize, population: usize) -> Vec<usize>;
+}
+
+impl<F: Fn(usize) -> Vec<usize>> Neighborhood for F {
+    fn neighbors(&self, individual: usize, _population: usize) -> Vec<usize> {
+        self(individual)
+    }
+}
+
+/// Two dimensional toroidal grid, with the individuals in row-major order.
+///
+/// Argument "**width**" is the number of columns in the grid. The population
+/// size must be a multiple of the width.
+///
+/// Argument "**radius**" is the maximum distance to a neighbor.
+///
+/// Argument "**shape**" is how distance is measured on the grid.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Grid {
+    pub width: usize,
+    pub radius: usize,
+    pub shape: GridShape,
+}
+
+/// How distance is measured on a [Grid].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GridShape {
+    /// Manhattan distance. With a radius of one, each cell has four neighbors.
+    #[default]
+    VonNeumann,
+    /// Chebyshev distance. With a radius of one, each cell has eight neighbors.
+    Moore,
+}
+
+impl Neighborhood for Grid {
+    fn neighbors(&self, individual: usize, population: usize) -> Vec<usize> {
+        assert!(self.width > 0, "argument \"width\" is less than one");
+        assert!(
+            population.is_multiple_of(self.width),
+            "population size is not a multiple of the grid width"
+        );
+        let width = self.width as isize;
+        let height = (population / self.width) as isize;
+        let row = individual as isize / width;
+        let col = individual as isize % width;
+        let radius = self.radius as isize;
+        let mut neighbors = Vec::new();
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let distance = match self.shape {
+                    GridShape::VonNeumann => dx.abs() + dy.abs(),
+                    GridShape::Moore => dx.abs().max(dy.abs()),
+                };
+                if distance == 0 || distance > radius {
+                    continue;
+                }
+                let y = (row + dy).rem_euclid(height);
+                let x = (col + dx).rem_euclid(width);
+                neighbors.push((y * width + x) as usize);
+            }
+        }
+        // Small grids wrap around onto themselves.
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors.retain(|&x| x != individual);
+        neighbors
+    }
+}
+
+impl<M, N: Neighborhood> Cellular<M, N> {
+    pub fn new(inner: M, neighborhood: N) -> Self {
+        Self {
+            inner,
+            neighborhood,
+        }
+    }
+
+    /// Choose a mate for the given individual from its neighborhood.
+    fn mate<R>(&self, rng: &mut R, individual: usize, scores: &[f64]) -> usize
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R>,
+    {
+        let neighbors = self.neighborhood.neighbors(individual, scores.len());
+        if neighbors.is_empty() {
+            return individual;
+        }
+        let local_scores = neighbors.iter().map(|&x| scores[x]).collect();
+        neighbors[self.inner.select(rng, 1, local_scores)[0]]
+    }
+}
+
+impl<M: core::fmt::Debug, N> core::fmt::Debug for Cellular<M, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Cellular")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, M, N> MateSelection<R> for Cellular<M, N>
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R>,
+    N: Neighborhood,
+{
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.inner.validate()
+    }
+
+    /// The first parent of each pair is the cell, and the second parent is its
+    /// mate from the neighborhood.
+    fn pairs(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        let cells = sus::choose_multiple(rng, amount, scores.len());
+        let mut pairs: Vec<[usize; 2]> = cells
+            .into_iter()
+            .map(|cell| [cell, self.mate(rng, cell, &scores)])
+            .collect();
+        pairs.shuffle(rng);
+        pairs
+    }
+
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        let mut selected = self.pairs(rng, amount.div_ceil(2), scores).concat();
+        selected.truncate(amount);
+        selected
+    }
+
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        // Every cell is the first parent once, and distributes the second
+        // parent among its neighbors.
+        let mut weights = vec![1.0; scores.len()];
+        for cell in 0..scores.len() {
+            let neighbors = self.neighborhood.neighbors(cell, scores.len());
+            if neighbors.is_empty() {
+                weights[cell] += 1.0;
+                continue;
+            }
+            let local_scores = neighbors.iter().map(|&x| scores[x]).collect();
+            for (&x, p) in neighbors.iter().zip(self.inner.pdf(local_scores)) {
+                weights[x] += p;
+            }
+        }
+        scores.copy_from_slice(&weights);
+    }
+}
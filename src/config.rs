@@ -0,0 +1,129 @@
+//! Bundle all of the settings for choosing mates into one object
+
+use crate::{vec_to_pairs, Elitist, MateSelection, MateSelectionKind, PairingPolicy, Random};
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Decides whether an individual can mate with itself.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum SelfMating {
+    /// Individuals can mate with themselves. With random pairing, the parents
+    /// are paired in the order that they're selected.
+    Allow,
+
+    /// Try to avoid mating individuals with themselves, but permit it when
+    /// there are too few eligible individuals. This is the default behavior of
+    /// [MateSelection::pairs].
+    #[default]
+    Avoid,
+
+    /// Never mate an individual with itself. Any such pairs are discarded and
+    /// redrawn. If there are too few eligible individuals, then this gives up
+    /// and returns fewer pairs than were requested.
+    Forbid,
+}
+
+/// All of the settings for choosing mates, which can be stored and
+/// serialized as a single object.
+///
+/// Argument "**strategy**" is the method for selecting the parents.
+///
+/// Argument "**pairs**" is the number of pairs to choose each generation.
+///
+/// Argument "**self mating**" decides whether individuals can mate with
+/// themselves.
+///
+/// Argument "**elitism**" is the number of elite individuals which are always
+/// selected at least once, see [Elitist]. The monogamous pairing policy
+/// samples without replacement and ignores this.
+///
+/// Argument "**pairing**" decides which of the selected parents mate together.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SelectionConfig {
+    pub strategy: MateSelectionKind,
+    pub pairs: usize,
+    pub self_mating: SelfMating,
+    pub elitism: usize,
+    pub pairing: PairingPolicy,
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        Self::new(MateSelectionKind::Random(Random))
+    }
+}
+
+impl SelectionConfig {
+    pub fn new(strategy: MateSelectionKind) -> Self {
+        Self {
+            strategy,
+            pairs: 0,
+            self_mating: SelfMating::Avoid,
+            elitism: 0,
+            pairing: PairingPolicy::Random,
+        }
+    }
+
+    /// Set the number of pairs to choose each generation.
+    pub fn with_pairs(mut self, pairs: usize) -> Self {
+        self.pairs = pairs;
+        self
+    }
+
+    /// Set whether individuals can mate with themselves.
+    pub fn with_self_mating(mut self, self_mating: SelfMating) -> Self {
+        self.self_mating = self_mating;
+        self
+    }
+
+    /// Set the number of elite individuals which are always selected.
+    pub fn with_elitism(mut self, elitism: usize) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    /// Set how the selected parents are paired together.
+    pub fn with_pairing(mut self, pairing: PairingPolicy) -> Self {
+        self.pairing = pairing;
+        self
+    }
+
+    /// Choose the mating pairs for one generation.
+    ///
+    /// * Argument "scores" is a list containing the reproductive fitness of each individual.
+    ///
+    /// * Returns a list of pairs of parents to mate together.
+    ///   The parents are specified as indices into the scores list.
+    pub fn run<R: Rng + ?Sized>(&self, rng: &mut R, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        let method = Elitist {
+            keep: self.elitism,
+            inner: &self.strategy,
+        };
+        match self.self_mating {
+            SelfMating::Allow if self.pairing == PairingPolicy::Random => {
+                vec_to_pairs(method.select(rng, self.pairs * 2, scores))
+            }
+            SelfMating::Allow | SelfMating::Avoid => {
+                method.pairs_with(rng, self.pairs, scores, &self.pairing)
+            }
+            SelfMating::Forbid => {
+                const MAX_ATTEMPTS: usize = 10;
+                let mut pairs = Vec::with_capacity(self.pairs);
+                // Give up after several consecutive attempts which find no new pairs.
+                let mut attempts = 0;
+                while pairs.len() < self.pairs && attempts < MAX_ATTEMPTS {
+                    attempts += 1;
+                    let amount = self.pairs - pairs.len();
+                    for [a, b] in method.pairs_with(rng, amount, scores.clone(), &self.pairing) {
+                        if a != b {
+                            pairs.push([a, b]);
+                            attempts = 0;
+                        }
+                    }
+                }
+                pairs
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Selection probabilities as point series, for plotting
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The probability of selecting each individual, sorted from the best to the
+/// worst score. See [MateSelection::selection_curve](crate::MateSelection::selection_curve).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SelectionCurve {
+    pub points: Vec<CurvePoint>,
+}
+
+/// One individual of a [SelectionCurve].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct CurvePoint {
+    /// Position in the population, where rank one is the best individual.
+    pub rank: usize,
+    /// Index of the individual in the scores.
+    pub index: usize,
+    pub score: f64,
+    pub probability: f64,
+}
+
+impl SelectionCurve {
+    pub(crate) fn new(scores: &[f64], pdf: &[f64]) -> Self {
+        let points = crate::argsort_best(scores)
+            .into_iter()
+            .enumerate()
+            .map(|(rank, index)| CurvePoint {
+                rank: rank + 1,
+                index,
+                score: scores[index],
+                probability: pdf[index],
+            })
+            .collect();
+        Self { points }
+    }
+
+    /// Points of `(rank, probability)`, from the best to the worst rank.
+    pub fn by_rank(&self) -> Vec<(usize, f64)> {
+        self.points
+            .iter()
+            .map(|p| (p.rank, p.probability))
+            .collect()
+    }
+
+    /// Points of `(score, probability)`, from the best to the worst score.
+    pub fn by_score(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|p| (p.score, p.probability))
+            .collect()
+    }
+
+    /// Format the curve as CSV, with a header row and one row per individual.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rank,index,score,probability\n");
+        for p in &self.points {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                p.rank, p.index, p.score, p.probability
+            ));
+        }
+        csv
+    }
+}
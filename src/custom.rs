@@ -0,0 +1,91 @@
+//! Selection methods defined by callbacks
+
+use crate::{sus, MateSelection};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+
+/// The type of the optional selection callback of [Custom].
+pub type SelectFn = fn(&mut dyn Rng, usize, &[f64]) -> Vec<usize>;
+
+/// Custom selection method, defined by a callback which transforms the scores
+/// into sampling weights. This is for trying out new selection methods without
+/// writing a new type.
+///
+/// Argument "**weight**" is a callback which transforms the scores into
+/// sampling weights, see [MateSelection::sample_weight]. It must return one
+/// weight for each score, and the weights must be finite and non-negative.
+///
+/// Argument "**select**" is an optional callback which chooses the parents,
+/// see [MateSelection::select]. It is given the random number generator, the
+/// number of parents to choose, and the scores. If missing, then the parents
+/// are chosen by stochastic universal sampling of the weights.
+#[derive(Copy, Clone)]
+pub struct Custom<W, S = SelectFn> {
+    pub weight: W,
+    pub select: Option<S>,
+}
+
+impl<W> Custom<W>
+where
+    W: Fn(&[f64]) -> Vec<f64>,
+{
+    pub fn new(weight: W) -> Self {
+        Self {
+            weight,
+            select: None,
+        }
+    }
+}
+
+impl<W, S> Custom<W, S> {
+    /// Set the callback which chooses the parents.
+    pub fn with_select<T>(self, select: T) -> Custom<W, T>
+    where
+        T: Fn(&mut dyn Rng, usize, &[f64]) -> Vec<usize>,
+    {
+        Custom {
+            weight: self.weight,
+            select: Some(select),
+        }
+    }
+}
+
+impl<W, S> core::fmt::Debug for Custom<W, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Custom").finish_non_exhaustive()
+    }
+}
+
+impl<R, W, S> MateSelection<R> for Custom<W, S>
+where
+    R: Rng + ?Sized,
+    W: Fn(&[f64]) -> Vec<f64>,
+    S: Fn(&mut dyn Rng, usize, &[f64]) -> Vec<usize>,
+{
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, mut rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        if let Some(select) = &self.select {
+            return select(&mut rng, amount, scores);
+        }
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let weights = (self.weight)(scores);
+        sus::choose_multiple_weighted(rng, amount, &weights)
+    }
+    fn sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        (self.weight)(&scores)
+    }
+    fn sample_weight_slice(&self, scores: &[f64]) -> Vec<f64> {
+        (self.weight)(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let weights = (self.weight)(scores);
+        scores.copy_from_slice(&weights);
+    }
+}
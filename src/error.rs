@@ -0,0 +1,63 @@
+//! Errors for invalid configurations and degenerate inputs
+
+use alloc::string::String;
+
+/// The reasons that a selection method can fail.
+///
+/// The fallible methods, such as [MateSelection::try_select](crate::MateSelection::try_select),
+/// return these errors instead of panicking.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectionError {
+    /// An argument of the selection method is invalid.
+    InvalidArgument {
+        /// Name of the argument.
+        name: &'static str,
+        /// Explanation of why the argument is invalid.
+        reason: &'static str,
+    },
+
+    /// Can not choose from an empty population.
+    EmptyPopulation,
+
+    /// The selection method transformed the scores into an invalid sampling
+    /// weight, which is either negative or not finite.
+    InvalidWeight { index: usize, weight: f64 },
+
+    /// None of the individuals have a positive sampling weight. The infallible
+    /// methods select uniformly at random in this case.
+    NoneEligible,
+
+    /// The text could not be parsed into a selection method. Contains the
+    /// part of the text which is invalid.
+    InvalidSpecification(String),
+}
+
+impl SelectionError {
+    pub(crate) fn argument(name: &'static str, reason: &'static str) -> Self {
+        Self::InvalidArgument { name, reason }
+    }
+}
+
+impl core::fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::InvalidArgument { name, reason } => write!(f, "argument \"{name}\" {reason}"),
+            Self::EmptyPopulation => write!(f, "no data: can not choose from empty set"),
+            Self::InvalidWeight { index, weight } => write!(
+                f,
+                "invalid weight: weights[{index}] = {weight} (must be finite and >= 0)"
+            ),
+            Self::NoneEligible => write!(f, "none of the individuals are eligible to mate"),
+            Self::InvalidSpecification(spec) => write!(f, "invalid specification \"{spec}\""),
+        }
+    }
+}
+
+impl core::error::Error for SelectionError {}
+
+/// Panic with the error message, if there is an error.
+pub(crate) fn expect_valid(result: Result<(), SelectionError>) {
+    if let Err(error) = result {
+        panic!("{error}");
+    }
+}
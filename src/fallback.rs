@@ -0,0 +1,67 @@
+//! Switch to another selection method when there is no one to select
+
+use crate::{expect_valid, MateSelection, SelectionError};
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Try several selection methods in order, and use the first one which finds
+/// any eligible individuals, meaning any individuals with a positive sampling
+/// weight. If none of them do then the last method is used.
+///
+/// For example, [Normalized](crate::Normalized) selection with a high cutoff
+/// can exclude everyone from a population with poor scores, which would
+/// otherwise fall back to selecting everyone with uniform probability.
+///
+/// Argument "**methods**" is the list of selection methods, in order of
+/// preference. Must not be empty.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Fallback<M>(pub Vec<M>);
+
+impl<M> Fallback<M> {
+    /// Find the first method which has any eligible individuals.
+    pub fn active<R>(&self, scores: &[f64]) -> &M
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R>,
+    {
+        expect_valid(MateSelection::<R>::validate(self));
+        let (last, methods) = self.0.split_last().unwrap();
+        methods
+            .iter()
+            .find(|method| {
+                method
+                    .sample_weight_slice(scores)
+                    .iter()
+                    .any(|&w| w > 0.0 && w.is_finite())
+            })
+            .unwrap_or(last)
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Fallback<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        if self.0.is_empty() {
+            return Err(SelectionError::argument("methods", "is empty"));
+        }
+        self.0.iter().try_for_each(|method| method.validate())
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.active(&scores).select(rng, amount, scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        self.active(scores).select_slice(rng, amount, scores)
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        self.active(&scores).expected_offspring(amount, scores)
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        self.active(&scores).loss_of_diversity(amount, scores)
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.active(&scores).pdf(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        self.active(scores).sample_weight_in_place(scores)
+    }
+}
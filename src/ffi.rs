@@ -0,0 +1,218 @@
+//! C compatible interface, see the header file "include/mate_selection.h"
+//!
+//! The selection method is an opaque handle which is created from a text
+//! specification, see [MateSelectionKind]. Every handle owns a seeded random
+//! number generator. The scores are passed as a pointer and a length, and the
+//! results are written into a buffer which is allocated by the caller.
+
+use crate::{MateSelection, MateSelectionKind, SelectionError};
+use core::ffi::{c_char, CStr};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Opaque handle to a selection method and its random number generator.
+pub struct MsSelection {
+    method: MateSelectionKind,
+    rng: StdRng,
+}
+
+/// Result of a call into this library.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MsStatus {
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// The arguments or the scores are invalid, see [SelectionError](crate::SelectionError).
+    InvalidArgument = 2,
+    /// The selection method panicked.
+    Panic = 3,
+}
+
+/// Create a selection method from its text specification, for example
+/// `"ranked_linear(0.5)"` or `"elitist(1, tournament(2))"`. Returns null if
+/// the specification is invalid. The handle must be freed with
+/// [ms_selection_free].
+///
+/// # Safety
+///
+/// Argument "spec" must be a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ms_selection_new(spec: *const c_char, seed: u64) -> *mut MsSelection {
+    if spec.is_null() {
+        return core::ptr::null_mut();
+    }
+    let Ok(spec) = CStr::from_ptr(spec).to_str() else {
+        return core::ptr::null_mut();
+    };
+    match spec.parse::<MateSelectionKind>() {
+        Ok(method) => Box::into_raw(Box::new(MsSelection {
+            method,
+            rng: StdRng::seed_from_u64(seed),
+        })),
+        Err(_) => core::ptr::null_mut(),
+    }
+}
+
+/// Free a selection method. Null handles are ignored.
+///
+/// # Safety
+///
+/// Argument "handle" must be null or a handle from [ms_selection_new] which
+/// has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ms_selection_free(handle: *mut MsSelection) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Restart the random number generator from the given seed.
+///
+/// # Safety
+///
+/// Argument "handle" must be a valid handle from [ms_selection_new].
+#[no_mangle]
+pub unsafe extern "C" fn ms_selection_seed(handle: *mut MsSelection, seed: u64) -> MsStatus {
+    let Some(handle) = handle.as_mut() else {
+        return MsStatus::NullPointer;
+    };
+    handle.rng = StdRng::seed_from_u64(seed);
+    MsStatus::Ok
+}
+
+/// Choose multiple weighted pairs, see [MateSelection::pairs]. Writes
+/// `2 * amount` indices into "out_pairs", with the two parents of each pair
+/// next to each other.
+///
+/// # Safety
+///
+/// Argument "scores" must point to "num_scores" values, and "out_pairs" must
+/// have room for `2 * amount` values.
+#[no_mangle]
+pub unsafe extern "C" fn ms_selection_pairs(
+    handle: *mut MsSelection,
+    scores: *const f64,
+    num_scores: usize,
+    amount: usize,
+    out_pairs: *mut usize,
+) -> MsStatus {
+    let Some(handle) = handle.as_mut() else {
+        return MsStatus::NullPointer;
+    };
+    guard(|| {
+        let num_parents = amount
+            .checked_mul(2)
+            .ok_or(SelectionError::argument("amount", "is too large"))?;
+        let (Some(scores), Some(out)) = (
+            input_slice(scores, num_scores),
+            output_slice(out_pairs, num_parents),
+        ) else {
+            return Err(MsStatus::NullPointer);
+        };
+        let rng = &mut handle.rng;
+        let pairs = handle.method.try_pairs(rng, amount, scores.to_vec())?;
+        out.copy_from_slice(pairs.as_flattened());
+        Ok(())
+    })
+}
+
+/// Choose multiple weighted, see [MateSelection::select]. Writes "amount"
+/// indices into "out_selected".
+///
+/// # Safety
+///
+/// Argument "scores" must point to "num_scores" values, and "out_selected"
+/// must have room for "amount" values.
+#[no_mangle]
+pub unsafe extern "C" fn ms_selection_select(
+    handle: *mut MsSelection,
+    scores: *const f64,
+    num_scores: usize,
+    amount: usize,
+    out_selected: *mut usize,
+) -> MsStatus {
+    let Some(handle) = handle.as_mut() else {
+        return MsStatus::NullPointer;
+    };
+    guard(|| {
+        let (Some(scores), Some(out)) = (
+            input_slice(scores, num_scores),
+            output_slice(out_selected, amount),
+        ) else {
+            return Err(MsStatus::NullPointer);
+        };
+        let rng = &mut handle.rng;
+        let selected = handle.method.try_select(rng, amount, scores.to_vec())?;
+        out.copy_from_slice(&selected);
+        Ok(())
+    })
+}
+
+/// Probability distribution function, see [MateSelection::pdf]. Writes
+/// "num_scores" probabilities into "out_pdf".
+///
+/// # Safety
+///
+/// Arguments "scores" and "out_pdf" must both point to "num_scores" values.
+#[no_mangle]
+pub unsafe extern "C" fn ms_selection_pdf(
+    handle: *const MsSelection,
+    scores: *const f64,
+    num_scores: usize,
+    out_pdf: *mut f64,
+) -> MsStatus {
+    let Some(handle) = handle.as_ref() else {
+        return MsStatus::NullPointer;
+    };
+    guard(|| {
+        let (Some(scores), Some(out)) = (
+            input_slice(scores, num_scores),
+            output_slice(out_pdf, num_scores),
+        ) else {
+            return Err(MsStatus::NullPointer);
+        };
+        MateSelection::<StdRng>::validate(&handle.method)?;
+        let pdf = MateSelection::<StdRng>::pdf_slice(&handle.method, scores);
+        out.copy_from_slice(&pdf);
+        Ok(())
+    })
+}
+
+/// Null pointers are only allowed for empty slices.
+unsafe fn input_slice<'a, T>(data: *const T, len: usize) -> Option<&'a [T]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(core::slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn output_slice<'a, T>(data: *mut T, len: usize) -> Option<&'a mut [T]> {
+    if len == 0 {
+        Some(&mut [])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(core::slice::from_raw_parts_mut(data, len))
+    }
+}
+
+impl From<SelectionError> for MsStatus {
+    fn from(_: SelectionError) -> Self {
+        MsStatus::InvalidArgument
+    }
+}
+
+/// Convert errors and panics into status codes, since panics must not unwind
+/// into the caller.
+fn guard(f: impl FnOnce() -> Result<(), MsStatus>) -> MsStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => MsStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => MsStatus::Panic,
+    }
+}
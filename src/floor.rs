@@ -0,0 +1,83 @@
+//! Guarantee every individual a minimum chance to mate
+
+use crate::{expect_valid, sus, uniform_if_zero, MateSelection, SelectionError};
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Guarantee every individual a minimum probability of being selected, by
+/// mixing the distribution of the inner method with the uniform distribution.
+/// This preserves a trickle of mating chances for the low scoring
+/// individuals, as a defense against the total loss of diversity.
+///
+/// >   `probability(i) = epsilon + (1 - N * epsilon) * inner_probability(i)`  
+/// >   Where `N` is the population size.
+///
+/// The floor applies to every individual, including those with invalid (NaN)
+/// scores. If the inner method gives every individual a weight of zero, then
+/// its distribution is treated as uniform. If the population is so small that
+/// `N * epsilon > 1` then every individual is equally likely to be selected.
+///
+/// Argument "**epsilon**" is the minimum probability of each individual. Must
+/// be in the range [0, 1].
+///
+/// Argument "**inner**" is the method for selecting parents.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Floor<M> {
+    pub epsilon: f64,
+    pub inner: M,
+}
+
+impl<M> Floor<M> {
+    pub fn new(epsilon: f64, inner: M) -> Self {
+        Self { epsilon, inner }
+    }
+
+    /// Fraction of the parents which are chosen uniformly at random.
+    fn uniform_fraction(&self, population: usize) -> f64 {
+        (self.epsilon * population as f64).min(1.0)
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Floor<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        if !(0.0..=1.0).contains(&self.epsilon) {
+            return Err(SelectionError::argument(
+                "epsilon",
+                "is out of bounds [0, 1]",
+            ));
+        }
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        expect_valid(MateSelection::<R>::validate(self));
+        let uniform = self.uniform_fraction(scores.len());
+        let num_uniform = sus::choose_multiple_weighted(rng, amount, &[1.0 - uniform, uniform])
+            .into_iter()
+            .filter(|&method| method == 1)
+            .count();
+        let mut selected = self.inner.select_slice(rng, amount - num_uniform, scores);
+        selected.append(&mut sus::choose_multiple(rng, num_uniform, scores.len()));
+        selected.shuffle(rng);
+        selected
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        expect_valid(MateSelection::<R>::validate(self));
+        let population = scores.len();
+        let uniform = self.uniform_fraction(population);
+        let mut pdf = self.inner.pdf(scores);
+        uniform_if_zero(&mut pdf);
+        for p in pdf.iter_mut() {
+            *p = (1.0 - uniform) * *p + uniform / population as f64;
+        }
+        pdf
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let pdf = MateSelection::<R>::pdf(self, scores.to_vec());
+        scores.copy_from_slice(&pdf);
+    }
+}
@@ -0,0 +1,99 @@
+//! Drive the whole generational loop of an evolutionary algorithm
+
+use crate::{SelectionConfig, SurvivorSelection};
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Runs one generation at a time: select the parents, breed and evaluate the
+/// offspring, and then choose the survivors.
+///
+/// The parents are chosen by the [SelectionConfig], which applies the
+/// elitism, self mating, and pairing settings. Each pair of parents has one
+/// child, so the number of offspring is the number of pairs in the config.
+///
+/// Argument "**selection**" chooses the parents.
+///
+/// Argument "**survivors**" chooses the next generation from the parents and
+/// the offspring, see [SurvivorSelection].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Generation<S> {
+    pub selection: SelectionConfig,
+    pub survivors: S,
+    /// Number of generations which have been run.
+    pub generation: usize,
+}
+
+/// What happened during one call to [Generation::step].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationStep {
+    /// Parents of each offspring, as indices into the previous population.
+    pub pairs: Vec<[usize; 2]>,
+    /// The new population, as indices into the previous population followed
+    /// by its offspring. See [SurvivorSelection::survivors].
+    pub survivors: Vec<usize>,
+}
+
+impl<S> Generation<S> {
+    pub fn new(selection: SelectionConfig, survivors: S) -> Self {
+        Self {
+            selection,
+            survivors,
+            generation: 0,
+        }
+    }
+
+    /// Run one generation, replacing the population and its scores with the
+    /// survivors.
+    ///
+    /// * Argument "population" contains the genome of each individual, and
+    ///   argument "scores" contains their reproductive fitness.
+    ///
+    /// * Argument "crossover" creates a child from two parents.
+    ///
+    /// * Argument "mutate" modifies a newly created child.
+    ///
+    /// * Argument "evaluate" computes the score of a newly created child.
+    pub fn step<R, T, C, M, E>(
+        &mut self,
+        rng: &mut R,
+        population: &mut Vec<T>,
+        scores: &mut Vec<f64>,
+        mut crossover: C,
+        mut mutate: M,
+        mut evaluate: E,
+    ) -> GenerationStep
+    where
+        R: Rng + ?Sized,
+        S: SurvivorSelection<R>,
+        C: FnMut(&mut R, &T, &T) -> T,
+        M: FnMut(&mut R, &mut T),
+        E: FnMut(&T) -> f64,
+    {
+        assert_eq!(
+            population.len(),
+            scores.len(),
+            "population and scores have different lengths"
+        );
+        let pairs = self.selection.run(rng, scores.clone());
+        let mut offspring = Vec::with_capacity(pairs.len());
+        let mut offspring_scores = Vec::with_capacity(pairs.len());
+        for &[a, b] in &pairs {
+            let mut child = crossover(rng, &population[a], &population[b]);
+            mutate(rng, &mut child);
+            offspring_scores.push(evaluate(&child));
+            offspring.push(child);
+        }
+        let survivors = self.survivors.survivors(rng, scores, &offspring_scores);
+        let mut combined: Vec<Option<T>> =
+            population.drain(..).chain(offspring).map(Some).collect();
+        let combined_scores: Vec<f64> = scores.drain(..).chain(offspring_scores).collect();
+        for &index in &survivors {
+            let individual = combined[index].take().expect("survivor was chosen twice");
+            population.push(individual);
+            scores.push(combined_scores[index]);
+        }
+        self.generation += 1;
+        GenerationStep { pairs, survivors }
+    }
+}
@@ -0,0 +1,115 @@
+//! Archive of the best individuals ever seen, for long-term elitism
+
+use crate::{cmp_score, reduce_repeats, sus, MateSelection};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Keeps the scores and IDs of the best individuals across all generations,
+/// and can add them to the pool of potential parents.
+///
+/// The IDs are chosen by the caller, and they identify individuals across
+/// generations. An individual which is offered more than once is only kept
+/// once, with its best score.
+///
+/// Argument "**capacity**" is the number of individuals to keep.
+///
+/// Argument "**weight**" multiplies the sampling weights of the members of the
+/// hall of fame, when they are selected together with the current population.
+/// At one, they are treated the same as the current population.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HallOfFame<T> {
+    pub capacity: usize,
+    pub weight: f64,
+    /// Scores and IDs of the members, from best to worst.
+    members: Vec<(f64, T)>,
+}
+
+/// A parent chosen by [HallOfFame::pairs].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Parent<T> {
+    /// Index into the scores of the current population.
+    Population(usize),
+    /// ID of a member of the hall of fame.
+    HallOfFame(T),
+}
+
+impl<T: Clone + PartialEq> HallOfFame<T> {
+    pub fn new(capacity: usize, weight: f64) -> Self {
+        Self {
+            capacity,
+            weight,
+            members: vec![],
+        }
+    }
+
+    /// Scores and IDs of the members, from best to worst.
+    pub fn members(&self) -> &[(f64, T)] {
+        &self.members
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Offer the individuals of a generation for membership. Invalid (NaN)
+    /// scores are never admitted.
+    pub fn update(&mut self, scores: &[f64], ids: &[T]) {
+        assert_eq!(
+            scores.len(),
+            ids.len(),
+            "scores and ids have different lengths"
+        );
+        for (&score, id) in scores.iter().zip(ids) {
+            if score.is_nan() {
+                continue;
+            }
+            match self.members.iter_mut().find(|(_, member)| member == id) {
+                Some(member) => member.0 = member.0.max(score),
+                None => self.members.push((score, id.clone())),
+            }
+        }
+        self.members.sort_by(|a, b| cmp_score(b.0, a.0));
+        self.members.truncate(self.capacity);
+    }
+
+    /// Choose multiple weighted pairs from the current population and the
+    /// members of the hall of fame together. See [MateSelection::pairs].
+    pub fn pairs<R, M>(
+        &self,
+        method: &M,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+    ) -> Vec<[Parent<T>; 2]>
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        let population = scores.len();
+        let mut pool = scores.to_vec();
+        pool.extend(self.members.iter().map(|(score, _)| *score));
+        let mut weights = method.sample_weight(pool);
+        for w in &mut weights[population..] {
+            *w *= self.weight;
+        }
+        let mut parents = sus::choose_multiple_weighted(rng, 2 * amount, &weights);
+        reduce_repeats(&mut parents);
+        let parent = |index: usize| {
+            if index < population {
+                Parent::Population(index)
+            } else {
+                Parent::HallOfFame(self.members[index - population].1.clone())
+            }
+        };
+        parents
+            .chunks_exact(2)
+            .map(|pair| [parent(pair[0]), parent(pair[1])])
+            .collect()
+    }
+}
@@ -0,0 +1,256 @@
+//! Island model with migration
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{argsort_best, MateSelection, SelectionError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The island model divides the population into several subpopulations,
+/// called islands or demes, which evolve independently of each other. Each
+/// island selects its own mating pairs using the inner method, and individuals
+/// only mate with other individuals on the same island. Periodically, some
+/// individuals migrate to the next island in a ring topology.
+///
+/// The population is divided into contiguous blocks of nearly equal size: the
+/// first island contains the first individuals of the scores list, and so on.
+/// The number of pairs allotted to each island is proportional to its size.
+///
+/// Argument "**inner**" is the method for selecting mates on each island.
+///
+/// Argument "**num islands**" is the number of subpopulations.
+///
+/// Argument "**interval**" is the number of generations between migrations.
+/// At zero there are no migrations.
+///
+/// Argument "**rate**" is the fraction of each island which migrates, in the
+/// range [0, 1].
+///
+/// Argument "**policy**" decides which individuals migrate.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Islands<M> {
+    pub inner: M,
+    pub num_islands: usize,
+    pub interval: u64,
+    pub rate: f64,
+    pub policy: MigrationPolicy,
+}
+
+/// Decides which individuals migrate to another island.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MigrationPolicy {
+    /// The best individuals on each island migrate.
+    #[default]
+    Best,
+    /// Randomly chosen individuals migrate.
+    Random,
+}
+
+/// An individual which moves from one island to another.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// Index into the scores list.
+    pub individual: usize,
+    /// Island which the individual is leaving.
+    pub from: usize,
+    /// Island which the individual is joining.
+    pub to: usize,
+}
+
+/// The outcome of one generation of the island model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IslandPlan {
+    /// Mating pairs for each island, as indices into the scores list.
+    pub pairs: Vec<Vec<[usize; 2]>>,
+    /// Individuals which should be copied to other islands.
+    pub migrations: Vec<Migration>,
+}
+
+impl<M> Islands<M> {
+    pub fn new(inner: M, num_islands: usize) -> Self {
+        Self {
+            inner,
+            num_islands,
+            interval: 0,
+            rate: 0.0,
+            policy: MigrationPolicy::Best,
+        }
+    }
+
+    /// Set how often and how many individuals migrate between islands.
+    pub fn with_migration(mut self, interval: u64, rate: f64, policy: MigrationPolicy) -> Self {
+        self.interval = interval;
+        self.rate = rate;
+        self.policy = policy;
+        self
+    }
+
+    /// Get the indices of the individuals on each island.
+    pub fn islands(&self, population: usize) -> Vec<Range<usize>> {
+        assert!(
+            self.num_islands > 0,
+            "argument \"num_islands\" is less than one"
+        );
+        let num = self.num_islands;
+        (0..num)
+            .map(|k| (k * population / num)..((k + 1) * population / num))
+            .collect()
+    }
+
+    /// Find which island an individual is on.
+    pub fn island_of(&self, population: usize, individual: usize) -> usize {
+        assert!(individual < population);
+        self.islands(population)
+            .iter()
+            .position(|island| island.contains(&individual))
+            .unwrap()
+    }
+
+    /// Divide the pairs among the islands, proportional to their size.
+    fn allot(&self, amount: usize, islands: &[Range<usize>]) -> Vec<usize> {
+        let population: usize = islands.iter().map(|island| island.len()).sum();
+        let mut allotment: Vec<usize> = islands
+            .iter()
+            .map(|island| amount * island.len() / population.max(1))
+            .collect();
+        // Distribute the remainder to the largest islands.
+        let mut remainder = amount - allotment.iter().sum::<usize>();
+        let mut order: Vec<usize> = (0..islands.len()).collect();
+        order.sort_by_key(|&k| core::cmp::Reverse(islands[k].len()));
+        for k in order.into_iter().cycle().take(islands.len() * 2) {
+            if remainder == 0 {
+                break;
+            }
+            if !islands[k].is_empty() {
+                allotment[k] += 1;
+                remainder -= 1;
+            }
+        }
+        allotment
+    }
+
+    /// Choose which individuals migrate during this generation.
+    pub fn migrations<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        generation: u64,
+        scores: &[f64],
+    ) -> Vec<Migration> {
+        assert!(
+            (0.0..=1.0).contains(&self.rate),
+            "argument \"rate\" is out of bounds [0, 1]"
+        );
+        let islands = self.islands(scores.len());
+        if self.interval == 0 || !generation.is_multiple_of(self.interval) || islands.len() < 2 {
+            return vec![];
+        }
+        let mut migrations = vec![];
+        for (from, island) in islands.iter().enumerate() {
+            let to = (from + 1) % islands.len();
+            let num_migrants = (self.rate * island.len() as f64).round() as usize;
+            let migrants: Vec<usize> = match self.policy {
+                MigrationPolicy::Best => argsort_best(&scores[island.clone()])
+                    .into_iter()
+                    .take(num_migrants)
+                    .collect(),
+                MigrationPolicy::Random => {
+                    rand::seq::index::sample(rng, island.len(), num_migrants).into_vec()
+                }
+            };
+            migrations.extend(migrants.into_iter().map(|local| Migration {
+                individual: island.start + local,
+                from,
+                to,
+            }));
+        }
+        migrations
+    }
+}
+
+impl<M> Islands<M> {
+    /// Run one generation of the island model: select the mating pairs on each
+    /// island and choose which individuals migrate.
+    pub fn plan<R>(
+        &self,
+        rng: &mut R,
+        generation: u64,
+        amount: usize,
+        scores: Vec<f64>,
+    ) -> IslandPlan
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R>,
+    {
+        let migrations = self.migrations(rng, generation, &scores);
+        let pairs = self.island_pairs(rng, amount, &scores);
+        IslandPlan { pairs, migrations }
+    }
+
+    fn island_pairs<R>(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<Vec<[usize; 2]>>
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R>,
+    {
+        let islands = self.islands(scores.len());
+        let allotment = self.allot(amount, &islands);
+        islands
+            .iter()
+            .zip(allotment)
+            .map(|(island, amount)| {
+                let mut pairs = self
+                    .inner
+                    .pairs(rng, amount, scores[island.clone()].to_vec());
+                for pair in pairs.iter_mut() {
+                    pair[0] += island.start;
+                    pair[1] += island.start;
+                }
+                pairs
+            })
+            .collect()
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Islands<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        if self.num_islands == 0 {
+            Err(SelectionError::argument("num_islands", "is less than one"))
+        } else if !(0.0..=1.0).contains(&self.rate) {
+            Err(SelectionError::argument("rate", "is out of bounds [0, 1]"))
+        } else {
+            self.inner.validate()
+        }
+    }
+
+    /// Mating pairs never cross between islands.
+    fn pairs(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        self.island_pairs(rng, amount, &scores).concat()
+    }
+
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        let islands = self.islands(scores.len());
+        let allotment = self.allot(amount, &islands);
+        let mut selected = Vec::with_capacity(amount);
+        for (island, amount) in islands.iter().zip(allotment) {
+            let local = self
+                .inner
+                .select(rng, amount, scores[island.clone()].to_vec());
+            selected.extend(local.into_iter().map(|x| x + island.start));
+        }
+        selected
+    }
+
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        // Each island's share of the sample is proportional to its size.
+        let population = scores.len() as f64;
+        for island in self.islands(scores.len()) {
+            let share = island.len() as f64 / population;
+            let pdf = self.inner.pdf_slice(&scores[island.clone()]);
+            for (x, p) in scores[island].iter_mut().zip(pdf) {
+                *x = p * share;
+            }
+        }
+    }
+}
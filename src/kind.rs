@@ -0,0 +1,343 @@
+//! A single concrete type for every selection method
+
+use crate::{
+    AnnealedBoltzmann, Best, Boltzmann, Capped, Constrained, Disruptive, Elitist, Inverse, Islands,
+    LinearScaled, MateSelection, MigrationPolicy, Normalized, Percentile, PowerScaled,
+    ProbabilisticTournament, Proportional, Random, RankedExponential, RankedExponentialBase,
+    RankedGaussian, RankedGeometric, RankedLinear, SelectionBuffer, SelectionError, SparseWeights,
+    TieBreaking, TiePolicy, Tournament, UnbiasedTournament, Windowed,
+};
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::FromStr;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Any of the selection methods in this crate, which can be stored in a
+/// concrete and serializable field instead of a boxed trait object.
+///
+/// The combinators contain another `MateSelectionKind` as their inner method.
+/// The methods which are configured with callbacks, such as
+/// [RestrictedTournament](crate::RestrictedTournament) and
+/// [DoubleTournament](crate::DoubleTournament), are not included.
+///
+/// Selection methods can be parsed from text, for example `"tournament(4)"`
+/// or `"elitist(2, ranked_linear(0.5))"`. The names are the snake case names
+/// of the selection methods, followed by their arguments in parentheses. The
+/// combinators take their inner method as their last argument:
+/// `inverse(inner)`, `elitist(keep, inner)`, `capped(cap, inner)`,
+/// `constrained(inner)`, and `islands(num_islands, inner)`. Islands with
+/// migration are written as `islands(num_islands, interval, rate, policy, inner)`
+/// where the policy is either `best` or `random`. Tie breaking is written as
+/// `tie_breaking(policy, inner)` where the policy is `average`, `random`, or
+/// `index`.
+///
+/// Formatting with [Display](core::fmt::Display) produces the same text, which
+/// parses back into an equal selection method. The runtime state of a method,
+/// such as the generation counter of [AnnealedBoltzmann], the history of
+/// [Windowed], and the violations of [Constrained], is not included.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MateSelectionKind {
+    Random(Random),
+    Proportional(Proportional),
+    PowerScaled(PowerScaled),
+    Normalized(Normalized),
+    Best(Best),
+    Percentile(Percentile),
+    RankedLinear(RankedLinear),
+    RankedExponential(RankedExponential),
+    LinearScaled(LinearScaled),
+    RankedExponentialBase(RankedExponentialBase),
+    Windowed(Windowed),
+    Tournament(Tournament),
+    ProbabilisticTournament(ProbabilisticTournament),
+    RankedGeometric(RankedGeometric),
+    RankedGaussian(RankedGaussian),
+    Boltzmann(Boltzmann),
+    AnnealedBoltzmann(AnnealedBoltzmann),
+    UnbiasedTournament(UnbiasedTournament),
+    Disruptive(Disruptive),
+    Inverse(Box<Inverse<MateSelectionKind>>),
+    Elitist(Box<Elitist<MateSelectionKind>>),
+    Capped(Box<Capped<MateSelectionKind>>),
+    Constrained(Box<Constrained<MateSelectionKind>>),
+    Islands(Box<Islands<MateSelectionKind>>),
+    TieBreaking(Box<TieBreaking<MateSelectionKind>>),
+}
+
+impl MateSelectionKind {
+    /// Get the selection method which this contains.
+    fn method<R: Rng + ?Sized>(&self) -> &dyn MateSelection<R> {
+        match self {
+            Self::Random(method) => method,
+            Self::Proportional(method) => method,
+            Self::PowerScaled(method) => method,
+            Self::Normalized(method) => method,
+            Self::Best(method) => method,
+            Self::Percentile(method) => method,
+            Self::RankedLinear(method) => method,
+            Self::RankedExponential(method) => method,
+            Self::LinearScaled(method) => method,
+            Self::RankedExponentialBase(method) => method,
+            Self::Windowed(method) => method,
+            Self::Tournament(method) => method,
+            Self::ProbabilisticTournament(method) => method,
+            Self::RankedGeometric(method) => method,
+            Self::RankedGaussian(method) => method,
+            Self::Boltzmann(method) => method,
+            Self::AnnealedBoltzmann(method) => method,
+            Self::UnbiasedTournament(method) => method,
+            Self::Disruptive(method) => method,
+            Self::Inverse(method) => &**method,
+            Self::Elitist(method) => &**method,
+            Self::Capped(method) => &**method,
+            Self::Constrained(method) => &**method,
+            Self::Islands(method) => &**method,
+            Self::TieBreaking(method) => &**method,
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> MateSelection<R> for MateSelectionKind {
+    fn pairs(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        self.method().pairs(rng, amount, scores)
+    }
+    fn groups(&self, rng: &mut R, amount: usize, size: usize, scores: Vec<f64>) -> Vec<Vec<usize>> {
+        self.method().groups(rng, amount, size, scores)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.method().select(rng, amount, scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        self.method().select_slice(rng, amount, scores)
+    }
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        self.method()
+            .select_into(rng, amount, scores, buffer, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        self.method()
+            .pairs_into(rng, amount, scores, buffer, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        MateSelection::<R>::validate(self.method::<R>())
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        self.method::<R>().expected_offspring(amount, scores)
+    }
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        self.method().takeover_time(rng, population)
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        self.method::<R>().loss_of_diversity(amount, scores)
+    }
+    fn sample_weight_sparse(&self, scores: &[f64]) -> SparseWeights {
+        self.method::<R>().sample_weight_sparse(scores)
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.method::<R>().pdf(scores)
+    }
+    fn sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.method::<R>().sample_weight(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        self.method::<R>().sample_weight_in_place(scores)
+    }
+    fn log_sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.method::<R>().log_sample_weight(scores)
+    }
+}
+
+impl FromStr for MateSelectionKind {
+    type Err = SelectionError;
+
+    fn from_str(spec: &str) -> Result<Self, SelectionError> {
+        let spec = spec.trim();
+        let invalid = || SelectionError::InvalidSpecification(spec.to_string());
+        let (name, args) = match spec.split_once('(') {
+            Some((name, args)) => {
+                let args = args.strip_suffix(')').ok_or_else(invalid)?;
+                (name.trim(), split_args(args).ok_or_else(invalid)?)
+            }
+            None => (spec, vec![]),
+        };
+        let number = |i: usize| args[i].parse::<f64>().map_err(|_| invalid());
+        let count = |i: usize| args[i].parse::<usize>().map_err(|_| invalid());
+        let inner = |i: usize| args[i].parse::<MateSelectionKind>().map(Box::new);
+        let method = match (name, args.len()) {
+            ("random", 0) => Self::Random(Random),
+            ("proportional", 0) => Self::Proportional(Proportional),
+            ("power_scaled", 1) => Self::PowerScaled(PowerScaled(number(0)?)),
+            ("normalized", 1) => Self::Normalized(Normalized(number(0)?)),
+            ("best", 1) => Self::Best(Best(count(0)?)),
+            ("percentile", 1) => Self::Percentile(Percentile(number(0)?)),
+            ("ranked_linear", 1) => Self::RankedLinear(RankedLinear(number(0)?)),
+            ("ranked_exponential", 1) => Self::RankedExponential(RankedExponential(count(0)?)),
+            ("linear_scaled", 1) => Self::LinearScaled(LinearScaled(number(0)?)),
+            ("ranked_exponential_base", 1) => {
+                Self::RankedExponentialBase(RankedExponentialBase(number(0)?))
+            }
+            ("windowed", 0) => Self::Windowed(Windowed::new(0)),
+            ("windowed", 1) => Self::Windowed(Windowed::new(count(0)?)),
+            ("tournament", 1) => Self::Tournament(Tournament(count(0)?)),
+            ("probabilistic_tournament", 2) => {
+                Self::ProbabilisticTournament(ProbabilisticTournament {
+                    size: count(0)?,
+                    probability: number(1)?,
+                })
+            }
+            ("ranked_geometric", 1) => Self::RankedGeometric(RankedGeometric(number(0)?)),
+            ("ranked_gaussian", 1) => Self::RankedGaussian(RankedGaussian(number(0)?)),
+            ("boltzmann", 1) => Self::Boltzmann(Boltzmann(number(0)?)),
+            ("annealed_boltzmann", 2) => {
+                Self::AnnealedBoltzmann(AnnealedBoltzmann::new(number(0)?, number(1)?))
+            }
+            ("unbiased_tournament", 1) => Self::UnbiasedTournament(UnbiasedTournament(count(0)?)),
+            ("disruptive", 0) => Self::Disruptive(Disruptive),
+            ("inverse", 1) => Self::Inverse(Box::new(Inverse(*inner(0)?))),
+            ("elitist", 2) => Self::Elitist(Box::new(Elitist {
+                keep: count(0)?,
+                inner: *inner(1)?,
+            })),
+            ("capped", 2) => Self::Capped(Box::new(Capped {
+                cap: count(0)?,
+                inner: *inner(1)?,
+            })),
+            ("constrained", 1) => Self::Constrained(Box::new(Constrained::new(*inner(0)?))),
+            ("islands", 2) => Self::Islands(Box::new(Islands::new(*inner(1)?, count(0)?))),
+            ("islands", 5) => {
+                let policy = match args[3] {
+                    "best" => MigrationPolicy::Best,
+                    "random" => MigrationPolicy::Random,
+                    _ => return Err(invalid()),
+                };
+                let interval = args[1].parse::<u64>().map_err(|_| invalid())?;
+                Self::Islands(Box::new(
+                    Islands::new(*inner(4)?, count(0)?).with_migration(
+                        interval,
+                        number(2)?,
+                        policy,
+                    ),
+                ))
+            }
+            ("tie_breaking", 2) => {
+                let policy = match args[0] {
+                    "average" => TiePolicy::Average,
+                    "random" => TiePolicy::Random,
+                    "index" => TiePolicy::Index,
+                    _ => return Err(invalid()),
+                };
+                Self::TieBreaking(Box::new(TieBreaking::new(policy, *inner(1)?)))
+            }
+            _ => return Err(invalid()),
+        };
+        MateSelection::<StdRng>::validate(&method)?;
+        Ok(method)
+    }
+}
+
+impl core::fmt::Display for MateSelectionKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Random(_) => write!(f, "random"),
+            Self::Proportional(_) => write!(f, "proportional"),
+            Self::PowerScaled(method) => write!(f, "power_scaled({})", method.0),
+            Self::Normalized(method) => write!(f, "normalized({})", method.0),
+            Self::Best(method) => write!(f, "best({})", method.0),
+            Self::Percentile(method) => write!(f, "percentile({})", method.0),
+            Self::RankedLinear(method) => write!(f, "ranked_linear({})", method.0),
+            Self::RankedExponential(method) => write!(f, "ranked_exponential({})", method.0),
+            Self::LinearScaled(method) => write!(f, "linear_scaled({})", method.0),
+            Self::RankedExponentialBase(method) => {
+                write!(f, "ranked_exponential_base({})", method.0)
+            }
+            Self::Windowed(method) => write!(f, "windowed({})", method.window),
+            Self::Tournament(method) => write!(f, "tournament({})", method.0),
+            Self::ProbabilisticTournament(method) => write!(
+                f,
+                "probabilistic_tournament({}, {})",
+                method.size, method.probability
+            ),
+            Self::RankedGeometric(method) => write!(f, "ranked_geometric({})", method.0),
+            Self::RankedGaussian(method) => write!(f, "ranked_gaussian({})", method.0),
+            Self::Boltzmann(method) => write!(f, "boltzmann({})", method.0),
+            Self::AnnealedBoltzmann(method) => write!(
+                f,
+                "annealed_boltzmann({}, {})",
+                method.initial_temperature, method.decay
+            ),
+            Self::UnbiasedTournament(method) => write!(f, "unbiased_tournament({})", method.0),
+            Self::Disruptive(_) => write!(f, "disruptive"),
+            Self::Inverse(method) => write!(f, "inverse({})", method.0),
+            Self::Elitist(method) => write!(f, "elitist({}, {})", method.keep, method.inner),
+            Self::Capped(method) => write!(f, "capped({}, {})", method.cap, method.inner),
+            Self::Constrained(method) => write!(f, "constrained({})", method.inner),
+            Self::Islands(method) => {
+                write!(f, "islands({}, ", method.num_islands)?;
+                if method.interval != 0
+                    || method.rate != 0.0
+                    || method.policy != MigrationPolicy::Best
+                {
+                    let policy = match method.policy {
+                        MigrationPolicy::Best => "best",
+                        MigrationPolicy::Random => "random",
+                    };
+                    write!(f, "{}, {}, {policy}, ", method.interval, method.rate)?;
+                }
+                write!(f, "{})", method.inner)
+            }
+            Self::TieBreaking(method) => {
+                let policy = match method.policy {
+                    TiePolicy::Average => "average",
+                    TiePolicy::Random => "random",
+                    TiePolicy::Index => "index",
+                };
+                write!(f, "tie_breaking({policy}, {})", method.inner)
+            }
+        }
+    }
+}
+
+/// Split a list of arguments at the commas which are not nested inside of
+/// parentheses. Returns None if the parentheses are unbalanced.
+fn split_args(args: &str) -> Option<Vec<&str>> {
+    if args.trim().is_empty() {
+        return Some(vec![]);
+    }
+    let mut split = vec![];
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (index, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                split.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    split.push(args[start..].trim());
+    Some(split)
+}
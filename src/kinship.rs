@@ -0,0 +1,157 @@
+//! Avoid mating close relatives
+
+use crate::{reduce_conflicts, vec_to_pairs, MateSelection, Pair};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Describes how the individuals of the current population are related to
+/// each other, for the purpose of preventing inbreeding.
+///
+/// Kinship is also a pairing strategy, which pairs the selected parents at
+/// random while avoiding mating relatives.
+///
+/// See [MateSelection::pairs_unrelated](crate::MateSelection::pairs_unrelated).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Kinship {
+    /// Family tree of the population.
+    ///
+    /// Argument "**parents**" contains the parents of every individual, for
+    /// each of the previous generations. The last element of the list describes
+    /// the current population: `parents.last()[i]` are the two parents of
+    /// individual `i`, specified as indices into the previous generation.
+    /// Likewise the second to last element describes the previous generation,
+    /// and so on.
+    ///
+    /// Argument "**degree**" is the number of generations to search for common
+    /// ancestors. Two individuals are related if they share an ancestor within
+    /// this many generations.
+    /// * At zero, only an individual and itself are related.
+    /// * At one, siblings and half-siblings are related.
+    /// * At two, first cousins are also related.
+    Genealogy {
+        parents: Vec<Vec<[usize; 2]>>,
+        degree: usize,
+    },
+
+    /// Explicit pairwise relationships.
+    ///
+    /// Argument "**matrix**" is the coefficient of relationship between every
+    /// pair of individuals in the current population, `matrix[a][b]`.
+    ///
+    /// Argument "**threshold**" is the largest coefficient of relationship
+    /// which is permitted to mate.
+    Matrix {
+        matrix: Vec<Vec<f64>>,
+        threshold: f64,
+    },
+}
+
+impl Kinship {
+    /// Returns a function which tests if two individuals are related.
+    pub(crate) fn relation(&self, population: usize) -> impl Fn(usize, usize) -> bool + '_ {
+        let ancestors = match self {
+            Self::Genealogy { parents, degree } => Some(ancestors(parents, *degree, population)),
+            Self::Matrix { .. } => None,
+        };
+        move |a, b| {
+            if a == b {
+                return true;
+            }
+            match self {
+                Self::Genealogy { .. } => {
+                    let ancestors = ancestors.as_ref().unwrap();
+                    intersects(&ancestors[a], &ancestors[b])
+                }
+                Self::Matrix { matrix, threshold } => matrix[a][b] > *threshold,
+            }
+        }
+    }
+
+    /// Check if two individuals in the current population are related.
+    ///
+    /// This only searches the family trees of the two individuals, so each
+    /// call costs `O(2^degree)` time regardless of the population size.
+    pub fn is_related(&self, a: usize, b: usize) -> bool {
+        if a == b {
+            return true;
+        }
+        match self {
+            Self::Genealogy { parents, degree } => intersects(
+                &ancestors_of(parents, *degree, a),
+                &ancestors_of(parents, *degree, b),
+            ),
+            Self::Matrix { matrix, threshold } => matrix[a][b] > *threshold,
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> Pair<R> for Kinship {
+    fn pair(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        selection: &dyn MateSelection<R>,
+        scores: Vec<f64>,
+    ) -> Vec<[usize; 2]> {
+        let population = scores.len();
+        let mut pairs = selection.select(rng, amount * 2, scores);
+
+        reduce_conflicts(&mut pairs, self.relation(population));
+
+        vec_to_pairs(pairs)
+    }
+}
+
+/// Find every ancestor of every individual within the given number of
+/// generations. Ancestors are identified by their generation (counting back
+/// from the current population) and their index within that generation.
+/// Each list of ancestors is sorted.
+fn ancestors(
+    parents: &[Vec<[usize; 2]>],
+    degree: usize,
+    population: usize,
+) -> Vec<Vec<(usize, usize)>> {
+    (0..population)
+        .map(|individual| ancestors_of(parents, degree, individual))
+        .collect()
+}
+
+/// Find the sorted ancestors of a single individual, see [ancestors].
+fn ancestors_of(
+    parents: &[Vec<[usize; 2]>],
+    degree: usize,
+    individual: usize,
+) -> Vec<(usize, usize)> {
+    let degree = degree.min(parents.len());
+    let mut found = Vec::new();
+    let mut frontier = vec![individual];
+    for generation in 0..degree {
+        let family = &parents[parents.len() - 1 - generation];
+        let mut next: Vec<usize> = frontier.iter().flat_map(|&child| family[child]).collect();
+        next.sort_unstable();
+        next.dedup();
+        found.extend(next.iter().map(|&x| (generation, x)));
+        frontier = next;
+    }
+    found.sort_unstable();
+    found
+}
+
+/// Check if two sorted lists have any elements in common.
+fn intersects<T: Ord>(a: &[T], b: &[T]) -> bool {
+    let (mut a, mut b) = (a.iter().peekable(), b.iter().peekable());
+    while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+        match x.cmp(y) {
+            core::cmp::Ordering::Less => {
+                a.next();
+            }
+            core::cmp::Ordering::Greater => {
+                b.next();
+            }
+            core::cmp::Ordering::Equal => return true,
+        }
+    }
+    false
+}
@@ -1,11 +1,115 @@
 //! A collection of mate selection methods for evolutionary algorithms
+//!
+//! Without the default "std" feature, this crate is `no_std` and only needs
+//! the `alloc` crate.
 
-use rand::Rng;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::{BinaryHeap, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use error::expect_valid;
+#[cfg(not(any(feature = "std", test)))]
+use math::Float;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngExt};
 use serde::{Deserialize, Serialize};
 
+mod adaptive;
+mod audit;
+mod buffer;
+mod cellular;
+mod config;
+mod curves;
+mod custom;
+mod error;
+mod fallback;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod floor;
+mod generation;
+mod hall_of_fame;
+mod islands;
+mod kind;
+mod kinship;
+#[cfg(any(not(feature = "std"), test))]
+mod math;
+mod mixture;
+mod moead;
+mod multi_objective;
+mod noisy;
+mod ordinal;
+mod pairing;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod pedigree;
+mod population;
+mod report;
+mod sampler;
+mod schedule;
+mod seeded;
+#[cfg(feature = "simd")]
+mod simd;
+mod sparse;
+mod streaming;
+mod survivor;
+mod sus;
+mod takeover;
+mod ties;
+mod transform;
+mod verify;
+
+pub use adaptive::{AdaptivePressure, Pressure};
+pub use audit::{hash_scores, AuditRecord, Recorder};
+pub use buffer::SelectionBuffer;
+pub use cellular::{Cellular, Grid, GridShape, Neighborhood};
+pub use config::{SelectionConfig, SelfMating};
+pub use curves::{CurvePoint, SelectionCurve};
+pub use custom::{Custom, SelectFn};
+pub use error::SelectionError;
+pub use fallback::Fallback;
+pub use floor::Floor;
+pub use generation::{Generation, GenerationStep};
+pub use hall_of_fame::{HallOfFame, Parent};
+pub use islands::{IslandPlan, Islands, Migration, MigrationPolicy};
+pub use kind::MateSelectionKind;
+pub use kinship::Kinship;
+pub use mixture::Mixture;
+pub use moead::Moead;
+pub use multi_objective::{
+    pareto_fronts, MultiObjectiveSelection, ParetoRank, Scalarize, Scalarized,
+};
+pub use noisy::{NoiseHandling, Noisy, NoisyScore};
+pub use ordinal::{RankSelection, Ranking};
+pub use pairing::{DistancePairing, Pair, PairingPolicy, RandomPairing};
+pub use pedigree::Pedigree;
+pub use population::{Metadata, Population, Sex};
+pub use report::{ScoreReport, ScoreWarning};
+pub use sampler::Sampler;
+pub use schedule::{Schedule, Scheduled};
+pub use seeded::{pairs_batch, pairs_seeded, select_seeded, Seeded, SelectionSession};
+pub use sparse::SparseWeights;
+pub use streaming::{choose_multiple_streaming, StreamingSample};
+pub use survivor::{
+    AgeBased, CommaSelection, PlusSelection, ReplaceWorst, RoundRobin, SurvivorSelection,
+};
+pub use ties::{TieBreaking, TiePolicy};
+pub use transform::{AgeDecay, Pipeline, ScoreTransform, Transform};
+pub use verify::{verify_distribution, EmpiricalDistribution, GoodnessOfFit};
+
 /// Mate selection algorithms randomly select pairs of individuals from a population.  
 /// The sampling probability of each individuals is a function of its reproductive fitness or "score".  
-pub trait MateSelection<R: Rng + ?Sized>: std::fmt::Debug {
+///
+/// The type parameter `R` is the random number generator. It defaults to
+/// `dyn Rng`, so that a selection method can be stored as a
+/// `Box<dyn MateSelection>` without choosing a concrete random number generator.
+/// Every selection method in this crate implements the trait for `dyn Rng`,
+/// and any random number generator can be passed to it.
+pub trait MateSelection<R: Rng + ?Sized = dyn Rng>: core::fmt::Debug {
     /// Choose multiple weighted pairs
     ///
     /// * Argument `amount` is the number of pairs to return.
@@ -21,7 +125,101 @@ pub trait MateSelection<R: Rng + ?Sized>: std::fmt::Debug {
 
         reduce_repeats(&mut pairs);
 
-        transmute_vec_to_pairs(pairs)
+        vec_to_pairs(pairs)
+    }
+
+    /// Choose multiple weighted pairs from two separate populations
+    ///
+    /// Each pair contains one parent from each population, for example from
+    /// two sexes. The first parent is an index into `scores_a` and the second
+    /// parent is an index into `scores_b`. The populations may have different
+    /// sizes. See [pairs_two_populations] for using a different selection
+    /// method for each population.
+    fn pairs_two_populations(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores_a: Vec<f64>,
+        scores_b: Vec<f64>,
+    ) -> Vec<[usize; 2]> {
+        pairs_two_populations(rng, amount, self, scores_a, self, scores_b)
+    }
+
+    /// Choose multiple weighted groups of parents, for multi-parent recombination
+    ///
+    /// * Argument `amount` is the number of groups to return.
+    ///
+    /// * Argument `size` is the number of parents in each group.
+    ///
+    /// * Returns a list of groups of parents to mate together.  
+    ///   The parents are specified as indices into the scores list.
+    ///
+    /// This implementation tries to avoid repeating an individual within a group.
+    fn groups(&self, rng: &mut R, amount: usize, size: usize, scores: Vec<f64>) -> Vec<Vec<usize>> {
+        if size == 0 {
+            return vec![vec![]; amount];
+        }
+        let mut groups = self.select(rng, amount * size, scores);
+
+        reduce_group_repeats(&mut groups, size);
+
+        groups.chunks_exact(size).map(<[usize]>::to_vec).collect()
+    }
+
+    /// Choose multiple weighted pairs, using the given pairing strategy to
+    /// decide which of the selected parents mate together.
+    ///
+    /// See [Pair] for the available strategies, such as [PairingPolicy] and
+    /// [Kinship].
+    fn pairs_with(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: Vec<f64>,
+        pairing: &dyn Pair<R>,
+    ) -> Vec<[usize; 2]>
+    where
+        Self: Sized,
+    {
+        pairing.pair(rng, amount, self, scores)
+    }
+
+    /// Choose multiple weighted pairs, mating dissimilar individuals together
+    ///
+    /// Argument "distance" is a callback which measures the dissimilarity
+    /// between two individuals, specified as indices into the scores list.
+    /// The selected parents are matched greedily so as to maximize the
+    /// distance between mates.
+    fn pairs_dissimilar(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: Vec<f64>,
+        distance: &dyn Fn(usize, usize) -> f64,
+    ) -> Vec<[usize; 2]> {
+        let parents = self.select(rng, amount * 2, scores);
+
+        pairing::pair_dissimilar(rng, parents, distance)
+    }
+
+    /// Choose multiple weighted pairs, avoiding mating close relatives
+    ///
+    /// This is the same as [MateSelection::pairs] except that it also tries
+    /// to avoid mating individuals which are related to each other. If there
+    /// are not enough unrelated individuals then some relatives will be mated.
+    fn pairs_unrelated(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: Vec<f64>,
+        kinship: &Kinship,
+    ) -> Vec<[usize; 2]> {
+        let population = scores.len();
+        let mut pairs = self.select(rng, amount * 2, scores);
+
+        reduce_conflicts(&mut pairs, kinship.relation(population));
+
+        vec_to_pairs(pairs)
     }
 
     /// Choose multiple weighted
@@ -34,14 +232,251 @@ pub trait MateSelection<R: Rng + ?Sized>: std::fmt::Debug {
 
         let weights = self.sample_weight(scores);
 
-        stochastic_universal_sampling::choose_multiple_weighted(rng, amount, &weights)
+        sus::choose_multiple_weighted(rng, amount, &weights)
+    }
+
+    /// Choose multiple weighted, without replacement.
+    ///
+    /// The same individual is never chosen more than once, so this returns
+    /// fewer than `amount` individuals if the population is too small. The
+    /// individuals with a sampling weight of zero are only chosen after all of
+    /// the other individuals, in random order.
+    fn select_distinct(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+
+        let weights = self.sample_weight(scores);
+
+        choose_distinct(rng, amount, &weights)
+    }
+
+    /// Choose multiple weighted pairs, or return an error instead of panicking
+    /// if the arguments or the scores are invalid. See [MateSelection::pairs].
+    ///
+    /// This also returns an error if none of the individuals are eligible to
+    /// mate, instead of choosing them uniformly at random.
+    fn try_pairs(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: Vec<f64>,
+    ) -> Result<Vec<[usize; 2]>, SelectionError> {
+        check_inputs(self, amount * 2, &scores)?;
+        Ok(self.pairs(rng, amount, scores))
+    }
+
+    /// Choose multiple weighted, or return an error instead of panicking if
+    /// the arguments or the scores are invalid. See [MateSelection::select].
+    ///
+    /// This also returns an error if none of the individuals are eligible to
+    /// mate, instead of choosing them uniformly at random.
+    fn try_select(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: Vec<f64>,
+    ) -> Result<Vec<usize>, SelectionError> {
+        check_inputs(self, amount, &scores)?;
+        Ok(self.select(rng, amount, scores))
+    }
+
+    /// Check that the arguments of this selection method are valid.
+    fn validate(&self) -> Result<(), SelectionError> {
+        Ok(())
+    }
+
+    /// Check the scores for problems before selecting from them. This reports
+    /// the number of negative, non-finite, and duplicate scores, the number of
+    /// individuals which this method could select, and any problems which
+    /// would make the selection fail or be degenerate.
+    fn validate_scores(&self, scores: &[f64]) -> ScoreReport {
+        report::score_report(self, scores)
+    }
+
+    /// Choose multiple weighted pairs, borrowing the scores. See [MateSelection::pairs].
+    ///
+    /// By default this copies the scores and calls [MateSelection::pairs].
+    fn pairs_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<[usize; 2]> {
+        self.pairs(rng, amount, scores.to_vec())
+    }
+
+    /// Choose multiple weighted pairs, as 32-bit indices. See [MateSelection::pairs].
+    ///
+    /// This halves the memory of the output compared to `usize` indices. The
+    /// population must have fewer than 2<sup>32</sup> individuals.
+    fn pairs_u32(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<[u32; 2]> {
+        assert_u32_population(scores.len());
+        let pairs = self.pairs(rng, amount, scores);
+        pairs.iter().map(|&[a, b]| [a as u32, b as u32]).collect()
+    }
+
+    /// Choose multiple weighted, as 32-bit indices. See [MateSelection::select].
+    ///
+    /// The population must have fewer than 2<sup>32</sup> individuals.
+    fn select_u32(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<u32> {
+        assert_u32_population(scores.len());
+        let selected = self.select(rng, amount, scores);
+        selected.iter().map(|&x| x as u32).collect()
+    }
+
+    /// Lazily choose weighted pairs, one at a time.
+    ///
+    /// The sampling weights are computed once up front, and then each parent
+    /// is drawn independently using [StochasticAcceptance]. An individual is
+    /// never mated with itself, unless it is the only eligible individual.
+    /// The iterator never ends. This does not respect any restrictions on
+    /// which individuals can mate together, such as [Islands] or [Cellular].
+    fn pairs_iter<'a>(&self, rng: &'a mut R, scores: Vec<f64>) -> PairsIter<'a, R> {
+        assert!(!scores.is_empty(), "no data: can not choose from empty set");
+        PairsIter::new(rng, StochasticAcceptance::new(self.sample_weight(scores)))
+    }
+
+    /// Compute the sampling weights once and compile them into a [Sampler],
+    /// which can then choose many batches of parents cheaply.
+    ///
+    /// The sampler chooses each parent independently with the probabilities of
+    /// the [pdf](MateSelection::pdf). This does not respect any restrictions on
+    /// which individuals can mate together, such as [Islands] or [Cellular],
+    /// nor the guarantees of [Elitist] and [Capped].
+    fn compile(&self, scores: Vec<f64>) -> Sampler {
+        Sampler::new(self.sample_weight(scores))
+    }
+
+    /// Choose multiple weighted, borrowing the scores. See [MateSelection::select].
+    ///
+    /// This avoids copying the scores if the selection method does not need
+    /// to transform them, for example the truncation and tournament methods.
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        self.select(rng, amount, scores.to_vec())
+    }
+
+    /// Probability distribution function, borrowing the scores. See [MateSelection::pdf].
+    fn pdf_slice(&self, scores: &[f64]) -> Vec<f64> {
+        self.pdf(scores.to_vec())
+    }
+
+    /// Transform the reproductive fitness scores into sampling weights,
+    /// borrowing the scores. See [MateSelection::sample_weight].
+    fn sample_weight_slice(&self, scores: &[f64]) -> Vec<f64> {
+        self.sample_weight(scores.to_vec())
+    }
+
+    /// Choose multiple weighted, from a stream of scores. See [MateSelection::select].
+    ///
+    /// Argument "capacity" is the expected number of scores. The scores are
+    /// collected into a single buffer, which is then transformed in place into
+    /// the sampling weights. The methods which do not need every score, such
+    /// as [Random] and [Best], consume the scores without storing them.
+    fn select_from_iter<I>(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: I,
+        capacity: usize,
+    ) -> Vec<usize>
+    where
+        I: IntoIterator<Item = f64>,
+        Self: Sized,
+    {
+        self.select(rng, amount, collect_scores(scores, capacity))
+    }
+
+    /// Choose multiple weighted pairs, from a stream of scores. See
+    /// [MateSelection::pairs] and [MateSelection::select_from_iter].
+    fn pairs_from_iter<I>(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: I,
+        capacity: usize,
+    ) -> Vec<[usize; 2]>
+    where
+        I: IntoIterator<Item = f64>,
+        Self: Sized,
+    {
+        self.pairs(rng, amount, collect_scores(scores, capacity))
+    }
+
+    /// Choose multiple weighted, reusing memory. See [MateSelection::select].
+    ///
+    /// The selected individuals replace the contents of `output`. The buffer
+    /// holds the sampling weights and other temporary data. The selection
+    /// methods which sample by weight do not allocate memory once the buffers
+    /// are large enough, the other methods still allocate memory.
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        _buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        output.clear();
+        output.append(&mut self.select_slice(rng, amount, scores));
+    }
+
+    /// Choose multiple weighted pairs, reusing memory. See [MateSelection::pairs]
+    /// and [MateSelection::select_into].
+    ///
+    /// By default this calls [MateSelection::pairs], which allocates memory.
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        _buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        output.clear();
+        output.append(&mut self.pairs_slice(rng, amount, scores));
+    }
+
+    /// Transform the scores into sampling weights, and keep only the eligible
+    /// individuals which have a positive weight. See [MateSelection::sample_weight].
+    ///
+    /// The truncation methods, [Best] and [Percentile], find the eligible
+    /// individuals without building a dense vector of weights.
+    fn sample_weight_sparse(&self, scores: &[f64]) -> SparseWeights {
+        SparseWeights::from_dense(&self.sample_weight_slice(scores))
+    }
+
+    /// Choose multiple weighted pairs, with single precision scores. See [MateSelection::pairs].
+    ///
+    /// The scores are converted to double precision once, while the selection
+    /// method transforms them into sampling weights.
+    fn pairs_f32(&self, rng: &mut R, amount: usize, scores: &[f32]) -> Vec<[usize; 2]> {
+        self.pairs(rng, amount, widen(scores))
+    }
+
+    /// Choose multiple weighted, with single precision scores. See [MateSelection::select].
+    fn select_f32(&self, rng: &mut R, amount: usize, scores: &[f32]) -> Vec<usize> {
+        self.select(rng, amount, widen(scores))
+    }
+
+    /// Probability distribution function, with single precision scores. See [MateSelection::pdf].
+    fn pdf_f32(&self, scores: &[f32]) -> Vec<f32> {
+        self.pdf(widen(scores))
+            .into_iter()
+            .map(|x| x as f32)
+            .collect()
     }
 
     /// Probability distribution function
     fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
         let mut pdf = self.sample_weight(scores);
         // Normalize the sum to one.
-        let sum: f64 = pdf.iter().sum();
+        let mut sum = sum_map(&pdf, |x| x);
+        if sum.is_infinite() {
+            // Rescale so the largest weight is one, to avoid overflowing the sum.
+            let max = pdf.iter().copied().fold(0.0, f64::max);
+            for x in pdf.iter_mut() {
+                *x /= max;
+            }
+            sum = sum_map(&pdf, |x| x);
+        }
         let div_sum = 1.0 / sum;
         for x in pdf.iter_mut() {
             *x *= div_sum;
@@ -49,9 +484,388 @@ pub trait MateSelection<R: Rng + ?Sized>: std::fmt::Debug {
         pdf
     }
 
+    /// Expected number of times that each individual is selected, when
+    /// selecting the given amount. This is `amount * pdf`, except for the
+    /// methods which select some individuals deterministically, such as
+    /// [Elitist] and [Capped].
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        let mut expected = self.pdf(scores);
+        for x in expected.iter_mut() {
+            *x *= amount as f64;
+        }
+        expected
+    }
+
+    /// Selection intensity, which is the expected change in the mean score of
+    /// the selected parents compared to the whole population, measured in
+    /// standard deviations of the scores.
+    ///
+    /// This is computed from the [expected_offspring](MateSelection::expected_offspring)
+    /// when selecting one parent for each individual in the population, so it
+    /// accounts for the guarantees of [Elitist] and [Capped]. Returns zero if
+    /// all of the scores are equal.
+    fn selection_intensity(&self, scores: Vec<f64>) -> f64 {
+        if scores.is_empty() {
+            return 0.0;
+        }
+        let num = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / num;
+        let variance = scores.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / num;
+        if variance.is_nan() || variance <= 0.0 {
+            return 0.0;
+        }
+        let expected = self.expected_offspring(scores.len(), scores.clone());
+        let selected_mean = expected
+            .iter()
+            .zip(&scores)
+            .map(|(e, x)| e * x)
+            .sum::<f64>()
+            / num;
+        (selected_mean - mean) / variance.sqrt()
+    }
+
+    /// Loss of diversity, which is the expected fraction of the population
+    /// that is not selected at all when selecting the given amount, see
+    /// Blickle & Thiele (1996), "A Comparison of Selection Schemes used in
+    /// Evolutionary Algorithms".
+    ///
+    /// This is exact for the methods which use stochastic universal sampling,
+    /// where an individual whose [expected_offspring](MateSelection::expected_offspring)
+    /// is less than one is left out with probability `1 - expected`. The
+    /// tournament methods choose every parent independently, which loses more
+    /// individuals. Returns zero if the population is empty.
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        if scores.is_empty() {
+            return 0.0;
+        }
+        let num = scores.len() as f64;
+        let expected = self.expected_offspring(amount, scores);
+        expected.iter().map(|e| (1.0 - e).max(0.0)).sum::<f64>() / num
+    }
+
+    /// Probability of selecting each individual, sorted by rank, for plotting
+    /// the selection pressure. For a synthetic population use evenly spaced
+    /// scores, such as `(0..100).map(|x| x as f64)`.
+    fn selection_curve(&self, scores: Vec<f64>) -> SelectionCurve {
+        let pdf = self.pdf(scores.clone());
+        SelectionCurve::new(&scores, &pdf)
+    }
+
+    /// Statistical self-test, which checks that this method selects
+    /// individuals with the probabilities which it claims. This selects the
+    /// given number of samples, one at a time, and compares how often each
+    /// individual was selected with its [expected_offspring](MateSelection::expected_offspring)
+    /// using Pearson's chi-squared test.
+    ///
+    /// The test is only accurate if every eligible individual is expected to
+    /// be selected at least five times.
+    fn self_test(&self, rng: &mut R, samples: usize, scores: &[f64]) -> GoodnessOfFit {
+        verify::self_test(self, rng, samples, scores)
+    }
+
+    /// Takeover time, which is the number of generations for the best
+    /// individual to fill the whole population under selection alone, without
+    /// mutation or crossover. Shorter takeover times mean stronger selection
+    /// pressure.
+    ///
+    /// This uses the approximations of Goldberg & Deb (1991) for [Tournament]
+    /// selection and of Mühlenbein & Schlierkamp-Voosen (1993) for truncation
+    /// selection with [Best] and [Percentile]. Otherwise it is estimated by
+    /// simulating a population with distinct scores and a single best
+    /// individual, averaged over several runs.
+    ///
+    /// Returns None if the best individual never took over the population,
+    /// which is likely for selection methods without selection pressure.
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        takeover::simulate_takeover(self, rng, population)
+    }
+
+    /// Cumulative distribution function, in the same order as the scores.  
+    /// Element `i` is the probability of selecting any of the individuals
+    /// `0..=i`, and the last element is one. If all of the sampling weights
+    /// are zero then every individual is equally likely.
+    fn cdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        let mut cdf = self.pdf(scores);
+        uniform_if_zero(&mut cdf);
+        let mut sum = 0.0;
+        for x in cdf.iter_mut() {
+            sum += *x;
+            *x = sum;
+        }
+        cdf
+    }
+
+    /// Inverse of the cumulative distribution function.  
+    /// Returns the first individual whose cumulative probability reaches `p`.
+    /// Individuals which can not be selected are never returned, unless all of
+    /// the sampling weights are zero, in which case every individual is
+    /// equally likely.
+    ///
+    /// Given a uniformly distributed random number in the range [0, 1], this
+    /// returns an individual chosen with the probabilities of the [pdf](MateSelection::pdf).
+    fn quantile(&self, p: f64, scores: Vec<f64>) -> usize {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "argument \"p\" is out of bounds [0, 1]"
+        );
+        assert!(!scores.is_empty(), "no data: can not choose from empty set");
+        let mut pdf = self.pdf(scores);
+        uniform_if_zero(&mut pdf);
+        let mut sum = 0.0;
+        let mut last = 0;
+        for (index, &x) in pdf.iter().enumerate() {
+            if x > 0.0 {
+                sum += x;
+                last = index;
+                if sum >= p {
+                    return index;
+                }
+            }
+        }
+        // Rounding errors can leave the total slightly less than one.
+        last
+    }
+
+    /// Natural logarithm of the probability distribution function.
+    ///
+    /// This is normalized in log space, so it remains accurate when the
+    /// probabilities span more orders of magnitude than floating point
+    /// numbers can represent. Individuals which can not be selected have a
+    /// log probability of negative infinity.
+    fn log_pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        let mut log_pdf = self.log_sample_weight(scores);
+        let max = log_pdf.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if max == f64::NEG_INFINITY {
+            // All of the weights are zero, so sample uniformly.
+            let log_p = -(log_pdf.len() as f64).ln();
+            log_pdf.fill(log_p);
+            return log_pdf;
+        }
+        // Log-sum-exp, shifted by the maximum to avoid overflow.
+        let sum: f64 = log_pdf.iter().map(|x| (x - max).exp()).sum();
+        let log_sum = max + sum.ln();
+        for x in log_pdf.iter_mut() {
+            *x -= log_sum;
+        }
+        log_pdf
+    }
+
+    /// Natural logarithm of the sampling weights. See [MateSelection::sample_weight].
+    ///
+    /// The methods which raise the scores to a power or exponentiate them,
+    /// such as [Proportional], [PowerScaled], and [Boltzmann], compute this
+    /// directly so that it does not overflow or underflow.
+    fn log_sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        let mut weights = self.sample_weight(scores);
+        for x in weights.iter_mut() {
+            *x = x.ln();
+        }
+        weights
+    }
+
     /// Transform the reproductive fitness scores into sampling weights.  
     /// The sampling weights do **not** need to sum to one.
-    fn sample_weight(&self, scores: Vec<f64>) -> Vec<f64>;
+    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        self.sample_weight_in_place(&mut scores);
+        scores
+    }
+
+    /// Transform the reproductive fitness scores into sampling weights,
+    /// overwriting the scores. See [MateSelection::sample_weight].
+    ///
+    /// This lets callers reuse the same buffer of scores every generation,
+    /// instead of allocating a new vector of weights.
+    fn sample_weight_in_place(&self, scores: &mut [f64]);
+}
+
+/// Choose multiple weighted pairs from two separate populations, using a
+/// different selection method for each population.
+///
+/// Each pair contains one parent from each population, for example from two
+/// sexes. The first parent is selected from `scores_a` by method `a` and the
+/// second parent is selected from `scores_b` by method `b`.
+pub fn pairs_two_populations<R, A, B>(
+    rng: &mut R,
+    amount: usize,
+    a: &A,
+    scores_a: Vec<f64>,
+    b: &B,
+    scores_b: Vec<f64>,
+) -> Vec<[usize; 2]>
+where
+    R: Rng + ?Sized,
+    A: MateSelection<R> + ?Sized,
+    B: MateSelection<R> + ?Sized,
+{
+    let parents_a = a.select(rng, amount, scores_a);
+    let mut parents_b = b.select(rng, amount, scores_b);
+    parents_b.shuffle(rng);
+    parents_a
+        .into_iter()
+        .zip(parents_b)
+        .map(|(a, b)| [a, b])
+        .collect()
+}
+
+fn assert_u32_population(population: usize) {
+    assert!(
+        u32::try_from(population).is_ok(),
+        "population of {population} is too large for 32-bit indices"
+    );
+}
+
+/// Convert single precision scores to double precision.
+fn widen(scores: &[f32]) -> Vec<f64> {
+    scores.iter().map(|&x| f64::from(x)).collect()
+}
+
+/// Check the arguments and the sampling weights before selecting.
+fn check_inputs<R, M>(method: &M, amount: usize, scores: &[f64]) -> Result<(), SelectionError>
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R> + ?Sized,
+{
+    method.validate()?;
+    if amount == 0 {
+        return Ok(());
+    } else if scores.is_empty() {
+        return Err(SelectionError::EmptyPopulation);
+    }
+    let weights = method.sample_weight_slice(scores);
+    if let Some(index) = weights.iter().position(|w| !(w.is_finite() && *w >= 0.0)) {
+        Err(SelectionError::InvalidWeight {
+            index,
+            weight: weights[index],
+        })
+    } else if weights.iter().all(|&w| w == 0.0) {
+        Err(SelectionError::NoneEligible)
+    } else {
+        Ok(())
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R> + ?Sized> MateSelection<R> for &M {
+    fn pairs(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        (**self).pairs(rng, amount, scores)
+    }
+    fn pairs_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<[usize; 2]> {
+        (**self).pairs_slice(rng, amount, scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        (**self).select_slice(rng, amount, scores)
+    }
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        (**self).select_into(rng, amount, scores, buffer, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        (**self).pairs_into(rng, amount, scores, buffer, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        (**self).validate()
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        (**self).expected_offspring(amount, scores)
+    }
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        (**self).takeover_time(rng, population)
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        (**self).loss_of_diversity(amount, scores)
+    }
+    fn sample_weight_sparse(&self, scores: &[f64]) -> SparseWeights {
+        (**self).sample_weight_sparse(scores)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        (**self).select(rng, amount, scores)
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        (**self).pdf(scores)
+    }
+    fn sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        (**self).sample_weight(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        (**self).sample_weight_in_place(scores)
+    }
+    fn log_sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        (**self).log_sample_weight(scores)
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R> + ?Sized> MateSelection<R> for Box<M> {
+    fn pairs(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        (**self).pairs(rng, amount, scores)
+    }
+    fn pairs_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<[usize; 2]> {
+        (**self).pairs_slice(rng, amount, scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        (**self).select_slice(rng, amount, scores)
+    }
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        (**self).select_into(rng, amount, scores, buffer, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        (**self).pairs_into(rng, amount, scores, buffer, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        (**self).validate()
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        (**self).expected_offspring(amount, scores)
+    }
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        (**self).takeover_time(rng, population)
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        (**self).loss_of_diversity(amount, scores)
+    }
+    fn sample_weight_sparse(&self, scores: &[f64]) -> SparseWeights {
+        (**self).sample_weight_sparse(scores)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        (**self).select(rng, amount, scores)
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        (**self).pdf(scores)
+    }
+    fn sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        (**self).sample_weight(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        (**self).sample_weight_in_place(scores)
+    }
+    fn log_sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        (**self).log_sample_weight(scores)
+    }
 }
 
 /// Select parents with a uniform random probability, ignoring the scores.
@@ -72,6 +886,24 @@ pub struct Random;
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct Proportional;
 
+/// Select parents with a probability that is proportional to their score
+/// raised to a power.
+///
+/// >   `probability(i) = score(i)^k / sum(score(x)^k for x in population)`
+///
+/// This method extends the proportional method with a continuously adjustable
+/// selection pressure. Exponents greater than one amplify the differences
+/// between the scores, and exponents less than one diminish them. At `k = 1`
+/// this is equivalent to the proportional method.
+///
+/// Negative or invalid (NaN) scores are discarded and those individuals are
+/// not permitted to mate.
+///
+/// Argument "**exponent**" is the power `k` which the scores are raised to.
+/// Must be a positive finite number.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct PowerScaled(pub f64);
+
 /// Normalize the fitness scores into a standard normal distribution.
 /// First the scores are normalized into a standard distribution and then they
 /// are shifted by the cutoff, which is naturally measured in standard deviations.
@@ -90,8 +922,12 @@ pub struct Normalized(pub f64);
 /// random probability.
 ///
 /// Argument "**number**" is the number of individuals who are allowed to mate.
+/// If the population is smaller than this then everyone is allowed to mate.
+/// Ties at the cutoff are broken by index, so that exactly "number"
+/// individuals are eligible. To admit a random subset of the tied individuals
+/// instead, wrap this in [TieBreaking] with [TiePolicy::Random].
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
-struct Best(pub usize);
+pub struct Best(pub usize);
 
 /// Apply a simple percentile based threshold to the population.
 /// Mating pairs are selected with uniform random probability from the eligible
@@ -100,6 +936,10 @@ struct Best(pub usize);
 /// Argument "**percentile**" is the fraction of the population which is denied
 /// the chance to mate. At `0` everyone is allowed to mate and at `1` only the
 /// single best individual is allowed to mate.
+///
+/// Ties at the cutoff are broken by index, so that exactly the requested
+/// fraction of the population is eligible. To admit a random subset of the
+/// tied individuals instead, wrap this in [TieBreaking] with [TiePolicy::Random].
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct Percentile(pub f64);
 
@@ -115,6 +955,8 @@ pub struct Percentile(pub f64);
 ///
 /// * At zero, all members are equally likely to be selected.  
 /// * At one, the worst ranked individual will never be selected.  
+///
+/// See also [RankedLinear::from_sp] for the classic parameterization by Baker.
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct RankedLinear(pub f64);
 
@@ -134,49 +976,501 @@ pub struct RankedLinear(pub f64);
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct RankedExponential(pub usize);
 
-#[cfg(feature = "pyo3")]
-mod python {
-    use super::MateSelection;
-    use pyo3::exceptions::PyValueError;
-    use pyo3::prelude::*;
+/// Goldberg's linear fitness scaling. The scores are transformed by a linear
+/// function `a * score + b` which maps the best score to a multiple of the
+/// average score. The scaled scores are then used for proportional selection.
+///
+/// >   `max(scaled) = C * average(scaled)`  
+///
+/// Scaled scores which are less than zero are clamped to zero, and those
+/// individuals are not permitted to mate. Invalid (NaN) scores are discarded.
+/// If all of the scores are equal then all individuals are equally likely to
+/// be selected.
+///
+/// Argument "**multiple**" is the expected number of offspring `C` of the best
+/// individual, relative to an average individual. Must be at least one.
+/// Typical values are in the range [1.2, 2.0].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct LinearScaled(pub f64);
 
-    /// A collection of mate selection methods for evolutionary algorithms
-    ///
-    /// Mate selection algorithms randomly select pairs of individuals from a
-    /// population. The sampling probability of each individuals is a function
-    /// of its reproductive fitness or "score".
-    ///
-    /// These implementations almost never mate an individual with itself.
-    #[pymodule]
-    fn mate_selection(m: Bound<PyModule>) -> PyResult<()> {
-        m.add_class::<Random>()?;
-        m.add_class::<Proportional>()?;
-        m.add_class::<Normalized>()?;
-        m.add_class::<Best>()?;
-        m.add_class::<Percentile>()?;
-        m.add_class::<RankedLinear>()?;
-        m.add_class::<RankedExponential>()?;
-        Ok(())
-    }
+/// Select parents based on their ranking in the population, with an
+/// exponentially weighted bias towards better ranked individuals. This is the
+/// same as the RankedExponential method except that the slope of the weights
+/// curve is specified by its base instead of its median, which is more
+/// convenient for small populations.
+///
+/// >   `weight(rank) = c ^ rank`  
+/// >   Where `rank = 0` is the best individual and `rank = N-1` is the worst.  
+///
+/// Argument "**base**" is the ratio `c` between the weights of consecutively
+/// ranked individuals. Must be in the range (0, 1). A small base will strongly
+/// favor the best individuals, whereas a base close to one will sample the
+/// individuals more equally.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct RankedExponentialBase(pub f64);
 
-    /// Select parents with a uniform random probability, ignoring the scores.
-    #[pyclass]
-    struct Random(super::Random);
+/// Subtract the worst score from all of the scores and then select parents
+/// with a probability that is directly proportional to their shifted score.
+///
+/// >   `probability(i) = (score(i) - worst) / sum(score(x) - worst for x in population)`
+///
+/// This method is similar to the proportional method except that it is not
+/// affected by the absolute values of the scores, and so it can deal with
+/// negative scores. The worst individual is never selected.
+///
+/// Optionally, the worst score can be tracked over a sliding window of recent
+/// generations. Call [Windowed::record] once per generation to remember the
+/// worst score of each generation. This prevents a single good generation from
+/// dramatically increasing the selection pressure.
+///
+/// If all of the valid scores are equal to the worst score, then those
+/// individuals are equally likely to be selected.
+///
+/// Invalid (NaN) scores are discarded and those individuals are not permitted
+/// to mate.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Windowed {
+    /// Number of previous generations to remember.
+    pub window: usize,
+    /// Worst score of each recorded generation, from oldest to newest.
+    pub history: VecDeque<f64>,
+}
 
-    /// Select parents with a probability that is directly proportional to their score.
-    ///
-    /// >   probability(i) = score(i) / sum(score(x) for x in population)
-    ///
-    /// This method biases the selection based on the parents scores. This
-    /// method is significantly influenced by the magnitude of the fitness
-    /// scoring function, and by the signal-to-noise ratio between the average
-    /// score and the variations in the scores.
-    ///
+/// Restricted tournament selection preserves niches in the population by only
+/// allowing individuals to compete against other similar individuals.
+///
+/// For each selection, a candidate is chosen with uniform random probability.
+/// Then a random window of individuals is chosen and the candidate competes
+/// against the individual in the window which is nearest to it. The better
+/// scoring of the two is selected. Ties are won by the candidate.
+///
+/// Argument "**window**" is the number of individuals to search for the
+/// candidate's nearest neighbor. A larger window makes the competition more
+/// local. At zero there is no competition and all individuals are equally
+/// likely to be selected.
+///
+/// Argument "**distance**" is a callback which measures the dissimilarity
+/// between two individuals, specified as indices into the scores list.
+///
+/// Invalid (NaN) scores always lose their tournaments.
+#[derive(Clone)]
+pub struct RestrictedTournament<F> {
+    pub window: usize,
+    pub distance: F,
+}
+
+/// Double tournament selection applies parsimony pressure by holding two
+/// nested tournaments: one on the scores and one on the sizes of the
+/// individuals. This is a method for controlling bloat in genetic programming,
+/// as described by Luke & Panait (2002).
+///
+/// By default the fitness tournament is the outer tournament, and each of its
+/// entrants is the winner of a size tournament. If "fitness first" is set then
+/// the size tournament is the outer tournament, and its two entrants are each
+/// the winner of a fitness tournament.
+///
+/// Argument "**fitness size**" is the number of individuals which enter each
+/// fitness tournament. The best scoring entrant wins.
+///
+/// Argument "**parsimony size**" controls the size tournament, which has two
+/// entrants. The smaller individual wins with probability `parsimony_size / 2`.
+/// Must be in the range [1, 2]. At one there is no parsimony pressure.
+///
+/// Argument "**size**" is a callback which measures the size of an individual,
+/// specified as an index into the scores list.
+///
+/// Invalid (NaN) scores and sizes always lose their tournaments.
+#[derive(Clone)]
+pub struct DoubleTournament<F> {
+    pub fitness_size: usize,
+    pub parsimony_size: f64,
+    pub fitness_first: bool,
+    pub size: F,
+}
+
+/// Tournament selection holds a competition between randomly chosen
+/// individuals, and the best scoring individual wins the tournament and is
+/// selected. Ties are won by a random entrant. The entrants are chosen with
+/// uniform random probability, with replacement.
+///
+/// Argument "**size**" is the number of individuals which enter each
+/// tournament. Larger tournaments apply more selection pressure. At one there
+/// is no competition and all individuals are equally likely to be selected.
+///
+/// Invalid (NaN) scores always lose their tournaments.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Tournament(pub usize);
+
+/// Probabilistic tournament selection is a variant of tournament selection
+/// where the best entrant does not always win. The entrants are ranked by
+/// their scores, and the best entrant wins with probability `p`. Otherwise the
+/// second best entrant wins with probability `p`, and so on. If none of the
+/// better entrants win then the worst entrant wins.
+///
+/// Argument "**size**" is the number of individuals which enter each
+/// tournament.
+///
+/// Argument "**probability**" is the probability `p` that the best entrant
+/// wins the tournament. Must be in the range [0.5, 1]. At one this is
+/// equivalent to the Tournament method. Lower values apply less selection
+/// pressure.
+///
+/// Invalid (NaN) scores always rank last in their tournaments.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ProbabilisticTournament {
+    pub size: usize,
+    pub probability: f64,
+}
+
+/// Geometric ranking, as found in the GAOT toolbox by Joines & Houck. The
+/// probability of selecting an individual decreases geometrically with its
+/// rank.
+///
+/// >   `probability(rank) = q' * (1 - q) ^ (rank - 1)`  
+/// >   `q' = q / (1 - (1 - q) ^ N)`  
+/// >   Where `N` is the population size, and  
+/// >   Where `rank = 1` is the best individual and `rank = N` is the worst.  
+///
+/// Argument "**q**" is the probability of selecting the best individual, before
+/// normalizing for the population size. Must be in the range (0, 1).
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct RankedGeometric(pub f64);
+
+/// Select parents based on their ranking in the population, with weights which
+/// follow a half Gaussian curve over the ranks. This is a smoother alternative
+/// to exponential ranking: the best individuals are sampled almost equally,
+/// and the weights fall off quickly beyond the first few sigmas.
+///
+/// >   `weight(rank) = exp(-rank^2 / (2 * sigma^2))`  
+/// >   Where `rank = 0` is the best individual and `rank = N-1` is the worst.  
+///
+/// Argument "**sigma**" is the standard deviation of the curve, measured in
+/// number of individuals. Must be a positive finite number. A small sigma will
+/// strongly favor the best individuals, whereas a large sigma will sample the
+/// individuals more equally.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct RankedGaussian(pub f64);
+
+/// Boltzmann selection, also known as softmax selection. The sampling
+/// probability is an exponential function of the score.
+///
+/// >   `probability(i) = exp(score(i) / T) / sum(exp(score(x) / T) for x in population)`
+///
+/// Argument "**temperature**" `T` controls the selection pressure. A high
+/// temperature will sample the individuals more equally, whereas a low
+/// temperature will strongly favor the best individuals. Must be a positive
+/// finite number.
+///
+/// Invalid (NaN) scores are discarded and those individuals are not permitted
+/// to mate. If any of the scores are positive infinity, then those individuals
+/// share all of the weight.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Boltzmann(pub f64);
+
+/// Boltzmann selection with a temperature which decreases over time, according
+/// to an exponential cooling schedule.
+///
+/// >   `T(generation) = initial_temperature * decay ^ generation`
+///
+/// This is useful for exploring the search space in the early generations and
+/// then exploiting the best individuals in the later generations. Call
+/// [AnnealedBoltzmann::set_generation] to advance the schedule.
+///
+/// Argument "**initial temperature**" is the temperature at generation zero.
+/// Must be a positive finite number.
+///
+/// Argument "**decay**" is the factor by which the temperature is multiplied
+/// every generation. Must be in the range (0, 1].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct AnnealedBoltzmann {
+    pub initial_temperature: f64,
+    pub decay: f64,
+    pub generation: u64,
+}
+
+/// Unbiased tournament selection, as described by Sokolov & Whitley (2005).
+/// This is a variant of tournament selection where every individual enters the
+/// same number of tournaments. The tournaments are formed by lining up several
+/// random permutations of the population, such that no individual competes
+/// against itself. This removes the sampling noise of choosing the entrants
+/// independently, which matters for small populations.
+///
+/// Argument "**size**" is the number of individuals which enter each
+/// tournament. If this is larger than the population then everyone enters
+/// every tournament.
+///
+/// Invalid (NaN) scores always lose their tournaments.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct UnbiasedTournament(pub usize);
+
+/// Select parents with a probability that is proportional to the distance
+/// between their score and the average score.
+///
+/// >   `probability(i) = |score(i) - mean| / sum(|score(x) - mean| for x in population)`
+///
+/// This method favors both the best and the worst individuals, at the expense
+/// of the average individuals. This is useful for maintaining divergent
+/// subpopulations and for dynamic environments.
+///
+/// Invalid (NaN) scores are discarded and those individuals are not permitted
+/// to mate.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Disruptive;
+
+/// Flip any selection method so that it favors the worst individuals instead
+/// of the best individuals. This is useful for choosing which individuals to
+/// remove from the population.
+///
+/// This negates the scores before passing them to the inner method. Invalid
+/// (NaN) scores remain invalid.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Inverse<M>(pub M);
+
+/// Guarantee that the best individuals are selected at least once, and then
+/// select the remaining parents using the inner method.
+///
+/// The elite individuals are shuffled into random positions in the sample, so
+/// that they are paired with random mates. If the sample is smaller than the
+/// number of elites then only the very best individuals are selected.
+/// Individuals with invalid (NaN) scores are never elites.
+///
+/// The probability distribution function and sampling weights are those of
+/// the inner method.
+///
+/// Argument "**keep**" is the number of elite individuals.
+///
+/// Argument "**inner**" is the method for selecting the remaining parents.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Elitist<M> {
+    pub keep: usize,
+    pub inner: M,
+}
+
+/// Limit the number of times that any individual can be selected, and then
+/// redistribute its excess probability to the other individuals. This prevents
+/// a single exceptional individual from parenting the entire next generation.
+///
+/// The limit applies to each call to `select` or `pairs`. If there are not
+/// enough eligible individuals (with a sampling weight greater than zero) to
+/// satisfy the limit, then the limit is raised as little as possible.
+///
+/// The probability distribution function and sampling weights are those of
+/// the inner method, because they do not depend on the number of selections.
+///
+/// Argument "**cap**" is the maximum number of times any individual can be
+/// selected. For pairs, this is the maximum number of matings per individual.
+///
+/// Argument "**inner**" is the method for selecting parents.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Capped<M> {
+    pub cap: usize,
+    pub inner: M,
+}
+
+/// Handle constraints with the feasibility rules of Deb (2000), before
+/// selecting parents using the inner method:
+/// 1. Feasible individuals are better than infeasible individuals.
+/// 2. Feasible individuals are compared by their scores.
+/// 3. Infeasible individuals are compared by their total constraint violation.
+///
+/// The scores are replaced by the ranks of the individuals under these rules,
+/// from one for the worst individual up to the population size for the best.
+/// Tied individuals share the average of their ranks. Feasible individuals
+/// with invalid (NaN) scores remain invalid.
+///
+/// Argument "**violations**" is the total constraint violation of each
+/// individual, where zero means that the individual is feasible. If missing,
+/// then every individual is feasible. Update this once per generation, or use
+/// [feasibility_scores] directly.
+///
+/// Argument "**inner**" is the method for selecting parents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Constrained<M> {
+    pub violations: Option<Vec<f64>>,
+    pub inner: M,
+}
+
+#[cfg(feature = "pyo3")]
+mod python {
+    use super::MateSelection;
+    use pyo3::buffer::{Element, PyBuffer};
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+    use pyo3::types::PyType;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use std::sync::Mutex;
+
+    /// Shared random number generator, if it was seeded by the user.
+    static SESSION: Mutex<Option<StdRng>> = Mutex::new(None);
+
+    /// Seed the random number generator. If the seed is missing then use the
+    /// session generator if it was seeded, or else seed it randomly.
+    fn rng(seed: Option<u64>) -> StdRng {
+        match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => match SESSION.lock().unwrap().as_mut() {
+                Some(session) => StdRng::seed_from_u64(session.random()),
+                None => rand::make_rng(),
+            },
+        }
+    }
+
+    /// Seed the random number generator which is used by all of the selection
+    /// methods when they are called without a seed argument, so that a whole
+    /// sequence of calls is reproducible. If the seed is None then the results
+    /// are random again.
+    #[pyfunction]
+    #[pyo3(signature = (seed=None))]
+    fn seed(seed: Option<u64>) {
+        *SESSION.lock().unwrap() = seed.map(StdRng::seed_from_u64);
+    }
+
+    /// Scores from a Python list, or from any object which supports the buffer
+    /// protocol, such as a one dimensional NumPy array of float64 or float32.
+    /// Buffers are copied directly, without converting each element.
+    struct Scores {
+        data: Vec<f64>,
+        /// Return the results as NumPy arrays instead of Python lists.
+        numpy: bool,
+    }
+
+    impl<'a, 'py> FromPyObject<'a, 'py> for Scores {
+        type Error = PyErr;
+
+        fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+            let py = obj.py();
+            let numpy = obj.get_type().module()?.to_str()? == "numpy";
+            let data = if let Ok(buffer) = PyBuffer::<f64>::get(&obj) {
+                check_dimensions(buffer.dimensions())?;
+                buffer.to_vec(py)?
+            } else if let Ok(buffer) = PyBuffer::<f32>::get(&obj) {
+                check_dimensions(buffer.dimensions())?;
+                buffer.to_vec(py)?.into_iter().map(f64::from).collect()
+            } else {
+                obj.extract()?
+            };
+            Ok(Self { data, numpy })
+        }
+    }
+
+    fn check_dimensions(dimensions: usize) -> PyResult<()> {
+        if dimensions <= 1 {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err("scores must be one dimensional"))
+        }
+    }
+
+    /// Copy the data into a new NumPy array.
+    fn numpy_array<T: Element>(
+        py: Python,
+        data: &[T],
+        shape: Vec<usize>,
+        dtype: &str,
+    ) -> PyResult<Py<PyAny>> {
+        let array = py.import("numpy")?.call_method1("empty", (shape, dtype))?;
+        PyBuffer::<T>::get(&array)?.copy_from_slice(py, data)?;
+        Ok(array.unbind())
+    }
+
+    fn pairs_output(py: Python, pairs: Vec<[usize; 2]>, numpy: bool) -> PyResult<Py<PyAny>> {
+        if numpy {
+            let shape = vec![pairs.len(), 2];
+            numpy_array(py, pairs.as_flattened(), shape, "uintp")
+        } else {
+            Ok(pairs.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+
+    fn select_output(py: Python, selected: Vec<usize>, numpy: bool) -> PyResult<Py<PyAny>> {
+        if numpy {
+            numpy_array(py, &selected, vec![selected.len()], "uintp")
+        } else {
+            Ok(selected.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+
+    fn pdf_output(py: Python, pdf: Vec<f64>, numpy: bool) -> PyResult<Py<PyAny>> {
+        if numpy {
+            numpy_array(py, &pdf, vec![pdf.len()], "float64")
+        } else {
+            Ok(pdf.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+
+    /// A collection of mate selection methods for evolutionary algorithms
+    ///
+    /// Mate selection algorithms randomly select pairs of individuals from a
+    /// population. The sampling probability of each individuals is a function
+    /// of its reproductive fitness or "score".
+    ///
+    /// These implementations almost never mate an individual with itself.
+    ///
+    /// The selection methods release the GIL while they select, so other
+    /// Python threads can run at the same time. Each selection method can be
+    /// shared between threads, but methods which change its state, such as
+    /// Windowed.record, fail while another thread is selecting with it.
+    #[pymodule]
+    fn mate_selection(m: Bound<PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(seed, &m)?)?;
+        m.add_class::<Random>()?;
+        m.add_class::<Proportional>()?;
+        m.add_class::<PowerScaled>()?;
+        m.add_class::<Normalized>()?;
+        m.add_class::<Best>()?;
+        m.add_class::<Percentile>()?;
+        m.add_class::<RankedLinear>()?;
+        m.add_class::<RankedExponential>()?;
+        m.add_class::<LinearScaled>()?;
+        m.add_class::<RankedExponentialBase>()?;
+        m.add_class::<Windowed>()?;
+        m.add_class::<Tournament>()?;
+        m.add_class::<ProbabilisticTournament>()?;
+        m.add_class::<RankedGeometric>()?;
+        m.add_class::<RankedGaussian>()?;
+        m.add_class::<Boltzmann>()?;
+        m.add_class::<AnnealedBoltzmann>()?;
+        m.add_class::<UnbiasedTournament>()?;
+        m.add_class::<Disruptive>()?;
+        Ok(())
+    }
+
+    /// Select parents with a uniform random probability, ignoring the scores.
+    #[pyclass(module = "mate_selection")]
+    struct Random(super::Random);
+
+    /// Select parents with a probability that is directly proportional to their score.
+    ///
+    /// >   probability(i) = score(i) / sum(score(x) for x in population)
+    ///
+    /// This method biases the selection based on the parents scores. This
+    /// method is significantly influenced by the magnitude of the fitness
+    /// scoring function, and by the signal-to-noise ratio between the average
+    /// score and the variations in the scores.
+    ///
     /// Negative or invalid (NaN) scores are discarded and those individuals are
     /// not permitted to mate.
-    #[pyclass]
+    #[pyclass(module = "mate_selection")]
     struct Proportional(super::Proportional);
 
+    /// Select parents with a probability that is proportional to their score
+    /// raised to a power.
+    ///
+    /// >   probability(i) = score(i)^k / sum(score(x)^k for x in population)
+    ///
+    /// This method extends the proportional method with a continuously
+    /// adjustable selection pressure. Exponents greater than one amplify the
+    /// differences between the scores, and exponents less than one diminish
+    /// them. At k = 1 this is equivalent to the proportional method.
+    ///
+    /// Negative or invalid (NaN) scores are discarded and those individuals are
+    /// not permitted to mate.
+    ///
+    /// Argument "exponent" is the power k which the scores are raised to.
+    #[pyclass(module = "mate_selection")]
+    struct PowerScaled(super::PowerScaled);
+
     /// Normalize the fitness scores into a standard normal distribution. First
     /// the scores are normalized into a standard distribution and then they
     /// are shifted by the cutoff, which is naturally measured in standard
@@ -187,7 +1481,7 @@ mod python {
     /// the magnitude and variation of the fitness scoring function.
     ///
     /// Argument "cutoff" is the minimum negative deviation required for mating.
-    #[pyclass]
+    #[pyclass(module = "mate_selection")]
     struct Normalized(super::Normalized);
 
     /// Select parents from the best ranked individuals in the population.
@@ -195,7 +1489,8 @@ mod python {
     /// random probability.
     ///
     /// Argument "number" is the number of individuals who are allowed to mate.
-    #[pyclass]
+    /// If the population is smaller than this then everyone is allowed to mate.
+    #[pyclass(module = "mate_selection")]
     struct Best(super::Best);
 
     /// Apply a simple percentile based threshold to the population.
@@ -205,7 +1500,7 @@ mod python {
     /// Argument "percentile" is the fraction of the population which is denied
     /// the chance to mate. At "0" everyone is allowed to mate and at "1" only the
     /// single best individual is allowed to mate.
-    #[pyclass]
+    #[pyclass(module = "mate_selection")]
     struct Percentile(super::Percentile);
 
     /// Select parents based on their ranking in the population. This method
@@ -220,7 +1515,7 @@ mod python {
     /// probability of being selected. Must be in the range [0, 1].
     /// * At zero, all members are equally likely to be selected.
     /// * At one, the worst ranked individual will never be selected.
-    #[pyclass]
+    #[pyclass(module = "mate_selection")]
     struct RankedLinear(super::RankedLinear);
 
     /// Select parents based on their ranking in the population, with an
@@ -236,9 +1531,160 @@ mod python {
     /// Approximately half of the sample will be drawn from individuals ranked
     /// better than the median, and the other half will be selected from
     /// individuals with a worse ranking than the median.
-    #[pyclass]
+    #[pyclass(module = "mate_selection")]
     struct RankedExponential(super::RankedExponential);
 
+    /// Goldberg's linear fitness scaling. The scores are transformed by a
+    /// linear function "a * score + b" which maps the best score to a multiple
+    /// of the average score. The scaled scores are then used for proportional
+    /// selection.
+    ///
+    /// >   max(scaled) = C * average(scaled)  
+    ///
+    /// Scaled scores which are less than zero are clamped to zero, and those
+    /// individuals are not permitted to mate. Invalid (NaN) scores are
+    /// discarded.
+    ///
+    /// Argument "multiple" is the expected number of offspring C of the best
+    /// individual, relative to an average individual. Must be at least one.
+    #[pyclass(module = "mate_selection")]
+    struct LinearScaled(super::LinearScaled);
+
+    /// Select parents based on their ranking in the population, with an
+    /// exponentially weighted bias towards better ranked individuals. This is
+    /// the same as the RankedExponential method except that the slope of the
+    /// weights curve is specified by its base instead of its median, which is
+    /// more convenient for small populations.
+    ///
+    /// >   weight(rank) = c ^ rank  
+    /// >   Where rank = 0 is the best individual and rank = N-1 is the worst.  
+    ///
+    /// Argument "base" is the ratio c between the weights of consecutively
+    /// ranked individuals. Must be in the range (0, 1).
+    #[pyclass(module = "mate_selection")]
+    struct RankedExponentialBase(super::RankedExponentialBase);
+
+    /// Subtract the worst score from all of the scores and then select parents
+    /// with a probability that is directly proportional to their shifted score.
+    ///
+    /// >   probability(i) = (score(i) - worst) / sum(score(x) - worst for x in population)
+    ///
+    /// This method is similar to the proportional method except that it is
+    /// not affected by the absolute values of the scores, and so it can deal
+    /// with negative scores. The worst individual is never selected.
+    ///
+    /// Optionally, the worst score can be tracked over a sliding window of
+    /// recent generations. Call the "record" method once per generation to
+    /// remember the worst score of each generation.
+    ///
+    /// Argument "window" is the number of previous generations to remember.
+    #[pyclass(module = "mate_selection")]
+    struct Windowed(super::Windowed);
+
+    /// Tournament selection holds a competition between randomly chosen
+    /// individuals, and the best scoring individual wins the tournament and is
+    /// selected. Ties are won by a random entrant. The entrants are chosen with
+    /// uniform random probability, with replacement.
+    ///
+    /// Argument "size" is the number of individuals which enter each
+    /// tournament. Larger tournaments apply more selection pressure.
+    #[pyclass(module = "mate_selection")]
+    struct Tournament(super::Tournament);
+
+    /// Probabilistic tournament selection is a variant of tournament selection
+    /// where the best entrant does not always win. The entrants are ranked by
+    /// their scores, and the best entrant wins with probability p. Otherwise
+    /// the second best entrant wins with probability p, and so on. If none of
+    /// the better entrants win then the worst entrant wins.
+    ///
+    /// Argument "size" is the number of individuals which enter each
+    /// tournament.
+    ///
+    /// Argument "probability" is the probability p that the best entrant wins
+    /// the tournament. Must be in the range [0.5, 1].
+    #[pyclass(module = "mate_selection")]
+    struct ProbabilisticTournament(super::ProbabilisticTournament);
+
+    /// Geometric ranking, as found in the GAOT toolbox by Joines & Houck. The
+    /// probability of selecting an individual decreases geometrically with its
+    /// rank.
+    ///
+    /// >   probability(rank) = q' * (1 - q) ^ (rank - 1)  
+    /// >   q' = q / (1 - (1 - q) ^ N)  
+    /// >   Where N is the population size, and  
+    /// >   Where rank = 1 is the best individual and rank = N is the worst.  
+    ///
+    /// Argument "q" is the probability of selecting the best individual,
+    /// before normalizing for the population size. Must be in the range (0, 1).
+    #[pyclass(module = "mate_selection")]
+    struct RankedGeometric(super::RankedGeometric);
+
+    /// Select parents based on their ranking in the population, with weights
+    /// which follow a half Gaussian curve over the ranks. This is a smoother
+    /// alternative to exponential ranking.
+    ///
+    /// >   weight(rank) = exp(-rank^2 / (2 * sigma^2))  
+    /// >   Where rank = 0 is the best individual and rank = N-1 is the worst.  
+    ///
+    /// Argument "sigma" is the standard deviation of the curve, measured in
+    /// number of individuals. Must be a positive finite number.
+    #[pyclass(module = "mate_selection")]
+    struct RankedGaussian(super::RankedGaussian);
+
+    /// Boltzmann selection, also known as softmax selection. The sampling
+    /// probability is an exponential function of the score.
+    ///
+    /// >   probability(i) = exp(score(i) / T) / sum(exp(score(x) / T) for x in population)
+    ///
+    /// Argument "temperature" T controls the selection pressure. A high
+    /// temperature will sample the individuals more equally, whereas a low
+    /// temperature will strongly favor the best individuals.
+    ///
+    /// Invalid (NaN) scores are discarded and those individuals are not
+    /// permitted to mate.
+    #[pyclass(module = "mate_selection")]
+    struct Boltzmann(super::Boltzmann);
+
+    /// Boltzmann selection with a temperature which decreases over time,
+    /// according to an exponential cooling schedule.
+    ///
+    /// >   T(generation) = initial_temperature * decay ^ generation
+    ///
+    /// Call the "set_generation" method to advance the schedule.
+    ///
+    /// Argument "initial_temperature" is the temperature at generation zero.
+    ///
+    /// Argument "decay" is the factor by which the temperature is multiplied
+    /// every generation. Must be in the range (0, 1].
+    ///
+    /// Argument "generation" is the starting generation of the schedule.
+    #[pyclass(module = "mate_selection")]
+    struct AnnealedBoltzmann(super::AnnealedBoltzmann);
+
+    /// Unbiased tournament selection, as described by Sokolov & Whitley (2005).
+    /// This is a variant of tournament selection where every individual enters
+    /// the same number of tournaments. The tournaments are formed by lining up
+    /// several random permutations of the population, such that no individual
+    /// competes against itself.
+    ///
+    /// Argument "size" is the number of individuals which enter each
+    /// tournament.
+    #[pyclass(module = "mate_selection")]
+    struct UnbiasedTournament(super::UnbiasedTournament);
+
+    /// Select parents with a probability that is proportional to the distance
+    /// between their score and the average score.
+    ///
+    /// >   probability(i) = |score(i) - mean| / sum(|score(x) - mean| for x in population)
+    ///
+    /// This method favors both the best and the worst individuals, at the
+    /// expense of the average individuals.
+    ///
+    /// Invalid (NaN) scores are discarded and those individuals are not
+    /// permitted to mate.
+    #[pyclass(module = "mate_selection")]
+    struct Disruptive(super::Disruptive);
+
     #[pymethods]
     impl Random {
         #[new]
@@ -248,23 +1694,52 @@ mod python {
         fn __str__(&self) -> String {
             "mate_selection.Random()".to_string()
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, ()) {
+            (slf.get_type(), ())
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::Random as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Random as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores.data)
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
 
@@ -277,23 +1752,122 @@ mod python {
         fn __str__(&self) -> String {
             "mate_selection.Proportional()".to_string()
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, ()) {
+            (slf.get_type(), ())
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Proportional as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl PowerScaled {
+        #[new]
+        fn new(exponent: f64) -> PyResult<Self> {
+            if exponent.is_finite() && exponent > 0.0 {
+                Ok(Self(super::PowerScaled(exponent)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"exponent\" is not a positive finite number",
+                ))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.PowerScaled({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::Proportional as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::PowerScaled as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
 
@@ -312,23 +1886,55 @@ mod python {
         fn __str__(&self) -> String {
             format!("mate_selection.Normalized({})", self.0 .0)
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::Normalized as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Normalized as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
 
@@ -347,23 +1953,52 @@ mod python {
         fn __str__(&self) -> String {
             format!("mate_selection.Best({})", self.0 .0)
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (usize,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::Best as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Best as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores.data)
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
 
@@ -382,23 +2017,55 @@ mod python {
         fn __str__(&self) -> String {
             format!("mate_selection.Percentile({})", self.0 .0)
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::Percentile as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Percentile as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
 
@@ -414,26 +2081,73 @@ mod python {
                 ))
             }
         }
+        /// Baker's parameterization of linear ranking.
+        ///
+        /// Argument "sp" is the expected number of offspring of the best
+        /// individual, relative to an average individual. Must be in the
+        /// range [1, 2].
+        #[staticmethod]
+        fn from_sp(sp: f64) -> PyResult<Self> {
+            if (1.0..=2.0).contains(&sp) {
+                Ok(Self(super::RankedLinear::from_sp(sp)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"sp\" is out of bounds [1, 2]",
+                ))
+            }
+        }
         fn __str__(&self) -> String {
             format!("mate_selection.RankedLinear({})", self.0 .0)
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::RankedLinear as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::RankedLinear as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
 
@@ -452,469 +2166,5148 @@ mod python {
         fn __str__(&self) -> String {
             format!("mate_selection.RankedExponential({})", self.0 .0)
         }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (usize,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
         /// Choose multiple weighted pairs
         /// * Argument "amount" is the number of pairs to return.
         /// * Argument "scores" is the list of reproductive fitness scores.
         /// * Returns a list of pairs of parents to mate together.
         ///   The parents are specified as indices into the scores list.
-        fn pairs(&self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
-            let rng = &mut rand::thread_rng();
-            self.0.pairs(rng, amount, scores)
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
         }
         /// Choose multiple weighted
-        fn select(&self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-            let rng = &mut rand::thread_rng();
-            self.0.select(rng, amount, scores)
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
         }
         /// Probability distribution function
-        fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
-            <super::RankedExponential as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores)
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::RankedExponential as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
         }
     }
-}
 
-impl<R: Rng + ?Sized> MateSelection<R> for Random {
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        scores.fill(1.0);
-        scores
+    #[pymethods]
+    impl LinearScaled {
+        #[new]
+        fn new(multiple: f64) -> PyResult<Self> {
+            if multiple.is_finite() && multiple >= 1.0 {
+                Ok(Self(super::LinearScaled(multiple)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"multiple\" is not a finite number greater than or equal to one",
+                ))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.LinearScaled({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::LinearScaled as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
     }
 
-    fn pdf(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        if !scores.is_empty() {
-            let p = 1.0 / scores.len() as f64;
+    #[pymethods]
+    impl RankedExponentialBase {
+        #[new]
+        fn new(base: f64) -> PyResult<Self> {
+            if base > 0.0 && base < 1.0 {
+                Ok(Self(super::RankedExponentialBase(base)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"base\" is out of bounds (0, 1)",
+                ))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.RankedExponentialBase({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::RankedExponentialBase as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl Windowed {
+        #[new]
+        #[pyo3(signature = (window=0))]
+        fn new(window: usize) -> Self {
+            Self(super::Windowed::new(window))
+        }
+        /// Remember the worst score of this generation.
+        fn record(&mut self, scores: Scores) {
+            self.0.record(&scores.data)
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.Windowed({})", self.0.window)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (usize,), Vec<f64>) {
+            let this = slf.borrow();
+            let history = this.0.history.iter().copied().collect();
+            (slf.get_type(), (this.0.window,), history)
+        }
+        fn __setstate__(&mut self, history: Vec<f64>) {
+            self.0.history = history.into();
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Windowed as MateSelection<rand::rngs::ThreadRng>>::pdf(&self.0, scores.data)
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl Tournament {
+        #[new]
+        fn new(size: usize) -> PyResult<Self> {
+            if size > 0 {
+                Ok(Self(super::Tournament(size)))
+            } else {
+                Err(PyValueError::new_err("argument \"size\" is less than one"))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.Tournament({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (usize,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Tournament as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl ProbabilisticTournament {
+        #[new]
+        fn new(size: usize, probability: f64) -> PyResult<Self> {
+            if size == 0 {
+                Err(PyValueError::new_err("argument \"size\" is less than one"))
+            } else if !(0.5..=1.0).contains(&probability) {
+                Err(PyValueError::new_err(
+                    "argument \"probability\" is out of bounds [0.5, 1]",
+                ))
+            } else {
+                Ok(Self(super::ProbabilisticTournament { size, probability }))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!(
+                "mate_selection.ProbabilisticTournament({}, {})",
+                self.0.size, self.0.probability
+            )
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (usize, f64)) {
+            let this = slf.borrow();
+            (slf.get_type(), (this.0.size, this.0.probability))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::ProbabilisticTournament as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl RankedGeometric {
+        #[new]
+        fn new(q: f64) -> PyResult<Self> {
+            if q > 0.0 && q < 1.0 {
+                Ok(Self(super::RankedGeometric(q)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"q\" is out of bounds (0, 1)",
+                ))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.RankedGeometric({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::RankedGeometric as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl RankedGaussian {
+        #[new]
+        fn new(sigma: f64) -> PyResult<Self> {
+            if sigma.is_finite() && sigma > 0.0 {
+                Ok(Self(super::RankedGaussian(sigma)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"sigma\" is not a positive finite number",
+                ))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.RankedGaussian({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::RankedGaussian as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl Boltzmann {
+        #[new]
+        fn new(temperature: f64) -> PyResult<Self> {
+            if temperature.is_finite() && temperature > 0.0 {
+                Ok(Self(super::Boltzmann(temperature)))
+            } else {
+                Err(PyValueError::new_err(
+                    "argument \"temperature\" is not a positive finite number",
+                ))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.Boltzmann({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Boltzmann as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl AnnealedBoltzmann {
+        #[new]
+        #[pyo3(signature = (initial_temperature, decay, generation=0))]
+        fn new(initial_temperature: f64, decay: f64, generation: u64) -> PyResult<Self> {
+            if !(initial_temperature.is_finite() && initial_temperature > 0.0) {
+                Err(PyValueError::new_err(
+                    "argument \"initial_temperature\" is not a positive finite number",
+                ))
+            } else if !(decay > 0.0 && decay <= 1.0) {
+                Err(PyValueError::new_err(
+                    "argument \"decay\" is out of bounds (0, 1]",
+                ))
+            } else {
+                let mut method = super::AnnealedBoltzmann::new(initial_temperature, decay);
+                method.set_generation(generation);
+                Ok(Self(method))
+            }
+        }
+        /// Advance the cooling schedule to the given generation.
+        fn set_generation(&mut self, generation: u64) {
+            self.0.set_generation(generation)
+        }
+        /// The temperature at the current generation.
+        fn temperature(&self) -> f64 {
+            self.0.temperature()
+        }
+        fn __str__(&self) -> String {
+            format!(
+                "mate_selection.AnnealedBoltzmann({}, {}, generation={})",
+                self.0.initial_temperature, self.0.decay, self.0.generation
+            )
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (f64, f64), u64) {
+            let this = slf.borrow();
+            let args = (this.0.initial_temperature, this.0.decay);
+            (slf.get_type(), args, this.0.generation)
+        }
+        fn __setstate__(&mut self, generation: u64) {
+            self.0.set_generation(generation)
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::AnnealedBoltzmann as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl UnbiasedTournament {
+        #[new]
+        fn new(size: usize) -> PyResult<Self> {
+            if size > 0 {
+                Ok(Self(super::UnbiasedTournament(size)))
+            } else {
+                Err(PyValueError::new_err("argument \"size\" is less than one"))
+            }
+        }
+        fn __str__(&self) -> String {
+            format!("mate_selection.UnbiasedTournament({})", self.0 .0)
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (usize,)) {
+            (slf.get_type(), (slf.borrow().0 .0,))
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::UnbiasedTournament as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+
+    #[pymethods]
+    impl Disruptive {
+        #[new]
+        fn new() -> Self {
+            Self(super::Disruptive)
+        }
+        fn __str__(&self) -> String {
+            "mate_selection.Disruptive()".to_string()
+        }
+        /// Support for pickle and copy.
+        fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, ()) {
+            (slf.get_type(), ())
+        }
+        /// Choose multiple weighted pairs
+        /// * Argument "amount" is the number of pairs to return.
+        /// * Argument "scores" is the list of reproductive fitness scores.
+        /// * Returns a list of pairs of parents to mate together.
+        ///   The parents are specified as indices into the scores list.
+        /// * Argument "seed" makes the results reproducible. If missing then
+        ///   the results are random, unless the session was seeded with
+        ///   mate_selection.seed().
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn pairs(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let pairs = py.detach(|| self.0.pairs(rng, amount, scores.data));
+            pairs_output(py, pairs, numpy)
+        }
+        /// Choose multiple weighted
+        #[pyo3(signature = (amount, scores, seed=None))]
+        fn select(
+            &self,
+            py: Python,
+            amount: usize,
+            scores: Scores,
+            seed: Option<u64>,
+        ) -> PyResult<Py<PyAny>> {
+            let rng = &mut rng(seed);
+            let numpy = scores.numpy;
+            let selected = py.detach(|| self.0.select(rng, amount, scores.data));
+            select_output(py, selected, numpy)
+        }
+        /// Probability distribution function
+        fn pdf(&self, py: Python, scores: Scores) -> PyResult<Py<PyAny>> {
+            let numpy = scores.numpy;
+            let pdf = py.detach(|| {
+                <super::Disruptive as MateSelection<rand::rngs::ThreadRng>>::pdf(
+                    &self.0,
+                    scores.data,
+                )
+            });
+            pdf_output(py, pdf, numpy)
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> MateSelection<R> for Random {
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        scores.fill(1.0);
+    }
+
+    fn pdf(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        if !scores.is_empty() {
+            let p = 1.0 / scores.len() as f64;
             scores.fill(p);
         }
-        scores
+        scores
+    }
+
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        sus::choose_multiple(rng, amount, scores.len())
+    }
+    fn select_from_iter<I>(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: I,
+        _capacity: usize,
+    ) -> Vec<usize>
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        sus::choose_multiple(rng, amount, scores.into_iter().count())
+    }
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        assert!(amount == 0 || !scores.is_empty());
+        output.clear();
+        buffer.choose_multiple(rng, amount, scores.len(), output);
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+}
+
+impl<R: Rng + ?Sized> MateSelection<R> for Proportional {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        // Replace negative & invalid values with zero.
+        for x in scores.iter_mut() {
+            *x = x.max(0.0);
+        }
+    }
+    fn log_sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        for x in scores.iter_mut() {
+            *x = x.max(0.0).ln();
+        }
+        scores
+    }
+}
+
+impl PowerScaled {
+    pub fn try_new(exponent: f64) -> Result<Self, SelectionError> {
+        let method = Self(exponent);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let exponent = self.0;
+        if exponent.is_finite() && exponent > 0.0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument(
+                "exponent",
+                "is not a positive finite number",
+            ))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for PowerScaled {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let exponent = self.0;
+        expect_valid(self.validate());
+        // Divide by the best score to avoid overflowing the power.
+        let max = scores.iter().copied().fold(0.0, f64::max);
+        let div_max = if max > 0.0 { 1.0 / max } else { 0.0 };
+        // Replace negative & invalid values with zero.
+        for x in scores.iter_mut() {
+            *x = (x.max(0.0) * div_max).powf(exponent);
+        }
+    }
+    fn log_sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        let exponent = self.0;
+        expect_valid(self.validate());
+        for x in scores.iter_mut() {
+            *x = exponent * x.max(0.0).ln();
+        }
+        scores
+    }
+}
+
+impl LinearScaled {
+    pub fn try_new(multiple: f64) -> Result<Self, SelectionError> {
+        let method = Self(multiple);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let multiple = self.0;
+        if multiple.is_finite() && multiple >= 1.0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument(
+                "multiple",
+                "is not a finite number greater than or equal to one",
+            ))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for LinearScaled {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let multiple = self.0;
+        expect_valid(self.validate());
+        // Find the statistics of the valid scores.
+        let mut sum = 0.0;
+        let mut num = 0;
+        let mut max = f64::NEG_INFINITY;
+        for x in scores.iter().filter(|x| !x.is_nan()) {
+            sum += x;
+            num += 1;
+            max = max.max(*x);
+        }
+        let mean = sum / num as f64;
+        // Solve for the linear transformation. The weights are only relative,
+        // so scale the average to one. This also works for negative scores.
+        let (a, b) = if num == 0 || max - mean <= 0.0 {
+            (0.0, 1.0) // All scores are equal.
+        } else {
+            let a = (multiple - 1.0) / (max - mean);
+            (a, 1.0 - a * mean)
+        };
+        // Replace negative & invalid values with zero.
+        for x in scores.iter_mut() {
+            *x = (a * *x + b).max(0.0);
+        }
+    }
+}
+
+impl Windowed {
+    /// Argument "window" is the number of previous generations whose worst
+    /// scores are remembered. A window of zero only uses the current scores.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            history: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Remember the worst score of this generation.
+    pub fn record(&mut self, scores: &[f64]) {
+        if self.window == 0 {
+            return;
+        }
+        if let Some(worst) = min_score(scores) {
+            if self.history.len() == self.window {
+                self.history.pop_front();
+            }
+            self.history.push_back(worst);
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for Windowed {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let history = self.history.iter().copied().reduce(f64::min);
+        let worst = match (min_score(scores), history) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => a.or(b).unwrap_or(0.0),
+        };
+        // If all of the valid scores are equal to the worst score, then give
+        // them equal weights instead of all zero weights.
+        let all_worst = scores.iter().all(|&x| x.is_nan() || x <= worst);
+        // Replace negative & invalid values with zero.
+        for x in scores.iter_mut() {
+            *x = if x.is_nan() {
+                0.0
+            } else if all_worst {
+                1.0
+            } else {
+                (*x - worst).max(0.0)
+            };
+        }
+    }
+}
+
+/// Find the smallest valid score, ignoring NaN's.
+fn min_score(scores: &[f64]) -> Option<f64> {
+    scores
+        .iter()
+        .copied()
+        .filter(|x| !x.is_nan())
+        .reduce(f64::min)
+}
+
+impl<R: Rng + ?Sized> MateSelection<R> for Disruptive {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        // Find the average of the valid scores.
+        let (sum, num) = scores
+            .iter()
+            .filter(|x| !x.is_nan())
+            .fold((0.0, 0), |(sum, num), x| (sum + x, num + 1));
+        let mean = sum / num as f64;
+        // Replace invalid values with zero.
+        for x in scores.iter_mut() {
+            *x = (*x - mean).abs().max(0.0);
+        }
+    }
+}
+
+impl Normalized {
+    pub fn try_new(cutoff: f64) -> Result<Self, SelectionError> {
+        let method = Self(cutoff);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let cutoff = self.0;
+        if cutoff.is_finite() {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("cutoff", "is not finite"))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for Normalized {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let cutoff = self.0;
+        expect_valid(self.validate());
+
+        // Find and normalize by the average score.
+        let mean = sum_map(scores, |x| x) / scores.len() as f64;
+        for x in scores.iter_mut() {
+            *x -= mean;
+        }
+        // Find and normalize by the standard deviation of the scores.
+        let var = sum_map(scores, |x| x * x) / scores.len() as f64;
+        let std = var.sqrt();
+        for x in scores.iter_mut() {
+            // Shift the entire distribution and cutoff all scores which
+            // are less than zero.
+            *x = (*x / std - cutoff).max(0.0);
+        }
+    }
+}
+
+/// Apply a function to every value and sum the results. The "simd" feature
+/// vectorizes this, at the cost of slightly different rounding.
+#[cfg(not(feature = "simd"))]
+#[inline]
+fn sum_map(data: &[f64], f: impl Fn(f64) -> f64) -> f64 {
+    data.iter().map(|&x| f(x)).sum()
+}
+#[cfg(feature = "simd")]
+use simd::sum_map;
+
+/// Replace a probability distribution function with the uniform distribution
+/// if all of its weights are zero, in which case it is invalid (NaN).
+pub(crate) fn uniform_if_zero(pdf: &mut [f64]) {
+    let sum: f64 = pdf.iter().sum();
+    if sum.is_nan() || sum <= 0.0 {
+        pdf.fill(1.0 / pdf.len() as f64);
+    }
+}
+
+/// Total ordering of floating point numbers, for use as a key in collections.
+#[derive(Debug, Copy, Clone)]
+struct TotalOrd(f64);
+
+impl PartialEq for TotalOrd {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+impl Eq for TotalOrd {}
+impl PartialOrd for TotalOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for TotalOrd {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Weighted random sampling without replacement, as described by
+/// Efraimidis & Spirakis (2006). Individuals with zero weight are chosen last.
+fn choose_distinct<R: Rng + ?Sized>(rng: &mut R, amount: usize, weights: &[f64]) -> Vec<usize> {
+    let amount = amount.min(weights.len());
+    // Sort by "ln(u) / weight", descending.
+    let mut keys = Vec::with_capacity(weights.len());
+    for (i, &w) in weights.iter().enumerate() {
+        assert!(
+            w.is_finite() && w >= 0.0,
+            "invalid weight: weights[{i}] = {w} (must be finite and >= 0)"
+        );
+        if w > 0.0 {
+            keys.push((rng.random::<f64>().ln() / w, i));
+        }
+    }
+    let descending = |a: &(f64, usize), b: &(f64, usize)| b.0.total_cmp(&a.0);
+    if keys.len() > amount {
+        keys.select_nth_unstable_by(amount, descending);
+        keys.truncate(amount);
+    }
+    keys.sort_unstable_by(descending);
+    let mut selected: Vec<usize> = keys.into_iter().map(|(_, i)| i).collect();
+    if selected.len() < amount {
+        let mut rest: Vec<usize> = (0..weights.len()).filter(|&i| weights[i] == 0.0).collect();
+        rest.shuffle(rng);
+        rest.truncate(amount - selected.len());
+        selected.append(&mut rest);
+    }
+    selected
+}
+
+/// Collect a stream of scores into a vector.
+fn collect_scores(scores: impl IntoIterator<Item = f64>, capacity: usize) -> Vec<f64> {
+    let mut data = Vec::with_capacity(capacity);
+    data.extend(scores);
+    data
+}
+
+/// Find the indices of the largest values, in no particular order.
+///
+/// This finds the cutoff in a single pass over the borrowed data, without
+/// copying or fully sorting it. Small amounts are kept in a bounded heap,
+/// otherwise a list of indices is partially sorted. Ties at the cutoff are
+/// broken in favor of the lowest index.
+fn arg_nth_max(amount: usize, data: &[f64]) -> Vec<usize> {
+    let amount = amount.min(data.len());
+    if amount == 0 {
+        return vec![];
+    }
+    if amount < data.len() / 64 {
+        return heap_nth_max(amount, data.iter().copied());
+    }
+    let mut index: Vec<usize> = (0..data.len()).collect();
+    if amount < data.len() {
+        index.select_nth_unstable_by(amount - 1, |&a, &b| {
+            data[b].total_cmp(&data[a]).then(a.cmp(&b))
+        });
+        index.truncate(amount);
+    }
+    index
+}
+
+/// Find the indices of the largest values in a stream, in no particular order.
+fn heap_nth_max(amount: usize, data: impl IntoIterator<Item = f64>) -> Vec<usize> {
+    // Keep only the largest values seen so far, in a min-heap.
+    let mut best = BinaryHeap::with_capacity(amount);
+    for (index, score) in data.into_iter().enumerate() {
+        let item = Reverse((TotalOrd(score), Reverse(index)));
+        if best.len() < amount {
+            best.push(item);
+        } else if let Some(mut worst) = best.peek_mut() {
+            if item < *worst {
+                *worst = item;
+            }
+        }
+    }
+    best.into_iter().map(|Reverse((_, Reverse(i)))| i).collect()
+}
+
+fn zero_and_write_sparse(data: &mut [f64], index: &[usize], value: f64) {
+    data.fill(0.0);
+    for i in index {
+        data[*i] = value;
+    }
+}
+
+impl Best {
+    pub fn try_new(number: usize) -> Result<Self, SelectionError> {
+        let method = Self(number);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        if self.0 > 0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("number", "is less than one"))
+        }
+    }
+
+    fn args(&self) -> usize {
+        expect_valid(self.validate());
+        self.0
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for Best {
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        let num_best = self.args();
+        let index = arg_nth_max(num_best, scores);
+        let sample = sus::choose_multiple(rng, amount, index.len());
+        sample.iter().map(|&s| index[s]).collect()
+    }
+    fn select_from_iter<I>(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: I,
+        _capacity: usize,
+    ) -> Vec<usize>
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let index = heap_nth_max(self.args(), scores);
+        let sample = sus::choose_multiple(rng, amount, index.len());
+        sample.iter().map(|&s| index[s]).collect()
+    }
+    fn pdf(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        let num_best = self.args();
+        let index = arg_nth_max(num_best, &scores);
+        zero_and_write_sparse(&mut scores, &index, 1.0 / index.len() as f64);
+        scores
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let num_best = self.args();
+        let index = arg_nth_max(num_best, scores);
+        zero_and_write_sparse(scores, &index, 1.0);
+    }
+    fn sample_weight_sparse(&self, scores: &[f64]) -> SparseWeights {
+        SparseWeights::uniform(scores.len(), arg_nth_max(self.args(), scores))
+    }
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        let eligible = self.args().min(population);
+        truncation_takeover(eligible as f64 / population as f64, population)
+            .or_else(|| takeover::simulate_takeover(self, rng, population))
+    }
+}
+
+impl Percentile {
+    pub fn try_new(percentile: f64) -> Result<Self, SelectionError> {
+        let method = Self(percentile);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        if (0.0..=1.0).contains(&self.0) {
+            Ok(())
+        } else {
+            Err(SelectionError::argument(
+                "percentile",
+                "is out of bounds [0, 1]",
+            ))
+        }
+    }
+
+    fn get_index(&self, scores: &[f64]) -> Vec<usize> {
+        let percentile = self.0;
+        expect_valid(self.validate());
+        let num_eligible = ((1.0 - percentile) * scores.len() as f64).round() as usize;
+        arg_nth_max(num_eligible.max(1), scores)
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for Percentile {
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        let index = self.get_index(scores);
+        let sample = sus::choose_multiple(rng, amount, index.len());
+        sample.iter().map(|&s| index[s]).collect()
+    }
+    fn pdf(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        let index = self.get_index(&scores);
+        zero_and_write_sparse(&mut scores, &index, 1.0 / index.len() as f64);
+        scores
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let index = self.get_index(scores);
+        zero_and_write_sparse(scores, &index, 1.0);
+    }
+    fn sample_weight_sparse(&self, scores: &[f64]) -> SparseWeights {
+        SparseWeights::uniform(scores.len(), self.get_index(scores))
+    }
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        expect_valid(self.validate());
+        let eligible = ((1.0 - self.0) * population as f64).round().max(1.0);
+        truncation_takeover(eligible / population as f64, population)
+            .or_else(|| takeover::simulate_takeover(self, rng, population))
+    }
+}
+
+impl RankedLinear {
+    /// Baker's parameterization of linear ranking.
+    ///
+    /// Argument "**sp**" is the expected number of offspring of the best
+    /// individual, relative to an average individual. Must be in the range
+    /// [1, 2]. The worst individual is expected to have `2 - sp` offspring.
+    pub fn from_sp(sp: f64) -> Self {
+        expect_valid(Self::validate_sp(sp));
+        Self(sp - 1.0)
+    }
+
+    /// Same as [RankedLinear::from_sp] except that it returns an error
+    /// instead of panicking if the argument is out of bounds.
+    pub fn try_from_sp(sp: f64) -> Result<Self, SelectionError> {
+        Self::validate_sp(sp).map(|()| Self(sp - 1.0))
+    }
+
+    fn validate_sp(sp: f64) -> Result<(), SelectionError> {
+        if (1.0..=2.0).contains(&sp) {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("sp", "is out of bounds [1, 2]"))
+        }
+    }
+}
+impl RankedLinear {
+    pub fn try_new(selection_pressure: f64) -> Result<Self, SelectionError> {
+        let method = Self(selection_pressure);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let selection_pressure = self.0;
+        if (0.0..=1.0).contains(&selection_pressure) {
+            Ok(())
+        } else {
+            Err(SelectionError::argument(
+                "selection_pressure",
+                "is out of bounds [0, 1]",
+            ))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for RankedLinear {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let order = argsort(scores);
+        self.rank_weights(&order, scores);
+    }
+}
+impl RankedLinear {
+    /// Write the sampling weights, given the indices sorted from worst to best.
+    pub(crate) fn rank_weights(&self, order: &[usize], weights: &mut [f64]) {
+        let selection_pressure = self.0;
+        expect_valid(self.validate());
+
+        let div_n = if order.len() == 1 {
+            0.0 // Value does not matter, just don't crash.
+        } else {
+            1.0 / (order.len() - 1) as f64
+        };
+        for (rank, index) in order.iter().enumerate() {
+            // Reverse the ranking from ascending to descending order
+            // so that rank 0 is the best & rank N-1 is the worst.
+            let rank = order.len() - 1 - rank;
+            // Scale the ranking into the range [0, 1].
+            let rank = rank as f64 * div_n;
+            weights[*index] = 1.0 + selection_pressure - 2.0 * selection_pressure * rank;
+        }
+    }
+}
+
+impl RankedExponential {
+    pub fn try_new(median: usize) -> Result<Self, SelectionError> {
+        let method = Self(median);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let median = self.0;
+        if median > 0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("median", "is less than one"))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for RankedExponential {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let order = argsort(scores);
+        self.rank_weights(&order, scores);
+    }
+}
+impl RankedExponential {
+    /// Write the sampling weights, given the indices sorted from worst to best.
+    pub(crate) fn rank_weights(&self, order: &[usize], weights: &mut [f64]) {
+        let median = self.0;
+        expect_valid(self.validate());
+        for (rank, index) in order.iter().enumerate() {
+            let rank = order.len() - rank - 1;
+            weights[*index] = (-(2.0_f64.ln()) * rank as f64 / median as f64).exp();
+        }
+    }
+}
+
+impl RankedExponentialBase {
+    pub fn try_new(base: f64) -> Result<Self, SelectionError> {
+        let method = Self(base);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let base = self.0;
+        if base > 0.0 && base < 1.0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("base", "is out of bounds (0, 1)"))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for RankedExponentialBase {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let order = argsort(scores);
+        self.rank_weights(&order, scores);
+    }
+}
+impl RankedExponentialBase {
+    /// Write the sampling weights, given the indices sorted from worst to best.
+    pub(crate) fn rank_weights(&self, order: &[usize], weights: &mut [f64]) {
+        let base = self.0;
+        expect_valid(self.validate());
+        for (rank, index) in order.iter().enumerate() {
+            let rank = order.len() - rank - 1;
+            weights[*index] = base.powi(rank as i32);
+        }
+    }
+}
+
+impl Tournament {
+    pub fn try_new(size: usize) -> Result<Self, SelectionError> {
+        let method = Self(size);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        self.args().validate()
+    }
+
+    fn args(&self) -> ProbabilisticTournament {
+        ProbabilisticTournament {
+            size: self.0,
+            probability: 1.0,
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for Tournament {
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        MateSelection::<R>::select_slice(&self.args(), rng, amount, scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        MateSelection::<R>::sample_weight_in_place(&self.args(), scores)
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        MateSelection::<R>::loss_of_diversity(&self.args(), amount, scores)
+    }
+    fn takeover_time(&self, rng: &mut R, population: usize) -> Option<f64> {
+        expect_valid(self.validate());
+        if population <= 1 {
+            Some(0.0)
+        } else if self.0 < 2 {
+            takeover::simulate_takeover(self, rng, population)
+        } else {
+            // Goldberg & Deb (1991), "A Comparative Analysis of Selection
+            // Schemes Used in Genetic Algorithms".
+            let n = population as f64;
+            Some(((n.ln() + n.ln().ln()) / (self.0 as f64).ln()).max(1.0))
+        }
+    }
+}
+
+/// Takeover time of truncation selection, which keeps the given fraction of
+/// the population, see Mühlenbein & Schlierkamp-Voosen (1993), "Predictive
+/// Models for the Breeder Genetic Algorithm". Returns None if everyone is
+/// allowed to mate, since then there is no selection pressure.
+fn truncation_takeover(fraction: f64, population: usize) -> Option<f64> {
+    if population <= 1 {
+        Some(0.0)
+    } else if fraction >= 1.0 {
+        None
+    } else {
+        Some(((population as f64).ln() / fraction.recip().ln()).max(1.0))
+    }
+}
+
+impl UnbiasedTournament {
+    pub fn try_new(size: usize) -> Result<Self, SelectionError> {
+        let method = Self(size);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        if self.0 > 0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("size", "is less than one"))
+        }
+    }
+
+    fn args(&self, num: usize) -> usize {
+        expect_valid(self.validate());
+        self.0.min(num)
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for UnbiasedTournament {
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        let size = self.args(scores.len());
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let num = scores.len();
+        let mut permutation: Vec<usize> = (0..num).collect();
+        let mut selected = Vec::with_capacity(amount.div_ceil(num) * num);
+        while selected.len() < amount {
+            // Line up rotated copies of a random permutation. Every individual
+            // enters exactly "size" tournaments per round, and the rotations
+            // are distinct so the entrants of each tournament are distinct.
+            permutation.shuffle(rng);
+            let rotations = rand::seq::index::sample(rng, num, size).into_vec();
+            for index in 0..num {
+                let mut slot = rotations.iter();
+                selected.push(tournament(rng, scores, size, 1.0, |_| {
+                    permutation[(index + slot.next().unwrap()) % num]
+                }));
+            }
+        }
+        // The tournaments are in random order, so this discards a random
+        // subset of the final round.
+        selected.truncate(amount);
+        selected.shuffle(rng);
+        selected
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let size = self.args(scores.len());
+        let num = scores.len();
+        // Probability that a random tournament contains none of the "excluded"
+        // individuals, choosing the entrants without replacement.
+        let p_exclude = |excluded: usize| -> f64 {
+            (0..size)
+                .map(|i| (num - excluded).saturating_sub(i) as f64 / (num - i) as f64)
+                .product()
+        };
+        let mut order: Vec<usize> = (0..num).collect();
+        order.sort_unstable_by(|&a, &b| cmp_score(scores[b], scores[a]));
+        let mut weights = vec![0.0; num];
+        let mut better = 0;
+        for group in order.chunk_by(|&a, &b| cmp_score(scores[a], scores[b]).is_eq()) {
+            // The group wins if the tournament contains none of the better
+            // individuals and at least one of the group. Ties are won by a
+            // random entrant.
+            let p_group = p_exclude(better) - p_exclude(better + group.len());
+            for &i in group {
+                weights[i] = p_group / group.len() as f64;
+            }
+            better += group.len();
+        }
+        scores.copy_from_slice(&weights);
+    }
+}
+
+impl ProbabilisticTournament {
+    pub fn try_new(size: usize, probability: f64) -> Result<Self, SelectionError> {
+        let method = Self { size, probability };
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        if self.size == 0 {
+            Err(SelectionError::argument("size", "is less than one"))
+        } else if !(0.5..=1.0).contains(&self.probability) {
+            Err(SelectionError::argument(
+                "probability",
+                "is out of bounds [0.5, 1]",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn args(&self) -> (usize, f64) {
+        expect_valid(self.validate());
+        (self.size, self.probability)
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for ProbabilisticTournament {
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        let (size, probability) = self.args();
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let num = scores.len();
+        (0..amount)
+            .map(|_| {
+                tournament(rng, scores, size, probability, |rng| {
+                    rng.random_range(0..num)
+                })
+            })
+            .collect()
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let (size, probability) = self.args();
+        let uniform = vec![1.0 / scores.len() as f64; scores.len()];
+        let weights = tournament_weights(scores, &uniform, size, probability);
+        scores.copy_from_slice(&weights);
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        if scores.is_empty() {
+            return 0.0;
+        }
+        let num = scores.len() as f64;
+        // Every tournament is independent, so each individual is left out of
+        // all of them with probability `(1 - p) ^ amount`.
+        let pdf = MateSelection::<R>::pdf(self, scores);
+        pdf.iter()
+            .map(|p| (1.0 - p).powf(amount as f64))
+            .sum::<f64>()
+            / num
+    }
+}
+
+impl RankedGeometric {
+    pub fn try_new(q: f64) -> Result<Self, SelectionError> {
+        let method = Self(q);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let q = self.0;
+        if q > 0.0 && q < 1.0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("q", "is out of bounds (0, 1)"))
+        }
+    }
+}
+impl RankedGeometric {
+    /// Write the sampling weights, given the indices sorted from worst to best.
+    pub(crate) fn rank_weights(&self, order: &[usize], weights: &mut [f64]) {
+        let q = self.0;
+        expect_valid(self.validate());
+        // The normalizing factor does not matter, the weights are only relative.
+        RankedExponentialBase(1.0 - q).rank_weights(order, weights)
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for RankedGeometric {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let q = self.0;
+        expect_valid(self.validate());
+        // The normalizing factor does not matter, the weights are only relative.
+        let weights = RankedExponentialBase(1.0 - q);
+        MateSelection::<R>::sample_weight_in_place(&weights, scores)
+    }
+}
+
+impl RankedGaussian {
+    pub fn try_new(sigma: f64) -> Result<Self, SelectionError> {
+        let method = Self(sigma);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let sigma = self.0;
+        if sigma.is_finite() && sigma > 0.0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument(
+                "sigma",
+                "is not a positive finite number",
+            ))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for RankedGaussian {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let order = argsort(scores);
+        self.rank_weights(&order, scores);
+    }
+}
+impl RankedGaussian {
+    /// Write the sampling weights, given the indices sorted from worst to best.
+    pub(crate) fn rank_weights(&self, order: &[usize], weights: &mut [f64]) {
+        let sigma = self.0;
+        expect_valid(self.validate());
+        for (rank, index) in order.iter().enumerate() {
+            let rank = (order.len() - rank - 1) as f64;
+            weights[*index] = (-0.5 * (rank / sigma).powi(2)).exp();
+        }
+    }
+}
+
+impl Boltzmann {
+    pub fn try_new(temperature: f64) -> Result<Self, SelectionError> {
+        let method = Self(temperature);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        let temperature = self.0;
+        if temperature.is_finite() && temperature > 0.0 {
+            Ok(())
+        } else {
+            Err(SelectionError::argument(
+                "temperature",
+                "is not a positive finite number",
+            ))
+        }
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for Boltzmann {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let temperature = self.0;
+        expect_valid(self.validate());
+        // Subtract the best score to avoid overflowing the exponential.
+        let max = scores
+            .iter()
+            .copied()
+            .filter(|x| !x.is_nan())
+            .fold(f64::NEG_INFINITY, f64::max);
+        if max == f64::INFINITY {
+            for x in scores.iter_mut() {
+                *x = if *x == f64::INFINITY { 1.0 } else { 0.0 };
+            }
+            return;
+        }
+        for x in scores.iter_mut() {
+            let weight = ((*x - max) / temperature).exp();
+            // Replace invalid values with zero.
+            *x = if weight.is_nan() { 0.0 } else { weight };
+        }
+    }
+    fn log_sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        let temperature = self.0;
+        expect_valid(self.validate());
+        if scores.contains(&f64::INFINITY) {
+            for x in scores.iter_mut() {
+                *x = if *x == f64::INFINITY {
+                    0.0
+                } else {
+                    f64::NEG_INFINITY
+                };
+            }
+            return scores;
+        }
+        for x in scores.iter_mut() {
+            // Replace invalid values with zero weight.
+            *x = if x.is_nan() {
+                f64::NEG_INFINITY
+            } else {
+                *x / temperature
+            };
+        }
+        scores
+    }
+}
+
+impl AnnealedBoltzmann {
+    pub fn new(initial_temperature: f64, decay: f64) -> Self {
+        Self {
+            initial_temperature,
+            decay,
+            generation: 0,
+        }
+    }
+
+    /// Advance the cooling schedule to the given generation.
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    pub fn try_new(initial_temperature: f64, decay: f64) -> Result<Self, SelectionError> {
+        let method = Self::new(initial_temperature, decay);
+        method.validate().map(|()| method)
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        if !(self.initial_temperature.is_finite() && self.initial_temperature > 0.0) {
+            Err(SelectionError::argument(
+                "initial_temperature",
+                "is not a positive finite number",
+            ))
+        } else if !(self.decay > 0.0 && self.decay <= 1.0) {
+            Err(SelectionError::argument("decay", "is out of bounds (0, 1]"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The temperature at the current generation.
+    pub fn temperature(&self) -> f64 {
+        expect_valid(self.validate());
+        let temperature = self.initial_temperature * self.decay.powf(self.generation as f64);
+        // Don't let the temperature underflow to zero.
+        temperature.max(f64::MIN_POSITIVE)
+    }
+}
+impl<R: Rng + ?Sized> MateSelection<R> for AnnealedBoltzmann {
+    fn select_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<usize>,
+    ) {
+        buffer.select_weighted(self, rng, amount, scores, output)
+    }
+    fn pairs_into(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        scores: &[f64],
+        buffer: &mut SelectionBuffer,
+        output: &mut Vec<[usize; 2]>,
+    ) {
+        buffer.pairs_from_select(self, rng, amount, scores, output)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        MateSelection::<R>::sample_weight_in_place(&Boltzmann(self.temperature()), scores)
+    }
+    fn log_sample_weight(&self, scores: Vec<f64>) -> Vec<f64> {
+        MateSelection::<R>::log_sample_weight(&Boltzmann(self.temperature()), scores)
+    }
+}
+
+fn negate(mut scores: Vec<f64>) -> Vec<f64> {
+    for x in scores.iter_mut() {
+        *x = -*x;
+    }
+    scores
+}
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Inverse<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.0.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.0.select(rng, amount, negate(scores))
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.0.pdf(negate(scores))
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        self.0.loss_of_diversity(amount, negate(scores))
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        for x in scores.iter_mut() {
+            *x = -*x;
+        }
+        self.0.sample_weight_in_place(scores)
+    }
+}
+
+impl<M> Elitist<M> {
+    /// Number of elites in a sample of the given amount, excluding the invalid
+    /// (NaN) scores.
+    fn num_elites(&self, amount: usize, scores: &[f64]) -> usize {
+        let num_valid = scores.iter().filter(|x| !x.is_nan()).count();
+        self.keep.min(amount).min(num_valid)
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Elitist<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        let num_elites = self.num_elites(amount, scores);
+        let mut selected = argsort_best(scores);
+        selected.truncate(num_elites);
+        selected.append(&mut self.inner.select_slice(rng, amount - num_elites, scores));
+        selected.shuffle(rng);
+        selected
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        let num_elites = self.num_elites(amount, &scores);
+        let mut elites = argsort_best(&scores);
+        elites.truncate(num_elites);
+        let mut expected = self.inner.expected_offspring(amount - num_elites, scores);
+        for i in elites {
+            expected[i] += 1.0;
+        }
+        expected
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        self.inner.sample_weight_in_place(scores)
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Capped<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        if self.cap == 0 {
+            return Err(SelectionError::argument("cap", "is less than one"));
+        }
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        expect_valid(MateSelection::<R>::validate(self));
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let expected = self.expected_offspring(amount, scores);
+        // Stochastic universal sampling never selects an individual more than
+        // its expected number of copies, rounded up.
+        sus::choose_multiple_weighted(rng, amount, &expected)
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        expect_valid(MateSelection::<R>::validate(self));
+        if amount == 0 || scores.is_empty() {
+            return vec![0.0; scores.len()];
+        }
+        let mut weights = self.inner.sample_weight(scores);
+        if weights.iter().all(|&w| w == 0.0) {
+            weights.fill(1.0);
+        }
+        let num_eligible = weights.iter().filter(|&&w| w > 0.0).count();
+        let cap = self.cap.max(amount.div_ceil(num_eligible)) as f64;
+        // Find the expected number of copies of each individual and clip them
+        // to the cap. Redistribute the excess among the remaining individuals
+        // until none of them exceed the cap.
+        let total: f64 = weights.iter().sum();
+        let mut expected: Vec<f64> = weights.iter().map(|w| w * amount as f64 / total).collect();
+        let mut capped = vec![false; expected.len()];
+        loop {
+            let mut done = true;
+            for (e, c) in expected.iter_mut().zip(capped.iter_mut()) {
+                if !*c && *e > cap {
+                    *e = cap;
+                    *c = true;
+                    done = false;
+                }
+            }
+            if done {
+                break;
+            }
+            let fixed = cap * capped.iter().filter(|&&c| c).count() as f64;
+            let free: f64 = expected
+                .iter()
+                .zip(&capped)
+                .filter(|(_, c)| !**c)
+                .map(|(e, _)| e)
+                .sum();
+            if free <= 0.0 {
+                break;
+            }
+            let scale = (amount as f64 - fixed) / free;
+            for (e, c) in expected.iter_mut().zip(&capped) {
+                if !*c {
+                    *e *= scale;
+                }
+            }
+        }
+        expected
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(scores)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        self.inner.sample_weight_in_place(scores)
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Constrained<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        let scores = self.scores(scores);
+        self.inner.select(rng, amount, scores)
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(self.scores(scores))
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        if let Some(violations) = &self.violations {
+            let feasible = feasibility_scores(scores.to_vec(), violations);
+            scores.copy_from_slice(&feasible);
+        }
+        self.inner.sample_weight_in_place(scores)
+    }
+}
+
+impl<M> Constrained<M> {
+    pub fn new(inner: M) -> Self {
+        Self {
+            violations: None,
+            inner,
+        }
+    }
+
+    /// Set the total constraint violation of each individual.
+    pub fn set_violations(&mut self, violations: Vec<f64>) {
+        self.violations = Some(violations);
+    }
+
+    fn scores(&self, scores: Vec<f64>) -> Vec<f64> {
+        match &self.violations {
+            Some(violations) => feasibility_scores(scores, violations),
+            None => scores,
+        }
+    }
+}
+
+/// Apply the feasibility rules of Deb (2000) to the scores, see [Constrained].
+///
+/// Argument "violations" is the total constraint violation of each individual,
+/// where zero means that the individual is feasible.
+pub fn feasibility_scores(scores: Vec<f64>, violations: &[f64]) -> Vec<f64> {
+    assert_eq!(
+        scores.len(),
+        violations.len(),
+        "argument \"violations\" has the wrong length"
+    );
+    let feasible = |i: usize| violations[i] <= 0.0;
+    // Sort from worst to best. Infeasible individuals are compared by their
+    // violations, where invalid (NaN) violations are the worst.
+    let compare = |&a: &usize, &b: &usize| match (feasible(a), feasible(b)) {
+        (true, true) => scores[a].total_cmp(&scores[b]),
+        (false, false) => cmp_score(-violations[a], -violations[b]),
+        (a_feasible, b_feasible) => a_feasible.cmp(&b_feasible),
+    };
+    let mut order: Vec<usize> = (0..scores.len())
+        .filter(|&i| !(feasible(i) && scores[i].is_nan()))
+        .collect();
+    order.sort_by(compare);
+    let mut ranks = vec![f64::NAN; scores.len()];
+    let mut start = 0;
+    for group in order.chunk_by(|a, b| compare(a, b).is_eq()) {
+        let rank = start as f64 + (group.len() + 1) as f64 / 2.0;
+        for &i in group {
+            ranks[i] = rank;
+        }
+        start += group.len();
+    }
+    ranks
+}
+
+impl<F> RestrictedTournament<F>
+where
+    F: Fn(usize, usize) -> f64,
+{
+    pub fn new(window: usize, distance: F) -> Self {
+        Self { window, distance }
+    }
+
+    fn nearest(&self, candidate: usize, a: usize, b: usize) -> core::cmp::Ordering {
+        let dist_a = (self.distance)(candidate, a);
+        let dist_b = (self.distance)(candidate, b);
+        dist_a.total_cmp(&dist_b).then(a.cmp(&b))
+    }
+}
+impl<F> core::fmt::Debug for RestrictedTournament<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("RestrictedTournament")
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+impl<R, F> MateSelection<R> for RestrictedTournament<F>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize, usize) -> f64,
+{
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let window = self.window.min(scores.len() - 1);
+        (0..amount)
+            .map(|_| {
+                let candidate = rng.random_range(0..scores.len());
+                // Choose a random window which does not contain the candidate.
+                let nearest = rand::seq::index::sample(rng, scores.len() - 1, window)
+                    .into_iter()
+                    .map(|x| if x >= candidate { x + 1 } else { x })
+                    .min_by(|&a, &b| self.nearest(candidate, a, b));
+                match nearest {
+                    Some(nearest) if cmp_score(scores[nearest], scores[candidate]).is_gt() => {
+                        nearest
+                    }
+                    _ => candidate,
+                }
+            })
+            .collect()
+    }
+
+    /// Computes the exact selection probabilities, which takes quadratic time.
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let num = scores.len();
+        let window = self.window.min(num.saturating_sub(1));
+        let mut weights = vec![0.0; num];
+        let mut neighbors = Vec::with_capacity(num);
+        for candidate in 0..num {
+            if window == 0 {
+                weights[candidate] += 1.0;
+                continue;
+            }
+            neighbors.clear();
+            neighbors.extend((0..num).filter(|&x| x != candidate));
+            neighbors.sort_unstable_by(|&a, &b| self.nearest(candidate, a, b));
+            // The k'th nearest neighbor competes if it is in the window and
+            // none of the closer neighbors are in the window.
+            let others = (num - 1) as f64;
+            let mut prob = window as f64 / others;
+            for (k, &neighbor) in neighbors.iter().enumerate() {
+                if prob <= 0.0 {
+                    break;
+                }
+                if cmp_score(scores[neighbor], scores[candidate]).is_gt() {
+                    weights[neighbor] += prob;
+                } else {
+                    weights[candidate] += prob;
+                }
+                let remaining = others - k as f64;
+                prob *= (remaining - window as f64) / (remaining - 1.0);
+            }
+        }
+        scores.copy_from_slice(&weights);
+    }
+}
+
+impl<F> DoubleTournament<F>
+where
+    F: Fn(usize) -> f64,
+{
+    pub fn new(fitness_size: usize, parsimony_size: f64, fitness_first: bool, size: F) -> Self {
+        Self {
+            fitness_size,
+            parsimony_size,
+            fitness_first,
+            size,
+        }
+    }
+
+    /// Check that the arguments are valid.
+    pub fn validate(&self) -> Result<(), SelectionError> {
+        if self.fitness_size == 0 {
+            Err(SelectionError::argument("fitness_size", "is less than one"))
+        } else if !(1.0..=2.0).contains(&self.parsimony_size) {
+            Err(SelectionError::argument(
+                "parsimony_size",
+                "is out of bounds [1, 2]",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn args(&self) -> (usize, f64) {
+        expect_valid(self.validate());
+        (self.fitness_size, self.parsimony_size / 2.0)
+    }
+
+    /// Smaller individuals are better, so negate the sizes.
+    fn parsimony_keys(&self, num: usize) -> Vec<f64> {
+        (0..num).map(|i| -(self.size)(i)).collect()
+    }
+}
+impl<F> core::fmt::Debug for DoubleTournament<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("DoubleTournament")
+            .field("fitness_size", &self.fitness_size)
+            .field("parsimony_size", &self.parsimony_size)
+            .field("fitness_first", &self.fitness_first)
+            .finish_non_exhaustive()
+    }
+}
+impl<R, F> MateSelection<R> for DoubleTournament<F>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize) -> f64,
+{
+    fn validate(&self) -> Result<(), SelectionError> {
+        Self::validate(self)
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        let (fitness_size, parsimony_prob) = self.args();
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let num = scores.len();
+        let sizes = self.parsimony_keys(num);
+        (0..amount)
+            .map(|_| {
+                if self.fitness_first {
+                    tournament(rng, &sizes, 2, parsimony_prob, |rng| {
+                        tournament(rng, scores, fitness_size, 1.0, |rng| {
+                            rng.random_range(0..num)
+                        })
+                    })
+                } else {
+                    tournament(rng, scores, fitness_size, 1.0, |rng| {
+                        tournament(rng, &sizes, 2, parsimony_prob, |rng| {
+                            rng.random_range(0..num)
+                        })
+                    })
+                }
+            })
+            .collect()
+    }
+
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let (fitness_size, parsimony_prob) = self.args();
+        let num = scores.len();
+        let sizes = self.parsimony_keys(num);
+        let uniform = vec![1.0 / num as f64; num];
+        let weights = if self.fitness_first {
+            let inner = tournament_weights(scores, &uniform, fitness_size, 1.0);
+            tournament_weights(&sizes, &inner, 2, parsimony_prob)
+        } else {
+            let inner = tournament_weights(&sizes, &uniform, 2, parsimony_prob);
+            tournament_weights(scores, &inner, fitness_size, 1.0)
+        };
+        scores.copy_from_slice(&weights);
+    }
+}
+
+/// Run a single tournament and return the winner.
+///
+/// The entrants are drawn by the callback. They are ranked by their keys, and
+/// the best entrant wins with probability "p_best". Otherwise the second best
+/// entrant wins with probability "p_best", and so on.
+fn tournament<R, D>(rng: &mut R, keys: &[f64], size: usize, p_best: f64, mut draw: D) -> usize
+where
+    R: Rng + ?Sized,
+    D: FnMut(&mut R) -> usize,
+{
+    let mut entrants: Vec<usize> = (0..size).map(|_| draw(rng)).collect();
+    // Stable sort, so that ties are won by a random entrant.
+    entrants.sort_by(|&a, &b| cmp_score(keys[b], keys[a]));
+    for &x in &entrants[..size - 1] {
+        if rng.random_bool(p_best) {
+            return x;
+        }
+    }
+    entrants[size - 1]
+}
+
+/// Find the exact probability of winning a [tournament()] for each individual.
+///
+/// Argument "prob" is the probability of each individual entering the
+/// tournament, for each of its entrants.
+fn tournament_weights(keys: &[f64], prob: &[f64], size: usize, p_best: f64) -> Vec<f64> {
+    debug_assert_eq!(keys.len(), prob.len());
+    // Probability of winning given the entrant's rank within the tournament,
+    // and its cumulative sum.
+    let mut position = vec![0.0; size + 1];
+    for rank in 0..size {
+        let p_lose = (1.0 - p_best).powi(rank as i32);
+        let p_win = if rank + 1 < size { p_best } else { 1.0 };
+        position[rank + 1] = position[rank] + p_lose * p_win;
+    }
+    let binomial =
+        |n: usize, k: usize| -> f64 { (0..k).map(|i| (n - i) as f64 / (i + 1) as f64).product() };
+    // Group the individuals which are tied with each other, from best to worst.
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_unstable_by(|&a, &b| cmp_score(keys[b], keys[a]));
+    let mut weights = vec![0.0; keys.len()];
+    let mut better: f64 = 0.0;
+    let mut worse: f64 = prob.iter().sum();
+    for group in order.chunk_by(|&a, &b| cmp_score(keys[a], keys[b]).is_eq()) {
+        let equal: f64 = group.iter().map(|&i| prob[i]).sum();
+        worse = (worse - equal).max(0.0);
+        if equal > 0.0 {
+            // Sum over the number of better & equal entrants in the tournament.
+            let mut p_group = 0.0;
+            for num_better in 0..size {
+                for num_equal in 1..=size - num_better {
+                    let num_worse = size - num_better - num_equal;
+                    let p_entrants = binomial(size, num_better)
+                        * binomial(size - num_better, num_equal)
+                        * better.powi(num_better as i32)
+                        * equal.powi(num_equal as i32)
+                        * worse.powi(num_worse as i32);
+                    p_group +=
+                        p_entrants * (position[num_better + num_equal] - position[num_better]);
+                }
+            }
+            // Ties are won by a random entrant.
+            for &i in group {
+                weights[i] = p_group * prob[i] / equal;
+            }
+        }
+        better += equal;
+    }
+    weights
+}
+
+/// Roulette wheel sampling via stochastic acceptance, as described by
+/// Lipowski & Lipowska (2012).
+///
+/// This sampler draws individuals one at a time from a fixed set of sampling
+/// weights. Each draw takes constant expected time, so it is useful when the
+/// number of draws is not known in advance. In contrast, the stochastic
+/// universal sampling method used by [MateSelection::select] must scan all of
+/// the weights every time it is called.
+///
+/// The expected time per draw is proportional to the ratio between the
+/// maximum weight and the average weight.
+///
+/// This is serialized as the list of weights, which are checked when they are
+/// deserialized.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "Vec<f64>", into = "Vec<f64>")]
+pub struct StochasticAcceptance {
+    weights: Vec<f64>,
+    max_weight: f64,
+}
+
+impl StochasticAcceptance {
+    /// Argument "weights" is the sampling weight of each individual, see
+    /// [MateSelection::sample_weight]. All weights must be finite and greater
+    /// than or equal to zero. If all of the weights are zero then each
+    /// individual is equally likely to be selected.
+    pub fn new(weights: Vec<f64>) -> Self {
+        Self::try_new(weights).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Same as [StochasticAcceptance::new], but returns an error instead of
+    /// panicking if any of the weights are invalid.
+    pub fn try_new(weights: Vec<f64>) -> Result<Self, SelectionError> {
+        let mut max_weight: f64 = 0.0;
+        for (index, &weight) in weights.iter().enumerate() {
+            if !(weight.is_finite() && weight >= 0.0) {
+                return Err(SelectionError::InvalidWeight { index, weight });
+            }
+            max_weight = max_weight.max(weight);
+        }
+        Ok(Self {
+            weights,
+            max_weight,
+        })
+    }
+
+    /// Transform the scores with the given method and build a sampler for them.
+    pub fn from_scores<R, M>(method: &M, scores: Vec<f64>) -> Self
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        Self::new(method.sample_weight(scores))
+    }
+
+    /// Number of individuals in the population.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Check if the population is empty, in which case nothing can be sampled.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Choose a single weighted individual.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        assert!(!self.is_empty(), "no data: can not choose from empty set");
+        loop {
+            let index = rng.random_range(0..self.weights.len());
+            if self.max_weight == 0.0 {
+                return index;
+            }
+            if rng.random::<f64>() * self.max_weight < self.weights[index] {
+                return index;
+            }
+        }
+    }
+
+    /// Choose multiple weighted individuals, with repetition.
+    pub fn sample_multiple<R: Rng + ?Sized>(&self, rng: &mut R, amount: usize) -> Vec<usize> {
+        (0..amount).map(|_| self.sample(rng)).collect()
+    }
+
+    /// Number of individuals which can be sampled.
+    fn num_eligible(&self) -> usize {
+        if self.max_weight == 0.0 {
+            self.weights.len()
+        } else {
+            self.weights.iter().filter(|&&w| w > 0.0).count()
+        }
+    }
+}
+
+impl TryFrom<Vec<f64>> for StochasticAcceptance {
+    type Error = SelectionError;
+    fn try_from(weights: Vec<f64>) -> Result<Self, SelectionError> {
+        Self::try_new(weights)
+    }
+}
+
+impl From<StochasticAcceptance> for Vec<f64> {
+    fn from(sampler: StochasticAcceptance) -> Self {
+        sampler.weights
+    }
+}
+
+/// An endless iterator over mating pairs, see [MateSelection::pairs_iter].
+#[derive(Debug)]
+pub struct PairsIter<'a, R: Rng + ?Sized> {
+    rng: &'a mut R,
+    sampler: StochasticAcceptance,
+    num_eligible: usize,
+}
+
+impl<'a, R: Rng + ?Sized> PairsIter<'a, R> {
+    pub fn new(rng: &'a mut R, sampler: StochasticAcceptance) -> Self {
+        let num_eligible = sampler.num_eligible();
+        Self {
+            rng,
+            sampler,
+            num_eligible,
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> Iterator for PairsIter<'_, R> {
+    type Item = [usize; 2];
+
+    fn next(&mut self) -> Option<[usize; 2]> {
+        let a = self.sampler.sample(self.rng);
+        let mut b = self.sampler.sample(self.rng);
+        // Redraw the second parent to avoid mating an individual with itself.
+        while a == b && self.num_eligible > 1 {
+            b = self.sampler.sample(self.rng);
+        }
+        Some([a, b])
+    }
+}
+
+/// Compare two scores, where invalid (NaN) scores are worse than all others.
+fn cmp_score(a: f64, b: f64) -> core::cmp::Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(&b),
+        (a_nan, b_nan) => b_nan.cmp(&a_nan),
+    }
+}
+
+/// Sort the indices from best to worst, with invalid (NaN) scores last.
+fn argsort_best(scores: &[f64]) -> Vec<usize> {
+    let mut argsort: Vec<_> = (0..scores.len()).collect();
+    #[cfg(not(feature = "parallel"))]
+    argsort.sort_by(|a, b| cmp_score(scores[*b], scores[*a]));
+    #[cfg(feature = "parallel")]
+    parallel::sort_by(&mut argsort, |a, b| cmp_score(scores[*b], scores[*a]));
+    argsort
+}
+
+fn argsort(scores: &[f64]) -> Vec<usize> {
+    let mut argsort: Vec<_> = (0..scores.len()).collect();
+    #[cfg(not(feature = "parallel"))]
+    argsort.sort_by(|a, b| f64::total_cmp(&scores[*a], &scores[*b]));
+    #[cfg(feature = "parallel")]
+    parallel::sort_by(&mut argsort, |a, b| {
+        f64::total_cmp(&scores[*a], &scores[*b])
+    });
+    argsort
+}
+
+/// This helps avoid mating an individual with itself.
+///
+/// The repeated pairs are first swapped with each other, and then with the
+/// other pairs. A pair is only left repeated if every other pair contains that
+/// same individual, which is unavoidable. This runs in `O(n log n)` time.
+fn reduce_repeats(data: &mut [usize]) {
+    debug_assert!(is_even(data.len()));
+    let mut repeats: Vec<usize> = (0..data.len())
+        .step_by(2)
+        .filter(|&i| data[i] == data[i + 1])
+        .collect();
+    if repeats.is_empty() {
+        return;
+    }
+    // Group the repeated pairs by individual, with the largest group first.
+    repeats.sort_unstable_by_key(|&i| data[i]);
+    let mut groups: Vec<&[usize]> = repeats.chunk_by(|&a, &b| data[a] == data[b]).collect();
+    groups.sort_by_key(|group| Reverse(group.len()));
+    let repeats = groups.concat();
+    // Swap the first half of the repeated pairs with the second half:
+    // (a, a), (b, b) -> (b, a), (a, b)
+    // This only leaves repeats within the largest group, and the last repeat
+    // if there are an odd number of them.
+    let half = repeats.len() / 2;
+    for k in 0..half {
+        let (a, b) = (repeats[k], repeats[k + half]);
+        if data[a] != data[b] {
+            data.swap(a, b);
+        }
+    }
+    let mut remaining: Vec<(usize, usize)> = repeats
+        .into_iter()
+        .filter(|&i| data[i] == data[i + 1])
+        .map(|i| (data[i], i))
+        .collect();
+    remaining.sort_unstable();
+    // Swap the remaining repeated pairs with any compatible pair:
+    // (a, a), (b, c) -> (b, a), (a, c)
+    for group in remaining.chunk_by(|a, b| a.0 == b.0) {
+        let value = group[0].0;
+        let mut search = 0;
+        for &(_, cursor) in group {
+            if data[cursor] != data[cursor + 1] {
+                continue; // Already fixed by swapping with another repeated pair.
+            }
+            while search < data.len() && (data[search] == value || data[search + 1] == value) {
+                search += 2;
+            }
+            if search == data.len() {
+                break; // Unavoidable.
+            }
+            data.swap(cursor, search);
+        }
+    }
+}
+
+/// Same as [reduce_repeats] except that each repeated pair is swapped with the
+/// nearest compatible pair, so that the parents stay close to their original
+/// positions. This is for parents which are in a meaningful order, such as
+/// sorted by their traits, where neighbors should mate together.
+fn reduce_repeats_local(data: &mut [usize]) {
+    debug_assert!(is_even(data.len()));
+    let num_pairs = data.len() / 2;
+    'outer: for pair in 0..num_pairs {
+        let value = data[2 * pair];
+        if data[2 * pair + 1] != value {
+            continue;
+        }
+        for distance in 1..num_pairs {
+            let after = Some(pair + distance).filter(|&other| other < num_pairs);
+            let before = pair.checked_sub(distance);
+            if after.is_none() && before.is_none() {
+                break;
+            }
+            for other in after.into_iter().chain(before) {
+                let search = 2 * other;
+                if data[search] != value && data[search + 1] != value {
+                    // Swap the nearest elements of the two pairs:
+                    // (a, a), (b, c) -> (a, b), (a, c)
+                    if other > pair {
+                        data.swap(2 * pair + 1, search);
+                    } else {
+                        data.swap(2 * pair, search + 1);
+                    }
+                    continue 'outer;
+                }
+            }
+        }
+    }
+}
+
+/// This helps avoid repeating an individual within a group.
+fn reduce_group_repeats(data: &mut [usize], size: usize) {
+    debug_assert!(data.len().is_multiple_of(size));
+    // Simple greedy algorithm, same as for pairs but with larger groups.
+    let num_groups = data.len() / size;
+    for group in 0..num_groups {
+        let range = group * size..(group + 1) * size;
+        'repeat: for cursor in range.clone() {
+            let value = data[cursor];
+            if !data[range.start..cursor].contains(&value) {
+                continue;
+            }
+            // Find an element from another group to swap with.
+            let search_order = (group + 1..num_groups).chain(0..group);
+            for other in search_order {
+                let other_range = other * size..(other + 1) * size;
+                for search in other_range.clone() {
+                    let candidate = data[search];
+                    if !data[range.clone()].contains(&candidate)
+                        && !data[other_range.clone()].contains(&value)
+                    {
+                        data.swap(cursor, search);
+                        continue 'repeat;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Break up pairs of elements which are not permitted to mate together.
+fn reduce_conflicts(data: &mut [usize], conflict: impl Fn(usize, usize) -> bool) {
+    debug_assert!(is_even(data.len()));
+    // Simple quadratic greedy algorithm for breaking up conflicting pairs.
+    // First search for conflicting pairs.
+    'outer: for cursor in (0..data.len()).step_by(2) {
+        let value = data[cursor];
+        let mate = data[cursor + 1];
+        if conflict(value, mate) {
+            // Then find a different pair to swap with.
+            let compatible = |search: usize, data: &[usize]| {
+                !conflict(data[search], mate) && !conflict(value, data[search + 1])
+            };
+            for search in (cursor + 2..data.len()).step_by(2) {
+                if compatible(search, data) {
+                    data.swap(cursor, search);
+                    continue 'outer;
+                }
+            }
+            for search in (0..cursor).step_by(2) {
+                if compatible(search, data) {
+                    data.swap(cursor, search);
+                    continue 'outer;
+                }
+            }
+        }
+    }
+}
+
+/// Group the vector of samples into pairs of samples.
+#[cfg(not(feature = "unsafe_transmute"))]
+fn vec_to_pairs(data: Vec<usize>) -> Vec<[usize; 2]> {
+    // Check that there are an even number of values in the vector.
+    assert!(is_even(data.len()));
+    data.chunks_exact(2)
+        .map(|pair| [pair[0], pair[1]])
+        .collect()
+}
+
+/// Transmute the vector of samples into pairs of samples, without needlessly copying the data.
+#[cfg(feature = "unsafe_transmute")]
+fn vec_to_pairs(data: Vec<usize>) -> Vec<[usize; 2]> {
+    // Check that there are an even number of values in the vector.
+    assert!(is_even(data.len()));
+    // The allocation can only be reused if it holds a whole number of pairs.
+    if !is_even(data.capacity()) {
+        return data
+            .chunks_exact(2)
+            .map(|pair| [pair[0], pair[1]])
+            .collect();
+    }
+    // Check the data alignment.
+    assert_eq!(
+        core::mem::align_of::<usize>(),
+        core::mem::align_of::<[usize; 2]>()
+    );
+    // Take manual control over the data vector.
+    let mut data = core::mem::ManuallyDrop::new(data);
+    unsafe {
+        // Disassemble the vector.
+        let ptr = data.as_mut_ptr();
+        let mut len = data.len();
+        let mut cap = data.capacity();
+        // Transmute the vector.
+        let ptr = core::mem::transmute::<*mut usize, *mut [usize; 2]>(ptr);
+        len /= 2;
+        cap /= 2;
+        // Reassemble and return the data.
+        Vec::from_raw_parts(ptr, len, cap)
+    }
+}
+
+const fn is_even(x: usize) -> bool {
+    x & 1 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flatten_and_sort(pairs: &[[usize; 2]]) -> Vec<usize> {
+        let mut data: Vec<usize> = pairs.iter().flatten().copied().collect();
+        data.sort_unstable();
+        data
+    }
+
+    #[test]
+    fn is_even() {
+        assert!(super::is_even(0));
+        assert!(!super::is_even(1));
+        assert!(super::is_even(2));
+        assert!(!super::is_even(3));
+    }
+
+    #[test]
+    fn vec_to_pairs() {
+        assert_eq!(super::vec_to_pairs(vec![]), [] as [[usize; 2]; 0]);
+        assert_eq!(super::vec_to_pairs(vec![1, 2, 3, 4]), [[1, 2], [3, 4]]);
+        // Odd capacity.
+        let mut data = Vec::with_capacity(5);
+        data.extend([5, 6, 7, 8]);
+        assert_eq!(super::vec_to_pairs(data), [[5, 6], [7, 8]]);
+    }
+
+    #[test]
+    fn no_data() {
+        let rng = &mut rand::rng();
+        let pairs = Proportional.pairs(rng, 0, vec![]);
+        assert!(pairs.is_empty());
+
+        let pairs = Proportional.pairs(rng, 0, vec![1.0, 2.0, 3.0]);
+        assert!(pairs.is_empty());
+    }
+
+    #[test]
+    fn truncate_top_one() {
+        let rng = &mut rand::rng();
+        // Truncate all but the single best individual.
+        let algo = Percentile(0.99);
+        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
+        let pairs = algo.pairs(rng, 1, weights);
+        assert!(pairs == [[99, 99]]);
+    }
+
+    #[test]
+    fn truncate_top_two() {
+        let rng = &mut rand::rng();
+        // Truncate all but the best two individuals.
+        let algo = Percentile(0.98);
+        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
+        let pairs = algo.pairs(rng, 1, weights);
+        assert!(pairs == [[98, 99]] || pairs == [[99, 98]]);
+    }
+
+    #[test]
+    fn truncate_none() {
+        let rng = &mut rand::rng();
+        // Truncate none of the individuals.
+        let algo = Percentile(0.0);
+        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
+        let pairs = algo.pairs(rng, 50, weights);
+        let selected = flatten_and_sort(&pairs);
+        assert_eq!(selected, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn truncate_all() {
+        let rng = &mut rand::rng();
+        // Truncating all individuals should actually just return the single
+        // best individual. This situation happens when building the starting
+        // population.
+        let algo = Percentile(0.999_999_999); // Technically less than one.
+        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
+        let pairs = algo.pairs(rng, 1, weights);
+        assert!(pairs == [[99, 99]]);
+    }
+
+    #[test]
+    fn all_equal_to_the_best() {
+        let rng = &mut rand::rng();
+        Best(3).select(rng, 1, vec![4.0, 4.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn best() {
+        let rng = &mut rand::rng();
+        let weights: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let algo = Best(3);
+        let selected = flatten_and_sort(&algo.pairs(rng, 3, weights.clone()));
+        assert_eq!(selected, [7, 7, 8, 8, 9, 9]);
+        let pdf = MateSelection::<rand::rngs::ThreadRng>::pdf(&algo, weights);
+        assert_eq!(&pdf[..7], &[0.0; 7]);
+        assert_eq!(&pdf[7..], &[1.0 / 3.0; 3]);
+    }
+
+    #[test]
+    fn best_entire_population() {
+        let rng = &mut rand::rng();
+        // Asking for more individuals than exist should allow everyone to mate.
+        for number in [4, 5, 100] {
+            let algo = Best(number);
+            let selected = flatten_and_sort(&algo.pairs(rng, 2, vec![1.0, 2.0, 3.0, 4.0]));
+            assert_eq!(selected, [0, 1, 2, 3]);
+            let pdf = MateSelection::<rand::rngs::ThreadRng>::pdf(&algo, vec![1.0, 2.0, 3.0, 4.0]);
+            assert_eq!(pdf, [0.25; 4]);
+        }
+    }
+
+    #[test]
+    fn best_duplicate_scores() {
+        // Ties at the cutoff should still yield exactly "number" eligible individuals.
+        let weights = vec![1.0, 5.0, 3.0, 3.0, 3.0, 0.0];
+        let algo = Best(3);
+        let w = MateSelection::<rand::rngs::ThreadRng>::sample_weight(&algo, weights);
+        assert_eq!(w.iter().filter(|&&x| x == 1.0).count(), 3);
+        assert_eq!(w[1], 1.0);
+        assert_eq!(w[0], 0.0);
+        assert_eq!(w[5], 0.0);
+    }
+
+    #[test]
+    fn propotional() {
+        let rng = &mut rand::rng();
+        // All scores are equal, proportional should select all of the items.
+        let weights = vec![1.0; 10];
+        let algo = Proportional;
+        let selected = flatten_and_sort(&algo.pairs(rng, 5, weights));
+        assert_eq!(selected, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn propotional_outlier() {
+        let rng = &mut rand::rng();
+        // Index 0 is an outlier. Proportional selection should allow the
+        // outlier to dominate the sample. The other items should not be selected.
+        let weights = vec![1_000_000_000_000_000.0, 1.0, 1.0, 1.0];
+        let algo = Proportional;
+        let selected = flatten_and_sort(&algo.pairs(rng, 10, weights));
+        let inliers: Vec<_> = selected.iter().filter(|&idx| *idx != 0).collect();
+        assert!(inliers.is_empty());
+    }
+
+    #[test]
+    fn propotional_negative() {
+        let rng = &mut rand::rng();
+        // One score is extremely negative and another is NAN.
+        // Proportional should ignore them.
+        let mut weights = vec![1.0; 12];
+        weights[5] = -100.0;
+        weights[6] = f64::NAN;
+        let algo = Proportional;
+        let selected = flatten_and_sort(&algo.pairs(rng, 5, weights));
+        assert_eq!(selected, [0, 1, 2, 3, 4, 7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn power_scaled() {
+        type Rng = rand::rngs::ThreadRng;
+        let weights = vec![1.0, 2.0, 4.0, -1.0, f64::NAN];
+        let pdf = MateSelection::<Rng>::pdf(&PowerScaled(2.0), weights.clone());
+        assert_eq!(pdf, [1.0 / 21.0, 4.0 / 21.0, 16.0 / 21.0, 0.0, 0.0]);
+        // An exponent of one is the same as proportional selection.
+        let a = MateSelection::<Rng>::pdf(&PowerScaled(1.0), weights.clone());
+        let b = MateSelection::<Rng>::pdf(&Proportional, weights);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn normalized() {
+        let rng = &mut rand::rng();
+        // Normalize can deal with negative scores, it does not care about their absolute values.
+        let weights = vec![-20.0, -12.0, -11.0, -10.5, -10.0, -9.5, -9.0, -8.0, 0.0];
+        const MEAN_IDX: usize = 4;
+        const MAX_IDX: usize = 8;
+        let cutoff = -0.01;
+        let algo = Normalized(cutoff);
+        let selected = flatten_and_sort(&algo.pairs(rng, 2, weights));
+        // Only scores greater than the mean should have been selected.
+        assert!(selected.iter().all(|&x| x >= MEAN_IDX));
+        // The sample should contain the highest score, but not be dominated by it.
+        assert!(selected.contains(&MAX_IDX));
+        assert!(!selected.iter().all(|&x| x == MAX_IDX));
+    }
+
+    #[test]
+    fn ranked_linear() {
+        let rng = &mut rand::rng();
+        // Index 0 is an outlier.
+        // Ranking the scores should prevent the outlier from dominating.
+        let weights = vec![1_000_000_000_000_000.0, 1.0, 1.0, 1.0];
+
+        // No selection pressure, should select all four scores.
+        let algo = RankedLinear(0.0);
+        let selected = flatten_and_sort(&algo.pairs(rng, 2, weights));
+        assert_eq!(selected, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn ranked_linear_from_sp() {
+        type Rng = rand::rngs::ThreadRng;
+        assert_eq!(RankedLinear::from_sp(1.0), RankedLinear(0.0));
+        assert_eq!(RankedLinear::from_sp(2.0), RankedLinear(1.0));
+        assert_eq!(RankedLinear::try_from_sp(1.5), Ok(RankedLinear(0.5)));
+        assert!(RankedLinear::try_from_sp(2.5).is_err());
+        // The best individual has "sp" times the average number of offspring.
+        let n = 5;
+        let scores: Vec<f64> = (0..n).map(|x| x as f64).collect();
+        let pdf = MateSelection::<Rng>::pdf(&RankedLinear::from_sp(1.6), scores);
+        assert!((pdf[n - 1] * n as f64 - 1.6).abs() < 1e-12);
+        assert!((pdf[0] * n as f64 - 0.4).abs() < 1e-12);
+    }
+
+    /// Finds those off-by-one errors.
+    #[test]
+    fn ranked_linear_single() {
+        let rng = &mut rand::rng();
+        let weights = vec![4.0];
+        let algo = RankedLinear(0.5);
+        let selected = flatten_and_sort(&algo.pairs(rng, 1, weights));
+        assert_eq!(selected, vec![0, 0]);
+    }
+
+    #[test]
+    fn ranked_linear_outlier() {
+        let rng = &mut rand::rng();
+        // Index 0 is an outlier.
+        // Ranking the scores should prevent the outlier from dominating.
+        let mut weights = vec![1_000_000_000_000_000.0];
+        weights.append(&mut vec![1.0; 1000]);
+        // With selection pressure, the outlier still should not dominate the sampling.
+        let algo = RankedLinear(1.0);
+        let selected = flatten_and_sort(&algo.pairs(rng, 10, weights));
+        let inliers: Vec<_> = selected.iter().filter(|&idx| *idx != 0).collect();
+        assert!(!inliers.is_empty());
+    }
+
+    #[test]
+    fn ranked_exponential() {
+        let rng = &mut rand::rng();
+        let test_cases = [
+            (1, 1, 2, 99), // Test selecting with one single weight does not crash.
+            (3, 1, 4, 1),
+            (100, 10, 100, 5),
+            (1000, 10, 100, 5),
+            (10_000, 100, 10_000, 20),
+            (10_000, 1000, 10_000, 50),
+        ];
+        for (num, median, sample, tolerance) in test_cases {
+            let weights: Vec<f64> = (0..num).map(|x| x as f64).collect();
+            let algo = RankedExponential(median);
+            assert_eq!(sample, (sample / 2) * 2); // Sample count needs to be even for this to work.
+            let selected = flatten_and_sort(&algo.pairs(rng, sample / 2, weights));
+            dbg!(&selected);
+            // Count how many elements are from the top ranked individuals.
+            let top_count_actual = selected
+                .iter()
+                .filter(|&&idx| idx >= (num - median))
+                .count();
+            let top_count_desired = sample / 2;
+            dbg!(num, median, sample, tolerance);
+            dbg!(top_count_actual, top_count_desired);
+            assert!((top_count_actual as i64 - top_count_desired as i64).abs() <= tolerance);
+            assert!(top_count_actual > 0);
+        }
+    }
+
+    #[test]
+    fn linear_scaled() {
+        type Rng = rand::rngs::ThreadRng;
+        let weights = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let scaled = MateSelection::<Rng>::sample_weight(&LinearScaled(1.5), weights);
+        // The best is scaled to 1.5x the average.
+        let mean = scaled.iter().sum::<f64>() / scaled.len() as f64;
+        assert!((scaled[4] / mean - 1.5).abs() < 1e-9);
+        assert!(scaled.windows(2).all(|w| w[0] < w[1]));
+        // Negative scaled scores are clamped to zero.
+        let weights = vec![-100.0, 1.0, 1.0, 1.0, 2.0, f64::NAN];
+        let scaled = MateSelection::<Rng>::sample_weight(&LinearScaled(2.0), weights);
+        assert_eq!(scaled[0], 0.0);
+        assert_eq!(scaled[5], 0.0);
+        assert!(scaled[1..5].iter().all(|&x| x > 0.0));
+        // All equal scores are sampled uniformly.
+        let pdf = MateSelection::<Rng>::pdf(&LinearScaled(2.0), vec![7.0; 4]);
+        assert_eq!(pdf, [0.25; 4]);
+    }
+
+    #[test]
+    fn ranked_exponential_base() {
+        type Rng = rand::rngs::ThreadRng;
+        let weights = vec![3.0, 1.0, 4.0, 2.0];
+        let w = MateSelection::<Rng>::sample_weight(&RankedExponentialBase(0.5), weights);
+        assert_eq!(w, [0.5, 0.125, 1.0, 0.25]);
+        // Equivalent to RankedExponential with a median of one.
+        let weights: Vec<f64> = (0..20).map(|x| x as f64).collect();
+        let a = MateSelection::<Rng>::pdf(&RankedExponentialBase(0.5), weights.clone());
+        let b = MateSelection::<Rng>::pdf(&RankedExponential(1), weights);
+        for (a, b) in a.iter().zip(&b) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn windowed() {
+        type Rng = rand::rngs::ThreadRng;
+        // All negative scores are still selectable, except for the worst one.
+        let weights = vec![-10.0, -8.0, -7.0, f64::NAN];
+        let w = MateSelection::<Rng>::sample_weight(&Windowed::default(), weights.clone());
+        assert_eq!(w, [0.0, 2.0, 3.0, 0.0]);
+        // The worst score from previous generations is remembered.
+        let mut algo = Windowed::new(2);
+        algo.record(&[-20.0, 0.0]);
+        algo.record(&[-15.0, 0.0]);
+        let w = MateSelection::<Rng>::sample_weight(&algo, weights.clone());
+        assert_eq!(w, [10.0, 12.0, 13.0, 0.0]);
+        // The oldest generation falls out of the window.
+        algo.record(&[-12.0, 0.0]);
+        assert_eq!(algo.history, [-15.0, -12.0]);
+        let w = MateSelection::<Rng>::sample_weight(&algo, weights);
+        assert_eq!(w, [5.0, 7.0, 8.0, 0.0]);
+        // Equal scores have equal weights.
+        let equal = vec![3.0, f64::NAN, 3.0];
+        let w = MateSelection::<Rng>::sample_weight(&Windowed::default(), equal);
+        assert_eq!(w, [1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn restricted_tournament() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        // Two niches which are far apart, the second niche has better scores.
+        let location: [f64; 8] = [0.0, 1.0, 2.0, 3.0, 100.0, 101.0, 102.0, 103.0];
+        let scores = vec![1.0, 2.0, 3.0, 4.0, 10.0, 20.0, 30.0, 40.0];
+        let algo =
+            RestrictedTournament::new(7, |a: usize, b: usize| (location[a] - location[b]).abs());
+        let pdf = MateSelection::<Rng>::pdf(&algo, scores.clone());
+        assert!((pdf.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        // The best of the inferior niche survives, the worst of each niche does not.
+        assert!(pdf[3] > 0.0);
+        assert_eq!(pdf[0], 0.0);
+        assert_eq!(pdf[4], 0.0);
+        assert!((pdf[0..4].iter().sum::<f64>() - 0.5).abs() < 1e-12);
+        let selected = algo.select(rng, 100, scores.clone());
+        assert!(selected.iter().all(|&x| pdf[x] > 0.0));
+        // Without a window there is no competition.
+        let algo = RestrictedTournament::new(0, |_, _| 0.0);
+        let pdf = MateSelection::<Rng>::pdf(&algo, scores);
+        assert_eq!(pdf, [0.125; 8]);
+    }
+
+    #[test]
+    fn tournament_weights() {
+        // Binary tournament without ties.
+        let w = super::tournament_weights(&[1.0, 2.0, 3.0], &[1.0 / 3.0; 3], 2, 1.0);
+        let expect = [1.0 / 9.0, 3.0 / 9.0, 5.0 / 9.0];
+        for (a, b) in w.iter().zip(&expect) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        // Ties share their probability, and NaN is the worst.
+        let w = super::tournament_weights(&[5.0, 5.0, f64::NAN], &[1.0 / 3.0; 3], 3, 1.0);
+        assert!((w[0] - w[1]).abs() < 1e-12);
+        assert!((w[2] - 1.0 / 27.0).abs() < 1e-12);
+        assert!((w.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        // A coin flip tournament ignores the keys.
+        let w = super::tournament_weights(&[1.0, 2.0, 3.0, 4.0], &[0.25; 4], 2, 0.5);
+        for x in w {
+            assert!((x - 0.25).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn double_tournament() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores = vec![1.0, 2.0, 3.0, 4.0, 4.0];
+        let sizes = [1.0, 1.0, 1.0, 50.0, 10.0];
+        for fitness_first in [false, true] {
+            let algo = DoubleTournament::new(3, 2.0, fitness_first, |i| sizes[i]);
+            let pdf = MateSelection::<Rng>::pdf(&algo, scores.clone());
+            assert!((pdf.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            // Among the equally good individuals, the smaller one is preferred.
+            assert!(pdf[4] > pdf[3]);
+            // Without parsimony pressure this is a plain fitness tournament.
+            let algo = DoubleTournament::new(3, 1.0, fitness_first, |i| sizes[i]);
+            let pdf = MateSelection::<Rng>::pdf(&algo, scores.clone());
+            assert!((pdf[4] - pdf[3]).abs() < 1e-12);
+            let selected = algo.select(rng, 100, scores.clone());
+            assert!(selected.iter().all(|&x| x < scores.len()));
+        }
+    }
+
+    #[test]
+    fn tournament() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores = vec![3.0, 1.0, 2.0];
+        let pdf = MateSelection::<Rng>::pdf(&Tournament(2), scores.clone());
+        let expect = [5.0 / 9.0, 1.0 / 9.0, 3.0 / 9.0];
+        for (a, b) in pdf.iter().zip(&expect) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        // A deterministic probabilistic tournament is a plain tournament.
+        let algo = ProbabilisticTournament {
+            size: 2,
+            probability: 1.0,
+        };
+        assert_eq!(pdf, MateSelection::<Rng>::pdf(&algo, scores.clone()));
+        // Lowering the probability reduces the selection pressure.
+        let algo = ProbabilisticTournament {
+            size: 2,
+            probability: 0.75,
+        };
+        let soft = MateSelection::<Rng>::pdf(&algo, scores.clone());
+        assert!(soft[0] < pdf[0] && soft[1] > pdf[1]);
+        assert!((soft.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        // The worst individual only wins if every entrant is the worst individual.
+        let selected = Tournament(10).select(rng, 1000, scores);
+        assert!(selected.iter().filter(|&&x| x == 1).count() < 10);
+    }
+
+    #[test]
+    fn ranked_geometric() {
+        type Rng = rand::rngs::ThreadRng;
+        let q = 0.2;
+        let scores = vec![2.0, 4.0, 1.0, 3.0];
+        let pdf = MateSelection::<Rng>::pdf(&RankedGeometric(q), scores);
+        let q_norm = q / (1.0 - (1.0 - q).powi(4));
+        let expect = [2, 0, 3, 1].map(|rank| q_norm * (1.0 - q).powi(rank));
+        for (a, b) in pdf.iter().zip(&expect) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn ranked_gaussian() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = vec![2.0, 4.0, 1.0, 3.0];
+        let w = MateSelection::<Rng>::sample_weight(&RankedGaussian(2.0), scores.clone());
+        let expect = [2, 0, 3, 1].map(|rank: i32| (-(rank * rank) as f64 / 8.0).exp());
+        for (a, b) in w.iter().zip(&expect) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        // Smaller sigma is stronger selection pressure.
+        let narrow = MateSelection::<Rng>::pdf(&RankedGaussian(0.5), scores.clone());
+        let wide = MateSelection::<Rng>::pdf(&RankedGaussian(50.0), scores);
+        assert!(narrow[1] > 0.8);
+        assert!(wide.iter().all(|p| (p - 0.25).abs() < 1e-3));
+        assert!(RankedGaussian::try_new(0.0).is_err());
+        let kind: MateSelectionKind = "ranked_gaussian(1.5)".parse().unwrap();
+        assert_eq!(kind, MateSelectionKind::RankedGaussian(RankedGaussian(1.5)));
+        assert_eq!(kind.to_string(), "ranked_gaussian(1.5)");
+    }
+
+    #[test]
+    fn boltzmann() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = vec![1.0, 2.0, 10.0, f64::NAN];
+        let w = MateSelection::<Rng>::sample_weight(&Boltzmann(1.0), scores);
+        assert_eq!(w[2], 1.0);
+        assert_eq!(w[3], 0.0);
+        assert!((w[1] / w[0] - 1.0_f64.exp()).abs() < 1e-12);
+        // Infinite scores take all of the weight.
+        let scores = vec![1.0, f64::INFINITY, f64::NAN, f64::INFINITY];
+        let pdf = MateSelection::<Rng>::pdf(&Boltzmann(1.0), scores.clone());
+        assert_eq!(pdf, [0.0, 0.5, 0.0, 0.5]);
+        let log_pdf = MateSelection::<Rng>::log_pdf(&Boltzmann(1.0), scores);
+        assert_eq!(log_pdf[0], f64::NEG_INFINITY);
+        assert_eq!(log_pdf[1], 0.5_f64.ln());
+    }
+
+    #[test]
+    fn annealed_boltzmann() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = vec![1.0, 2.0, 3.0];
+        let mut algo = AnnealedBoltzmann::new(10.0, 0.5);
+        assert_eq!(algo.temperature(), 10.0);
+        let hot = MateSelection::<Rng>::pdf(&algo, scores.clone());
+        assert_eq!(
+            hot,
+            MateSelection::<Rng>::pdf(&Boltzmann(10.0), scores.clone())
+        );
+        algo.set_generation(3);
+        assert_eq!(algo.temperature(), 1.25);
+        let cold = MateSelection::<Rng>::pdf(&algo, scores.clone());
+        assert!(cold[2] > hot[2]);
+        // The temperature never reaches zero.
+        algo.set_generation(u64::MAX);
+        let frozen = MateSelection::<Rng>::pdf(&algo, scores);
+        assert_eq!(frozen, [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn unbiased_tournament() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        // The best individual enters exactly two tournaments per round, and always wins.
+        let selected = UnbiasedTournament(2).select(rng, 30, scores.clone());
+        assert_eq!(selected.iter().filter(|&&x| x == 9).count(), 6);
+        // The worst individual never competes against itself, so it never wins.
+        assert!(!selected.contains(&0));
+        let pdf =
+            MateSelection::<rand::rngs::ThreadRng>::pdf(&UnbiasedTournament(2), scores.clone());
+        assert_eq!(pdf[0], 0.0);
+        assert!((pdf[9] - 0.2).abs() < 1e-12);
+        assert!((pdf.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        // Without competition, every individual is selected once per round.
+        let mut selected = UnbiasedTournament(1).select(rng, 10, scores);
+        selected.sort_unstable();
+        assert_eq!(selected, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stochastic_acceptance() {
+        let rng = &mut rand::rng();
+        let sampler = StochasticAcceptance::new(vec![0.0, 1.0, 3.0, 0.0]);
+        let mut counts = [0; 4];
+        for x in sampler.sample_multiple(rng, 4000) {
+            counts[x] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[3], 0);
+        assert!((counts[2] as f64 / counts[1] as f64 - 3.0).abs() < 0.5);
+        // All zero weights are sampled uniformly.
+        let sampler = StochasticAcceptance::new(vec![0.0; 3]);
+        assert!(sampler.sample(rng) < 3);
+        // Build a sampler from a selection method.
+        let sampler = StochasticAcceptance::from_scores::<rand::rngs::ThreadRng, _>(
+            &Best(1),
+            vec![1.0, 5.0, 2.0],
+        );
+        assert_eq!(sampler.sample_multiple(rng, 10), [1; 10]);
+        // Invalid weights are rejected, including when deserializing.
+        assert_eq!(
+            StochasticAcceptance::try_new(vec![1.0, -1.0]),
+            Err(SelectionError::InvalidWeight {
+                index: 1,
+                weight: -1.0
+            })
+        );
+        type Weights =
+            serde::de::value::SeqDeserializer<std::vec::IntoIter<f64>, serde::de::value::Error>;
+        let weights = |w: Vec<f64>| Weights::new(w.into_iter());
+        assert!(StochasticAcceptance::deserialize(weights(vec![1.0, f64::NAN])).is_err());
+        assert_eq!(
+            StochasticAcceptance::deserialize(weights(vec![0.0, 2.0])),
+            Ok(StochasticAcceptance::new(vec![0.0, 2.0]))
+        );
+    }
+
+    #[test]
+    fn disruptive() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = vec![1.0, 5.0, 9.0, f64::NAN, 4.0, 6.0];
+        let w = MateSelection::<Rng>::sample_weight(&Disruptive, scores);
+        assert_eq!(w, [4.0, 0.0, 4.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn inverse() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores = vec![3.0, 1.0, 4.0, 2.0, f64::NAN];
+        let mut selected = Inverse(Best(2)).select(rng, 4, scores.clone());
+        selected.sort_unstable();
+        assert_eq!(selected, [1, 1, 3, 3]);
+        let pdf = MateSelection::<Rng>::pdf(&Inverse(RankedLinear(1.0)), scores.clone());
+        assert!(pdf[1] > pdf[3] && pdf[3] > pdf[0] && pdf[0] > pdf[2]);
+        // Works with dynamically dispatched methods too.
+        let algo: Inverse<Box<dyn MateSelection<Rng>>> = Inverse(Box::new(Tournament(2)));
+        let pdf = algo.pdf(scores);
+        assert!(pdf[1] > pdf[2]);
+    }
+
+    #[test]
+    fn elitist() {
+        let rng = &mut rand::rng();
+        let scores = vec![f64::NAN, 1.0, 5.0, 2.0, 4.0, 3.0];
+        let algo = Elitist {
+            keep: 2,
+            inner: Inverse(Best(1)),
+        };
+        let mut selected = algo.select(rng, 5, scores.clone());
+        selected.sort_unstable();
+        assert_eq!(selected, [1, 1, 1, 2, 4]);
+        // More elites than the sample size.
+        let selected = algo.select(rng, 1, scores.clone());
+        assert_eq!(selected, [2]);
+        // More elites than the population size.
+        let algo = Elitist {
+            keep: 100,
+            inner: Random,
+        };
+        let selected = algo.select(rng, 6, scores.clone());
+        assert_eq!(selected.len(), 6);
+        assert!((1..6).all(|i| selected.contains(&i)));
+        // Invalid (NaN) scores are never elites.
+        let algo = Elitist {
+            keep: 100,
+            inner: Inverse(Best(1)),
+        };
+        let mut selected = algo.select(rng, 6, scores.clone());
+        selected.sort_unstable();
+        assert_eq!(selected, [1, 1, 2, 3, 4, 5]);
+        let expected = MateSelection::<rand::rngs::ThreadRng>::expected_offspring(&algo, 6, scores);
+        assert_eq!(expected, [0.0, 2.0, 1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn selection_error() {
+        let rng = &mut rand::rng();
+        assert_eq!(
+            RankedLinear::try_new(1.5),
+            Err(SelectionError::InvalidArgument {
+                name: "selection_pressure",
+                reason: "is out of bounds [0, 1]"
+            })
+        );
+        assert!(Best::try_new(0).is_err());
+        assert!(ProbabilisticTournament::try_new(2, 0.75).is_ok());
+        // Wrappers check their inner method.
+        let algo = Elitist {
+            keep: 1,
+            inner: Boltzmann(-1.0),
+        };
+        let error = algo.try_pairs(rng, 5, vec![1.0, 2.0]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "argument \"temperature\" is not a positive finite number"
+        );
+        // Degenerate inputs.
+        assert_eq!(
+            Random.try_select(rng, 1, vec![]),
+            Err(SelectionError::EmptyPopulation)
+        );
+        assert_eq!(Random.try_select(rng, 0, vec![]), Ok(vec![]));
+        let error = Proportional.try_select(rng, 2, vec![1.0, f64::INFINITY]);
+        assert!(matches!(
+            error,
+            Err(SelectionError::InvalidWeight { index: 1, .. })
+        ));
+        assert_eq!(
+            Proportional.try_select(rng, 2, vec![0.0, 1.0]),
+            Ok(vec![1, 1])
+        );
+        // All of the weights are zero.
+        assert_eq!(
+            Proportional.try_select(rng, 2, vec![-1.0, -2.0]),
+            Err(SelectionError::NoneEligible)
+        );
+        assert_eq!(
+            Proportional.try_pairs(rng, 1, vec![-1.0, -2.0]),
+            Err(SelectionError::NoneEligible)
+        );
+    }
+
+    #[test]
+    fn mate_selection_kind() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores = vec![1.0, 2.0, 3.0];
+        let algo = MateSelectionKind::Elitist(Box::new(Elitist {
+            keep: 1,
+            inner: MateSelectionKind::Best(Best(1)),
+        }));
+        assert_eq!(algo.select(rng, 2, scores.clone()), [2, 2]);
+        let algo = MateSelectionKind::RankedLinear(RankedLinear(1.0));
+        assert_eq!(
+            MateSelection::<Rng>::pdf(&algo, scores.clone()),
+            MateSelection::<Rng>::pdf(&RankedLinear(1.0), scores)
+        );
+        let algo = MateSelectionKind::Inverse(Box::new(Inverse(MateSelectionKind::Best(Best(0)))));
+        assert!(MateSelection::<Rng>::validate(&algo).is_err());
+    }
+
+    #[test]
+    fn selection_config() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let config = SelectionConfig::new(MateSelectionKind::Best(Best(2)))
+            .with_pairs(5)
+            .with_self_mating(SelfMating::Forbid);
+        for _ in 0..10 {
+            let pairs = config.run(rng, scores.clone());
+            assert_eq!(flatten_and_sort(&pairs), [8, 8, 8, 8, 8, 9, 9, 9, 9, 9]);
+        }
+        let config = SelectionConfig::new(MateSelectionKind::Best(Best(1))).with_pairs(3);
+        assert_eq!(config.run(rng, scores.clone()), [[9, 9]; 3]);
+        let config = SelectionConfig::default()
+            .with_pairs(3)
+            .with_elitism(1)
+            .with_pairing(PairingPolicy::Monogamous);
+        let pairs = config.run(rng, scores);
+        assert!(flatten_and_sort(&pairs).windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn borrowed_scores() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        assert_eq!(Best(1).pairs_slice(rng, 2, &scores), [[9, 9]; 2]);
+        assert_eq!(Percentile(0.9).select_slice(rng, 3, &scores), [9, 9, 9]);
+        let algo = Islands::new(Best(1), 2);
+        assert_eq!(
+            flatten_and_sort(&algo.pairs_slice(rng, 2, &scores)),
+            [4, 4, 9, 9]
+        );
+        assert_eq!(
+            MateSelection::<Rng>::pdf_slice(&Proportional, &scores),
+            MateSelection::<Rng>::pdf(&Proportional, scores)
+        );
+    }
+
+    #[test]
+    fn single_precision() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores: Vec<f32> = vec![1.0, 3.0, 2.0];
+        assert_eq!(Best(1).pairs_f32(rng, 1, &scores), [[1, 1]]);
+        assert_eq!(Percentile(0.9).select_f32(rng, 2, &scores), [1, 1]);
+        let scores: Vec<f32> = vec![1.0, 3.0, 2.0, f32::NAN];
+        let pdf = MateSelection::<Rng>::pdf_f32(&Proportional, &scores);
+        assert_eq!(pdf, [1.0 / 6.0, 0.5, 1.0 / 3.0, 0.0]);
+    }
+
+    #[test]
+    fn ordered_scores() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        // These scores are not distinct after converting them to floats.
+        let scores: Vec<u64> = vec![u64::MAX - 2, u64::MAX, u64::MAX - 1];
+        assert_eq!(Best(1).pairs_ord(rng, 2, &scores), [[1, 1]; 2]);
+        assert_eq!(Inverse(Best(1)).select_ord(rng, 1, &scores), [0]);
+        let scores = ["b", "a", "b", "c"];
+        let pdf = RankSelection::<Rng>::pdf_ord(&Percentile(0.75), &scores);
+        assert_eq!(pdf, [0.0, 0.0, 0.0, 1.0]);
+        let pdf = RankSelection::<Rng>::pdf_ord(&RankedLinear(1.0), &["a", "c", "b"]);
+        assert_eq!(
+            pdf,
+            MateSelection::<Rng>::pdf(&RankedLinear(1.0), vec![0.0, 2.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn pairs_iter() {
+        let rng = &mut rand::rng();
+        let mut pairs = Proportional.pairs_iter(rng, vec![0.0, 1.0, 1.0]);
+        for _ in 0..100 {
+            let pair = pairs.next().unwrap();
+            assert!(pair == [1, 2] || pair == [2, 1]);
+        }
+        let pairs: Vec<_> = Best(1).pairs_iter(rng, vec![1.0, 2.0]).take(3).collect();
+        assert_eq!(pairs, [[1, 1]; 3]);
     }
 
-    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-        stochastic_universal_sampling::choose_multiple(rng, amount, scores.len())
+    #[test]
+    fn seeded() {
+        let scores: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        let mut a = Seeded::new(Proportional, 42);
+        let mut b = Seeded::new(Proportional, 42);
+        for _ in 0..3 {
+            assert_eq!(a.pairs(10, scores.clone()), b.pairs(10, scores.clone()));
+        }
+        a.reseed(7);
+        assert_ne!(a.select(10, scores.clone()), b.select(10, scores.clone()));
+        assert_eq!(
+            pairs_seeded(&Tournament(3), 1, 10, scores.clone()),
+            pairs_seeded(&Tournament(3), 1, 10, scores.clone())
+        );
+        assert_eq!(
+            select_seeded(&Random, 1, 10, scores.clone()),
+            select_seeded(&Random, 1, 10, scores)
+        );
     }
-}
 
-impl<R: Rng + ?Sized> MateSelection<R> for Proportional {
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        // Replace negative & invalid values with zero.
-        for x in scores.iter_mut() {
-            *x = x.max(0.0);
+    #[test]
+    fn selection_session() {
+        let scores: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        let mut a = SelectionSession::new(MateSelectionKind::Tournament(Tournament(3)), 42);
+        a.pairs(10, scores.clone());
+        // Checkpoint in the middle of a block of random numbers. Cloning goes
+        // through the same saved state as serialization.
+        a.rng().random::<u32>();
+        let mut b = a.clone();
+        assert_eq!(a, b);
+        for _ in 0..3 {
+            assert_eq!(a.pairs(10, scores.clone()), b.pairs(10, scores.clone()));
         }
-        scores
+        // Same algorithm as the standard generator.
+        let mut c = Seeded::new(Random, 7);
+        let mut d = SelectionSession::new(Random, 7);
+        assert_eq!(c.select(10, scores.clone()), d.select(10, scores));
     }
-}
 
-impl<R: Rng + ?Sized> MateSelection<R> for Normalized {
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let cutoff = self.0;
-        assert!(cutoff.is_finite(), "argument \"cutoff\" is not finite");
+    #[test]
+    fn parse_strategy() {
+        let parse = |spec: &str| spec.parse::<MateSelectionKind>();
+        assert_eq!(
+            parse("ranked_linear(0.5)"),
+            Ok(MateSelectionKind::RankedLinear(RankedLinear(0.5)))
+        );
+        assert_eq!(
+            parse(" tournament( 4 ) "),
+            Ok(MateSelectionKind::Tournament(Tournament(4)))
+        );
+        assert_eq!(
+            parse("elitist(2, inverse(proportional))"),
+            Ok(MateSelectionKind::Elitist(Box::new(Elitist {
+                keep: 2,
+                inner: MateSelectionKind::Inverse(Box::new(Inverse(
+                    MateSelectionKind::Proportional(Proportional)
+                ))),
+            })))
+        );
+        assert!(parse("percentile(0.9)").is_ok());
+        assert!(matches!(
+            parse("percentile(1.5)"),
+            Err(SelectionError::InvalidArgument { .. })
+        ));
+        assert_eq!(
+            parse("tournament(x)"),
+            Err(SelectionError::InvalidSpecification("tournament(x)".into()))
+        );
+        assert!(parse("best(1").is_err());
+        assert!(parse("elitist(1, best(1)").is_err());
+        assert!(parse("unknown").is_err());
+    }
 
-        // Find and normalize by the average score.
-        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
-        for x in scores.iter_mut() {
-            *x -= mean;
-        }
-        // Find and normalize by the standard deviation of the scores.
-        let var = scores.iter().map(|x| x.powi(2)).sum::<f64>() / scores.len() as f64;
-        let std = var.sqrt();
-        for x in scores.iter_mut() {
-            // Shift the entire distribution and cutoff all scores which
-            // are less than zero.
-            *x = (*x / std - cutoff).max(0.0);
+    #[test]
+    fn display_strategy() {
+        for spec in [
+            "random",
+            "ranked_linear(0.5)",
+            "percentile(0.9)",
+            "probabilistic_tournament(4, 0.75)",
+            "annealed_boltzmann(10, 0.95)",
+            "elitist(2, inverse(capped(3, tournament(4))))",
+            "islands(4, constrained(proportional))",
+            "islands(4, 10, 0.1, random, best(2))",
+        ] {
+            let method: MateSelectionKind = spec.parse().unwrap();
+            assert_eq!(method.to_string(), spec);
+            assert_eq!(method.to_string().parse(), Ok(method));
         }
-        scores
     }
-}
 
-fn arg_nth_max(amount: usize, data: &[f64]) -> Vec<usize> {
-    if amount == 0 {
-        return vec![];
+    #[test]
+    fn cumulative_distribution() {
+        let method = Proportional;
+        let scores = vec![1.0, 0.0, 2.0, 1.0];
+        let cdf = MateSelection::<rand::rngs::ThreadRng>::cdf(&method, scores.clone());
+        for (x, y) in cdf.iter().zip([0.25, 0.25, 0.75, 1.0]) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        let quantile =
+            |p| MateSelection::<rand::rngs::ThreadRng>::quantile(&method, p, scores.clone());
+        assert_eq!(quantile(0.0), 0);
+        assert_eq!(quantile(0.25), 0);
+        assert_eq!(quantile(0.26), 2);
+        assert_eq!(quantile(0.75), 2);
+        assert_eq!(quantile(1.0), 3);
+        // All of the weights are zero, so every individual is equally likely.
+        let scores = vec![-1.0, -2.0, -3.0, -4.0];
+        let cdf = MateSelection::<rand::rngs::ThreadRng>::cdf(&method, scores.clone());
+        assert_eq!(cdf, [0.25, 0.5, 0.75, 1.0]);
+        let quantile =
+            |p| MateSelection::<rand::rngs::ThreadRng>::quantile(&method, p, scores.clone());
+        assert_eq!(quantile(0.0), 0);
+        assert_eq!(quantile(0.3), 1);
+        assert_eq!(quantile(1.0), 3);
     }
-    let pivot = data.len() - amount;
-    let mut data_copy = data.to_vec();
-    let (_, cutoff, _) = data_copy.select_nth_unstable_by(pivot, f64::total_cmp);
-    let cutoff = *cutoff;
-    let mut index = Vec::with_capacity(amount);
-    for (i, x) in data.iter().enumerate() {
-        if *x >= cutoff {
-            index.push(i)
-        }
-    }
-    // Discard extra elements which are equal to the cutoff.
-    if index.len() > amount {
-        let mut num_discard = index.len() - amount;
-        for cursor in (0..index.len()).rev() {
-            if data[index[cursor]] == cutoff {
-                index.swap_remove(cursor);
-                num_discard -= 1;
-                if num_discard == 0 {
-                    break;
-                }
+
+    #[test]
+    fn reuse_buffers() {
+        let rng = &mut rand::rng();
+        let scores = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let mut buffer = SelectionBuffer::new();
+        let mut selected = vec![];
+        let mut pairs = vec![];
+        let methods: [&dyn MateSelection<_>; 4] =
+            [&Random, &Proportional, &RankedLinear(1.0), &Best(2)];
+        for method in methods {
+            for _ in 0..3 {
+                method.select_into(rng, 20, &scores, &mut buffer, &mut selected);
+                assert_eq!(selected.len(), 20);
+                assert!(selected.iter().all(|&x| x < scores.len()));
+                method.pairs_into(rng, 10, &scores, &mut buffer, &mut pairs);
+                assert_eq!(pairs.len(), 10);
             }
         }
+        // Stochastic universal sampling selects each individual its expected
+        // number of times, rounded up or down.
+        Proportional.select_into(rng, 10, &scores, &mut buffer, &mut selected);
+        assert_eq!(buffer.weights(), &scores[..]);
+        selected.sort();
+        assert_eq!(selected, [1, 2, 2, 3, 3, 3, 4, 4, 4, 4]);
+        Best(2).select_into(rng, 4, &scores, &mut buffer, &mut selected);
+        selected.sort();
+        assert_eq!(selected, [3, 3, 4, 4]);
+        // Methods which only override pairs are used by pairs_into.
+        let islands = Islands::new(Best(1), 2);
+        islands.pairs_into(rng, 4, &scores, &mut buffer, &mut pairs);
+        assert_eq!(pairs.len(), 4);
+        assert!(pairs.iter().all(|&pair| pair == [1, 1] || pair == [4, 4]));
     }
-    index
-}
 
-fn zero_and_write_sparse(data: &mut [f64], index: &[usize], value: f64) {
-    data.fill(0.0);
-    for i in index {
-        data[*i] = value;
+    #[test]
+    fn dynamic_rng() {
+        struct Config {
+            method: Box<dyn MateSelection>,
+        }
+        let config = Config {
+            method: Box::new(Elitist {
+                keep: 1,
+                inner: Tournament(2),
+            }),
+        };
+        let scores = vec![1.0, 2.0, 3.0];
+        let mut rng = rand::rng();
+        assert_eq!(config.method.select(&mut rng, 4, scores.clone()).len(), 4);
+        let mut rng = rand::make_rng::<rand::rngs::StdRng>();
+        assert_eq!(config.method.pairs(&mut rng, 2, scores.clone()).len(), 2);
+        let methods: Vec<Box<dyn MateSelection>> = vec![Box::new(Random), Box::new(Proportional)];
+        for method in &methods {
+            assert_eq!(method.select(&mut rng, 3, scores.clone()).len(), 3);
+        }
     }
-}
 
-impl Best {
-    fn args(&self) -> usize {
-        let number = self.0;
-        assert!(number > 0, "argument \"number\" is less than one");
-        number
+    #[test]
+    fn custom() {
+        let rng = &mut rand::rng();
+        let scores = vec![0.0, 1.0, 2.0, 3.0];
+        let cubed = Custom::new(|scores: &[f64]| scores.iter().map(|x| x * x * x).collect());
+        assert_eq!(
+            MateSelection::<rand::rngs::ThreadRng>::pdf(&cubed, scores.clone()),
+            [0.0, 1.0 / 36.0, 8.0 / 36.0, 27.0 / 36.0]
+        );
+        let mut selected = cubed.select(rng, 36, scores.clone());
+        selected.sort();
+        assert_eq!(selected.iter().filter(|&&x| x == 3).count(), 27);
+        assert_eq!(cubed.pairs(rng, 5, scores.clone()).len(), 5);
+        // Custom selection always chooses the last individual.
+        let last = cubed.with_select(|_rng: &mut dyn rand::Rng, amount, scores: &[f64]| {
+            vec![scores.len() - 1; amount]
+        });
+        assert_eq!(last.select(rng, 3, scores.clone()), [3, 3, 3]);
+        let boxed: Box<dyn MateSelection> = Box::new(last);
+        assert_eq!(boxed.select(rng, 2, scores), [3, 3]);
     }
-}
-impl<R: Rng + ?Sized> MateSelection<R> for Best {
-    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-        let num_best = self.args();
-        let index = arg_nth_max(num_best, &scores);
-        let sample = stochastic_universal_sampling::choose_multiple(rng, amount, index.len());
-        sample.iter().map(|&s| index[s]).collect()
+
+    #[test]
+    fn scores_from_iter() {
+        let rng = &mut rand::rng();
+        let scores = || (0..100).map(|x| x as f64);
+        let mut best = Best(3).select_from_iter(rng, 6, scores(), 0);
+        best.sort();
+        assert_eq!(best, [97, 97, 98, 98, 99, 99]);
+        // Ties are won by the earlier individual, same as with a slice.
+        let ties = [1.0, 2.0, 2.0, 2.0, 0.0];
+        let mut best = Best(2).select_from_iter(rng, 2, ties, ties.len());
+        best.sort();
+        let mut expected = Best(2).select(rng, 2, ties.to_vec());
+        expected.sort();
+        assert_eq!(best, expected);
+        let mut random = Random.select_from_iter(rng, 100, scores(), 100);
+        random.sort();
+        assert_eq!(random, (0..100).collect::<Vec<_>>());
+        assert_eq!(
+            RankedLinear(1.0)
+                .pairs_from_iter(rng, 10, scores(), 100)
+                .len(),
+            10
+        );
     }
-    fn pdf(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let num_best = self.args();
-        let index = arg_nth_max(num_best, &scores);
-        zero_and_write_sparse(&mut scores, &index, 1.0 / num_best as f64);
-        scores
+
+    #[test]
+    fn select_distinct() {
+        let rng = &mut rand::rng();
+        let scores = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        for amount in 0..=8 {
+            let mut selected = Proportional.select_distinct(rng, amount, scores.clone());
+            assert_eq!(selected.len(), amount.min(scores.len()));
+            selected.sort();
+            selected.dedup();
+            assert_eq!(selected.len(), amount.min(scores.len()));
+        }
+        // The zero weight individual is chosen last.
+        let selected = Proportional.select_distinct(rng, 6, scores.clone());
+        assert_eq!(selected[5], 0);
+        let mut best = Best(2).select_distinct(rng, 2, scores.clone());
+        best.sort();
+        assert_eq!(best, [4, 5]);
+        // Heavier individuals are more likely to be chosen first.
+        let mut first = [0; 6];
+        for _ in 0..1000 {
+            first[Proportional.select_distinct(rng, 1, scores.clone())[0]] += 1;
+        }
+        assert_eq!(first[0], 0);
+        assert!(first[5] > first[1]);
     }
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let num_best = self.args();
-        let index = arg_nth_max(num_best, &scores);
-        zero_and_write_sparse(&mut scores, &index, 1.0);
-        scores
+
+    #[test]
+    fn expected_offspring() {
+        type Rng = rand::rngs::ThreadRng;
+        let close = |a: Vec<f64>, b: &[f64]| {
+            assert_eq!(a.len(), b.len());
+            for (x, y) in a.iter().zip(b) {
+                assert!((x - y).abs() < 1e-12, "{a:?} != {b:?}");
+            }
+        };
+        let scores = vec![0.0, 1.0, 3.0];
+        close(
+            MateSelection::<Rng>::expected_offspring(&Random, 7, scores.clone()),
+            &[7.0 / 3.0; 3],
+        );
+        close(
+            MateSelection::<Rng>::expected_offspring(&Proportional, 8, scores.clone()),
+            &[0.0, 2.0, 6.0],
+        );
+        let elitist = Elitist {
+            keep: 1,
+            inner: Random,
+        };
+        close(
+            MateSelection::<Rng>::expected_offspring(&elitist, 7, scores.clone()),
+            &[2.0, 2.0, 3.0],
+        );
+        let capped = Capped {
+            cap: 3,
+            inner: Proportional,
+        };
+        let scores = vec![1.0, 1.0, 4.0];
+        close(
+            MateSelection::<Rng>::expected_offspring(&capped, 6, scores.clone()),
+            &[1.5, 1.5, 3.0],
+        );
+        let kind: MateSelectionKind = "capped(3, proportional)".parse().unwrap();
+        close(
+            MateSelection::<Rng>::expected_offspring(&kind, 6, scores),
+            &[1.5, 1.5, 3.0],
+        );
     }
-}
 
-impl Percentile {
-    fn get_index(&self, scores: &[f64]) -> Vec<usize> {
-        let percentile = self.0;
-        assert!(
-            (0.0..=1.0).contains(&percentile),
-            "argument \"percentile\" is out of bounds [0, 1]"
+    #[test]
+    fn selection_intensity() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores: Vec<f64> = (0..1000).map(|x| x as f64).collect();
+        let intensity =
+            |method: &dyn MateSelection<Rng>| method.selection_intensity(scores.clone());
+        assert!(intensity(&Random).abs() < 1e-9);
+        assert_eq!(
+            MateSelection::<Rng>::selection_intensity(&Proportional, vec![2.0; 5]),
+            0.0
         );
-        let num_eligible = ((1.0 - percentile) * scores.len() as f64).round() as usize;
-        arg_nth_max(num_eligible.max(1), &scores)
+        // Binary tournaments and linear ranking are equivalent.
+        let tournament = intensity(&Tournament(2));
+        assert!((tournament - intensity(&RankedLinear(1.0))).abs() < 0.01);
+        assert!(tournament > 0.5 && tournament < 0.6);
+        assert!(intensity(&Tournament(4)) > tournament);
+        assert!(intensity(&Inverse(Tournament(2))) < 0.0);
+        assert!(intensity(&Best(1)) > intensity(&Percentile(0.5)));
+        // The elites and the caps change the selection intensity.
+        let elitist = Elitist {
+            keep: 100,
+            inner: Random,
+        };
+        assert!(intensity(&elitist) > 0.1);
+        let capped = Capped {
+            cap: 1,
+            inner: Tournament(2),
+        };
+        assert!(intensity(&capped).abs() < 1e-9);
     }
-}
-impl<R: Rng + ?Sized> MateSelection<R> for Percentile {
-    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
-        let index = self.get_index(&scores);
-        let sample = stochastic_universal_sampling::choose_multiple(rng, amount, index.len());
-        sample.iter().map(|&s| index[s]).collect()
+
+    #[test]
+    fn tie_breaking() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = vec![0.0, 1.0, 1.0, 1.0, 2.0];
+        let average = TieBreaking::new(TiePolicy::Average, RankedLinear(1.0));
+        let weights = MateSelection::<Rng>::sample_weight(&average, scores.clone());
+        assert_eq!(weights, [0.0, 1.0, 1.0, 1.0, 2.0]);
+        let index = TieBreaking::new(TiePolicy::Index, RankedLinear(1.0));
+        let weights = MateSelection::<Rng>::sample_weight(&index, scores.clone());
+        assert_eq!(weights, [0.0, 1.5, 1.0, 0.5, 2.0]);
+        // Best of two must choose one of the tied individuals at random.
+        let rng = &mut rand::rng();
+        let random = TieBreaking::new(TiePolicy::Random, Best(2));
+        let mut counts = [0; 5];
+        for _ in 0..300 {
+            for i in random.select(rng, 2, scores.clone()) {
+                counts[i] += 1;
+            }
+        }
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[4], 300);
+        assert!(counts[1..4].iter().all(|&c| c > 50));
+        // Exactly two of the tied individuals are eligible in each selection.
+        let random = TieBreaking::new(TiePolicy::Random, Percentile(0.6));
+        let mut counts = [0; 5];
+        for _ in 0..300 {
+            let mut eligible = random.select(rng, 20, scores.clone());
+            eligible.sort();
+            eligible.dedup();
+            assert_eq!(eligible.len(), 2);
+            for i in eligible {
+                counts[i] += 1;
+            }
+        }
+        assert_eq!(counts[4], 300);
+        assert!(counts[1..4].iter().all(|&c| c > 50));
+        let average = TieBreaking::new(TiePolicy::Average, Percentile(0.6));
+        let pdf = MateSelection::<Rng>::pdf(&average, scores.clone());
+        assert!((pdf[1] - 1.0 / 6.0).abs() < 1e-12);
+        assert!((pdf[4] - 0.5).abs() < 1e-12);
+        let kind: MateSelectionKind = "tie_breaking(index, best(1))".parse().unwrap();
+        assert_eq!(kind.to_string(), "tie_breaking(index, best(1))");
     }
-    fn pdf(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let index = self.get_index(&scores);
-        zero_and_write_sparse(&mut scores, &index, 1.0 / index.len() as f64);
-        scores
+
+    #[test]
+    fn sparse_weights() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..1000).map(|x| x as f64).collect();
+        let mut sparse = MateSelection::<Rng>::sample_weight_sparse(&Percentile(0.99), &scores);
+        assert_eq!(sparse.population, 1000);
+        assert_eq!(sparse.len(), 10);
+        sparse.index.sort();
+        assert_eq!(sparse.index, (990..1000).collect::<Vec<_>>());
+        let dense = MateSelection::<Rng>::sample_weight(&Percentile(0.99), scores.clone());
+        assert_eq!(sparse.to_dense(), dense);
+        let mut selected = sparse.select(rng, 20);
+        selected.sort();
+        selected.dedup();
+        assert_eq!(selected, sparse.index);
+        // Default conversion from the dense weights.
+        let sparse =
+            MateSelection::<Rng>::sample_weight_sparse(&Proportional, &[0.0, 2.0, 0.0, 1.0]);
+        assert_eq!(sparse.index, [1, 3]);
+        assert_eq!(sparse.weights, [2.0, 1.0]);
+        let sparse = SparseWeights::from_dense(&[0.0; 3]);
+        assert_eq!(sparse.index, [0, 1, 2]);
     }
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let index = self.get_index(&scores);
-        zero_and_write_sparse(&mut scores, &index, 1.0);
-        scores
+
+    #[test]
+    fn log_space() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        // The squared scores overflow.
+        let scores = vec![1e200, 1e300, f64::MAX, 0.0];
+        let method = PowerScaled(2.0);
+        let pdf = MateSelection::<Rng>::pdf(&method, scores.clone());
+        assert!(pdf[2] > 0.99 && pdf[2] <= 1.0);
+        assert_eq!(pdf[3], 0.0);
+        let log_pdf = MateSelection::<Rng>::log_pdf(&method, scores.clone());
+        assert!(log_pdf[2].abs() < 1e-12);
+        assert!((log_pdf[0] - 2.0 * (1e200_f64.ln() - f64::MAX.ln())).abs() < 1e-9);
+        assert_eq!(log_pdf[3], f64::NEG_INFINITY);
+        assert_eq!(method.select(rng, 3, scores.clone()), [2, 2, 2]);
+        // The sum of the weights overflows.
+        let pdf = MateSelection::<Rng>::pdf(&Proportional, vec![f64::MAX; 4]);
+        assert_eq!(pdf, [0.25; 4]);
+        // Probabilities which underflow.
+        let log_pdf = MateSelection::<Rng>::log_pdf(&Boltzmann(1.0), vec![0.0, -1000.0, -2000.0]);
+        assert!(log_pdf[0].abs() < 1e-12);
+        assert!((log_pdf[1] + 1000.0).abs() < 1e-9);
+        assert!((log_pdf[2] + 2000.0).abs() < 1e-9);
+        let log_pdf = MateSelection::<Rng>::log_pdf(&Proportional, vec![0.0; 4]);
+        assert_eq!(log_pdf, [-(4.0_f64.ln()); 4]);
     }
-}
 
-impl<R: Rng + ?Sized> MateSelection<R> for RankedLinear {
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let selection_pressure = self.0;
-        assert!(
-            (0.0..=1.0).contains(&selection_pressure),
-            "argument \"selection_pressure\" is out of bounds [0, 1]"
+    #[test]
+    fn validate_scores() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = [-1.0, 2.0, 2.0, f64::NAN, f64::INFINITY, 0.5];
+        let report = MateSelection::<Rng>::validate_scores(&RankedLinear(1.0), &scores);
+        assert_eq!(report.population, 6);
+        assert_eq!(report.negative, 1);
+        assert_eq!(report.non_finite, 2);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.eligible, 5);
+        assert!(report.warnings.is_empty());
+        let report = MateSelection::<Rng>::validate_scores(&Proportional, &scores);
+        assert!(report.is_err());
+        let report = MateSelection::<Rng>::validate_scores(&Proportional, &[-1.0, -2.0]);
+        assert_eq!(report.eligible, 0);
+        assert_eq!(report.warnings, [ScoreWarning::NoneEligible]);
+        let report = MateSelection::<Rng>::validate_scores(&Normalized(0.0), &[3.0; 4]);
+        assert_eq!(report.duplicates, 3);
+        assert_eq!(report.warnings[0], ScoreWarning::AllEqual);
+        let report = MateSelection::<Rng>::validate_scores(&Tournament(0), &[1.0]);
+        assert!(matches!(
+            report.warnings[..],
+            [ScoreWarning::Error(SelectionError::InvalidArgument { .. })]
+        ));
+        let report = MateSelection::<Rng>::validate_scores(&Random, &[]);
+        assert_eq!(
+            report.warnings,
+            [ScoreWarning::Error(SelectionError::EmptyPopulation)]
         );
+    }
 
-        let div_n = if scores.len() == 1 {
-            0.0 // Value does not matter, just don't crash.
-        } else {
-            1.0 / (scores.len() - 1) as f64
-        };
-        for (rank, index) in argsort(&scores).iter().enumerate() {
-            // Reverse the ranking from ascending to descending order
-            // so that rank 0 is the best & rank N-1 is the worst.
-            let rank = scores.len() - 1 - rank;
-            // Scale the ranking into the range [0, 1].
-            let rank = rank as f64 * div_n;
-            scores[*index] = 1.0 + selection_pressure - 2.0 * selection_pressure * rank;
+    #[test]
+    fn reduce_repeats() {
+        let rng = &mut rand::rng();
+        for _ in 0..200 {
+            let len = 2 * rng.random_range(1..20);
+            let num_values = rng.random_range(1..6);
+            let mut data: Vec<usize> = (0..len).map(|_| rng.random_range(0..num_values)).collect();
+            let mut original = data.clone();
+            super::reduce_repeats(&mut data);
+            // Compare with the minimum possible number of repeated pairs.
+            let num_pairs = len / 2;
+            let most = (0..num_values)
+                .map(|v| data.iter().filter(|&&x| x == v).count())
+                .max()
+                .unwrap();
+            let repeats = data.chunks(2).filter(|pair| pair[0] == pair[1]).count();
+            assert_eq!(
+                repeats,
+                most.saturating_sub(num_pairs),
+                "{original:?} -> {data:?}"
+            );
+            // Only the order of the data changed.
+            original.sort();
+            data.sort();
+            assert_eq!(data, original);
         }
-        scores
     }
-}
 
-impl<R: Rng + ?Sized> MateSelection<R> for RankedExponential {
-    fn sample_weight(&self, mut scores: Vec<f64>) -> Vec<f64> {
-        let median = self.0;
-        assert!(median > 0, "argument \"median\" is less than one");
-        for (rank, index) in argsort(&scores).iter().enumerate() {
-            let rank = scores.len() - rank - 1;
-            scores[*index] = (-(2.0_f64.ln()) * rank as f64 / median as f64).exp();
+    #[test]
+    fn reduce_repeats_local() {
+        let rng = &mut rand::rng();
+        for _ in 0..200 {
+            let len = 2 * rng.random_range(1..20);
+            let num_values = rng.random_range(1..6);
+            let mut data: Vec<usize> = (0..len).map(|_| rng.random_range(0..num_values)).collect();
+            data.sort();
+            let original = data.clone();
+            super::reduce_repeats_local(&mut data);
+            let num_pairs = len / 2;
+            let most = (0..num_values)
+                .map(|v| data.iter().filter(|&&x| x == v).count())
+                .max()
+                .unwrap();
+            let repeats = data.chunks(2).filter(|pair| pair[0] == pair[1]).count();
+            if most <= num_pairs {
+                assert_eq!(repeats, 0, "{original:?} -> {data:?}");
+            }
+            // Only the order of the data changed.
+            let mut sorted = data.clone();
+            sorted.sort();
+            assert_eq!(sorted, original);
         }
-        scores
+        // Repeats are swapped with the nearest pair, not with a distant one.
+        let mut data = vec![0, 1, 2, 3, 4, 4, 5, 6, 7, 8];
+        super::reduce_repeats_local(&mut data);
+        assert_eq!(data, [0, 1, 2, 3, 4, 5, 4, 6, 7, 8]);
     }
-}
 
-fn argsort(scores: &[f64]) -> Vec<usize> {
-    let mut argsort: Vec<_> = (0..scores.len()).collect();
-    argsort.sort_unstable_by(|a, b| f64::total_cmp(&scores[*a], &scores[*b]));
-    argsort
-}
+    #[test]
+    fn compiled_sampler() {
+        let rng = &mut rand::rng();
+        let scores = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let sampler =
+            MateSelection::<rand::rngs::ThreadRng>::compile(&Proportional, scores.clone());
+        assert_eq!(sampler.len(), 5);
+        let mut counts = [0; 5];
+        for _ in 0..1000 {
+            for i in sampler.select(rng, 10) {
+                counts[i] += 1;
+            }
+        }
+        assert_eq!(counts[0], 0);
+        for (i, &count) in counts.iter().enumerate().skip(1) {
+            let expected = 10_000.0 * i as f64 / 10.0;
+            assert!(
+                (count as f64 - expected).abs() < 0.15 * expected,
+                "{counts:?}"
+            );
+        }
+        let pairs = sampler.pairs(rng, 100);
+        assert_eq!(pairs.len(), 100);
+        assert!(pairs.iter().all(|[a, b]| a != b && *a != 0 && *b != 0));
+        // As a rand distribution.
+        assert_ne!(rng.sample(&sampler), 0);
+        assert!(rand::distr::Distribution::sample_iter(&sampler, &mut *rng)
+            .take(100)
+            .all(|i| i != 0 && i < 5));
+        // All zero weights.
+        let sampler = Sampler::new(vec![0.0; 3]);
+        let mut selected = sampler.select(rng, 300);
+        selected.sort();
+        selected.dedup();
+        assert_eq!(selected, [0, 1, 2]);
+    }
 
-/// This helps avoid mating an individual with itself.
-fn reduce_repeats(data: &mut [usize]) {
-    debug_assert!(is_even(data.len()));
-    // Simple quadratic greedy algorithm for breaking up pairs of repeated elements.
-    // First search for pairs of repeated values.
-    'outer: for cursor in (0..data.len()).step_by(2) {
-        let value = data[cursor];
-        if value == data[cursor + 1] {
-            // Then find a different value to swap with.
-            for search in (cursor + 2..data.len()).step_by(2) {
-                if data[search] != value && data[search + 1] != value {
-                    data.swap(cursor, search);
-                    continue 'outer;
-                }
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_sort() {
+        let rng = &mut rand::rng();
+        for len in [0, 1, 1000, 100_000, 300_001] {
+            // Few distinct values, to check that the sort is stable.
+            let scores: Vec<f64> = (0..len).map(|_| rng.random_range(0..100) as f64).collect();
+            let mut expected: Vec<usize> = (0..len).collect();
+            expected.sort_by(|a, b| cmp_score(scores[*b], scores[*a]));
+            assert_eq!(argsort_best(&scores), expected);
+        }
+    }
+
+    #[test]
+    fn argsort_ties() {
+        // Ties are ordered by index, with or without the parallel feature.
+        let scores = [1.0, 0.0, 1.0, 0.0, 1.0];
+        assert_eq!(argsort(&scores), [1, 3, 0, 2, 4]);
+        assert_eq!(argsort_best(&scores), [0, 2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn arg_nth_max_ties() {
+        let rng = &mut rand::rng();
+        // Few distinct values, so there are many ties at the cutoff.
+        let scores: Vec<f64> = (0..1000).map(|_| rng.random_range(0..10) as f64).collect();
+        let mut expected: Vec<usize> = (0..scores.len()).collect();
+        expected.sort_by(|a, b| cmp_score(scores[*b], scores[*a]));
+        // Check both the heap and the partial sort.
+        for amount in [1, 5, 500, 1000] {
+            let mut index = arg_nth_max(amount, &scores);
+            index.sort();
+            let mut best = expected[..amount].to_vec();
+            best.sort();
+            assert_eq!(index, best);
+        }
+    }
+
+    #[test]
+    fn sample_weight_in_place() {
+        let rng = &mut rand::rng();
+        let methods: Vec<Box<dyn MateSelection>> = vec![
+            Box::new(Proportional),
+            Box::new(Percentile(0.5)),
+            Box::new(RankedLinear(0.7)),
+            Box::new(Tournament(3)),
+            Box::new(Inverse(Boltzmann(1.0))),
+            Box::new(Islands::new(RankedExponential(2), 3)),
+        ];
+        // Reuse the same buffer of scores for every method.
+        let mut buffer = vec![0.0; 12];
+        for method in methods {
+            let scores: Vec<f64> = (0..12).map(|_| rng.random()).collect();
+            buffer.copy_from_slice(&scores);
+            method.sample_weight_in_place(&mut buffer);
+            assert_eq!(buffer, method.sample_weight(scores));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn simd_sum() {
+        let rng = &mut rand::rng();
+        for len in [0, 1, 7, 8, 9, 1001] {
+            let data: Vec<f64> = (0..len).map(|_| rng.random()).collect();
+            let expected: f64 = data.iter().map(|x| x * x).sum();
+            assert!((sum_map(&data, |x| x * x) - expected).abs() < 1e-9);
+        }
+    }
+
+    /// Compare the float functions for builds without std against std.
+    #[test]
+    fn math_fallback() {
+        let rng = &mut rand::rng();
+        let ulps = |a: f64, b: f64| {
+            if a == b || (a.is_nan() && b.is_nan()) {
+                0
+            } else {
+                (a.to_bits() as i64).abs_diff(b.to_bits() as i64)
             }
-            for search in (0..cursor).step_by(2) {
-                if data[search] != value && data[search + 1] != value {
-                    data.swap(cursor, search);
-                    continue 'outer;
-                }
+        };
+        let mut values = vec![
+            0.0,
+            -0.0,
+            0.5,
+            -0.5,
+            1.0,
+            -1.0,
+            2.5,
+            -2.5,
+            1e-310,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+            709.7,
+            -745.0,
+            core::f64::consts::FRAC_PI_2,
+            0.49999999999999994,
+        ];
+        for _ in 0..10000 {
+            values.push(rng.random_range(-1.0..1.0));
+            values.push(rng.random_range(-750.0..750.0));
+            values.push(rng.random_range(0.0..1e6));
+            values.push(f64::from_bits(rng.random()));
+        }
+        for &x in &values {
+            assert_eq!(ulps(math::floor(x), x.floor()), 0, "floor({x})");
+            assert_eq!(ulps(math::round(x), x.round()), 0, "round({x})");
+            assert!(ulps(math::sqrt(x), x.sqrt()) <= 1, "sqrt({x})");
+            assert!(ulps(math::exp(x), x.exp()) <= 1, "exp({x})");
+            assert!(ulps(math::ln(x), x.ln()) <= 1, "ln({x})");
+            assert!(ulps(math::ln_1p(x), x.ln_1p()) <= 4, "ln_1p({x})");
+            if x.abs() < 1e6 {
+                assert!((math::cos(x) - x.cos()).abs() <= 1e-15, "cos({x})");
+            }
+            for n in [-3, -1, 0, 1, 2, 7] {
+                let (a, b) = (math::powi(x, n), x.powi(n));
+                assert!(
+                    ulps(a, b) <= 4 || (a - b).abs() <= 1e-14 * b.abs(),
+                    "powi({x}, {n})"
+                );
+            }
+            let y = rng.random_range(-10.0..10.0);
+            for (base, n) in [(x.abs(), y), (x, 3.0), (x, 0.5)] {
+                let (a, b) = (math::powf(base, n), base.powf(n));
+                let tolerance = 4.0 * f64::EPSILON * (1.0 + (n * base.abs().ln()).abs());
+                assert!(
+                    ulps(a, b) <= 1 || (a - b).abs() <= tolerance * b.abs(),
+                    "powf({base}, {n})"
+                );
             }
         }
     }
-}
 
-/// Transmute the vector of samples into pairs of samples, without needlessly copying the data.
-fn transmute_vec_to_pairs(data: Vec<usize>) -> Vec<[usize; 2]> {
-    // Check that there are an even number of values in the vector.
-    assert!(is_even(data.len()));
-    // Check the data alignment.
-    assert_eq!(
-        std::mem::align_of::<usize>(),
-        std::mem::align_of::<[usize; 2]>()
-    );
-    // Take manual control over the data vector.
-    let mut data = std::mem::ManuallyDrop::new(data);
-    unsafe {
-        // Disassemble the vector.
-        let ptr = data.as_mut_ptr();
-        let mut len = data.len();
-        let mut cap = data.capacity();
-        // Transmute the vector.
-        let ptr = std::mem::transmute::<*mut usize, *mut [usize; 2]>(ptr);
-        len /= 2;
-        cap /= 2;
-        // Reassemble and return the data.
-        Vec::from_raw_parts(ptr, len, cap)
+    #[test]
+    fn ranking_update() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..100).map(|_| rng.random_range(0..20) as f64).collect();
+        let mut ranking = Ranking::new(scores);
+        for _ in 0..20 {
+            let changes: Vec<(usize, f64)> = (0..5)
+                .map(|_| (rng.random_range(0..100), rng.random_range(0..20) as f64))
+                .collect();
+            ranking.update(&changes);
+            let fresh = Ranking::new(ranking.scores().to_vec());
+            assert_eq!(ranking.ranks(), fresh.ranks());
+            let mut order = ranking.order().to_vec();
+            order.sort();
+            assert_eq!(order, (0..100).collect::<Vec<_>>());
+        }
     }
-}
 
-const fn is_even(x: usize) -> bool {
-    x & 1 == 0
-}
+    #[test]
+    fn ranking_weights() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        // Distinct scores, so that the order is not arbitrary.
+        let mut scores: Vec<f64> = (0..50).map(|x| x as f64).collect();
+        scores.shuffle(rng);
+        let mut ranking = Ranking::new(scores.clone());
+        ranking.update(&[(3, 100.0), (7, -1.0)]);
+        scores[3] = 100.0;
+        scores[7] = -1.0;
+        let algo = RankedLinear(0.8);
+        let weights = RankSelection::<Rng>::sample_weight_ranked(&algo, &ranking);
+        assert_eq!(weights, MateSelection::<Rng>::sample_weight(&algo, scores));
+        let pairs = RankSelection::<Rng>::pairs_ranked(&Best(2), rng, 3, &ranking);
+        let best = &ranking.order()[48..];
+        assert!(pairs.iter().flatten().all(|x| best.contains(x)));
+        // Invalid (NaN) scores are ranked worst.
+        let scores = vec![f64::NAN, 1.0, 2.0];
+        let mut ranking = Ranking::new(scores.clone());
+        assert_eq!(ranking.order(), [0, 1, 2]);
+        ranking.update(&[(1, 3.0)]);
+        assert_eq!(ranking.order(), [0, 2, 1]);
+        ranking.update(&[(1, 1.0)]);
+        let weights = RankSelection::<Rng>::sample_weight_ranked(&Tournament(2), &ranking);
+        assert_eq!(
+            weights,
+            MateSelection::<Rng>::sample_weight(&Tournament(2), scores)
+        );
+    }
+
+    #[test]
+    fn streaming_sus() {
+        let rng = &mut rand::rng();
+        let chunks: Vec<Vec<f64>> = vec![vec![1.0, 0.0, 2.0], vec![], vec![0.5, 0.5, 4.0, 0.0]];
+        let total = 8.0;
+        let amount = 20;
+        let selected: Vec<usize> = choose_multiple_streaming(rng, amount, || &chunks).collect();
+        assert_eq!(selected.len(), amount);
+        assert!(selected.is_sorted());
+        // Every individual is selected its expected number of times, rounded up or down.
+        let weights = chunks.concat();
+        for (index, w) in weights.iter().enumerate() {
+            let expected = w * amount as f64 / total;
+            let count = selected.iter().filter(|&&x| x == index).count() as f64;
+            assert!(count == expected.floor() || count == expected.ceil());
+        }
+        // All of the weights are zero.
+        let zeros = [[0.0; 4]];
+        let selected: Vec<usize> = choose_multiple_streaming(rng, 8, || zeros).collect();
+        assert_eq!(selected, [0, 0, 1, 1, 2, 2, 3, 3]);
+        // Subnormal weights must not underflow the spacing between pointers.
+        let tiny = [[5e-324, 5e-324, 5e-324, 5e-324, 5e-324, 0.0]];
+        let selected: Vec<usize> = choose_multiple_streaming(rng, 10, || tiny).collect();
+        assert_eq!(selected, [0, 0, 1, 1, 2, 2, 3, 3, 4, 4]);
+    }
+
+    #[test]
+    fn pairs_u32() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let pairs = Best(2).pairs_u32(rng, 3, scores.clone());
+        assert_eq!(pairs.len(), 3);
+        assert!(pairs.iter().all(|&[a, b]| a != b && a >= 8 && b >= 8));
+        let selected = Percentile(0.5).select_u32(rng, 10, scores);
+        assert!(selected.iter().all(|&x| (5..10).contains(&x)));
+    }
+
+    #[test]
+    fn pairs_batch() {
+        let islands: Vec<Vec<f64>> = (0..5).map(|i| vec![i as f64; 10 + i]).collect();
+        let requests: Vec<(usize, &[f64])> = islands
+            .iter()
+            .map(|s| (s.len() / 2, s.as_slice()))
+            .collect();
+        let algo = RankedLinear(0.5);
+        let a = super::pairs_batch(&algo, &mut rand::rng(), &requests);
+        assert_eq!(a.len(), 5);
+        for (pairs, scores) in a.iter().zip(&islands) {
+            assert_eq!(pairs.len(), scores.len() / 2);
+            assert!(pairs.iter().flatten().all(|&x| x < scores.len()));
+        }
+        // The same seed gives the same results.
+        let b = super::pairs_batch(
+            &algo,
+            &mut <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(7),
+            &requests,
+        );
+        let c = super::pairs_batch(
+            &algo,
+            &mut <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(7),
+            &requests,
+        );
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    #[cfg(feature = "ffi")]
+    fn ffi() {
+        use ffi::*;
+        unsafe {
+            let spec = c"elitist(1, ranked_linear(0.5))";
+            let handle = ms_selection_new(spec.as_ptr(), 42);
+            assert!(!handle.is_null());
+            let scores = [1.0, 2.0, 3.0, 4.0];
+            let mut pairs = [0; 6];
+            let status = ms_selection_pairs(handle, scores.as_ptr(), 4, 3, pairs.as_mut_ptr());
+            assert_eq!(status, MsStatus::Ok);
+            assert!(pairs.contains(&3) && pairs.iter().all(|&x| x < 4));
+            let mut pdf = [0.0; 4];
+            let status = ms_selection_pdf(handle, scores.as_ptr(), 4, pdf.as_mut_ptr());
+            assert_eq!(status, MsStatus::Ok);
+            assert!((pdf.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            // Errors are reported instead of panicking.
+            let mut selected = [0; 2];
+            let empty = ms_selection_select(handle, scores.as_ptr(), 0, 2, selected.as_mut_ptr());
+            assert_eq!(empty, MsStatus::InvalidArgument);
+            let null = ms_selection_select(handle, scores.as_ptr(), 4, 2, core::ptr::null_mut());
+            assert_eq!(null, MsStatus::NullPointer);
+            let overflow =
+                ms_selection_pairs(handle, scores.as_ptr(), 4, usize::MAX, pairs.as_mut_ptr());
+            assert_eq!(overflow, MsStatus::InvalidArgument);
+            ms_selection_free(handle);
+            assert!(ms_selection_new(c"ranked_linear(7)".as_ptr(), 0).is_null());
+        }
+    }
+
+    #[test]
+    fn audit_log() {
+        let scores: Vec<f64> = (0..50).map(|x| x as f64).collect();
+        let other: Vec<f64> = (0..50).map(|x| -x as f64).collect();
+        let mut recorder = Recorder::new("ranked_linear(0.5)".parse().unwrap(), 3);
+        let pairs = recorder.pairs(10, &scores);
+        recorder.pairs(5, &other);
+        let records = recorder.take_records();
+        assert_eq!(records.len(), 2);
+        assert!(recorder.records().is_empty());
+        assert_eq!(records[0].pairs, pairs);
+        assert_eq!(records[0].replay(&scores), Some(pairs));
+        assert!(records[1].verify(&other));
+        assert_eq!(records[0].replay(&other), None);
+        assert!(!records[1].verify(&scores));
+        assert_ne!(hash_scores(&[0.0]), hash_scores(&[-0.0]));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn verify_distribution() {
+        let scores: Vec<f64> = (0..20).map(|x| x as f64).collect();
+        let report = super::verify_distribution(&Proportional, 1, 1000, 50, &scores);
+        assert_eq!(report.samples, 50_000);
+        assert!(report.is_within(0.005), "{:?}", report.outliers(0.005));
+        let elitist = Elitist {
+            keep: 2,
+            inner: Tournament(2),
+        };
+        let report = super::verify_distribution(&elitist, 1, 1000, 10, &scores);
+        assert!(report.is_within(0.01), "{:?}", report.outliers(0.01));
+        // Claims to be proportional, but always selects the first individual.
+        let broken = Custom::new(|scores: &[f64]| scores.to_vec())
+            .with_select(|_: &mut dyn rand::Rng, amount, _: &[f64]| vec![0; amount]);
+        let report = super::verify_distribution(&broken, 1, 100, 10, &scores);
+        assert!(!report.is_within(0.1));
+        assert_eq!(report.outliers(0.1)[0], (0, 0.0, 1.0));
+    }
 
-    fn flatten_and_sort(pairs: &Vec<[usize; 2]>) -> Vec<usize> {
-        let mut data: Vec<usize> = pairs.iter().flatten().copied().collect();
-        data.sort_unstable();
-        data
+    #[test]
+    fn adaptive_pressure() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        let mut controller = AdaptivePressure::new(Percentile(0.0), 0.7, 0.5);
+        for _ in 0..50 {
+            let loss = MateSelection::<Rng>::loss_of_diversity(&controller, 100, scores.clone());
+            controller.update(loss);
+        }
+        assert!((controller.inner.0 - 0.7).abs() < 0.01);
+        // The tournament size is rounded, but the controller still settles.
+        let mut controller = AdaptivePressure::new(Tournament(1), 0.6, 2.0);
+        for _ in 0..50 {
+            let loss = MateSelection::<Rng>::loss_of_diversity(&controller, 100, scores.clone());
+            controller.update(loss);
+        }
+        assert!(controller.inner.0 > 2);
+        let loss = MateSelection::<Rng>::loss_of_diversity(&controller, 100, scores.clone());
+        assert!((loss - 0.6).abs() < 0.1);
+        controller.update(f64::NAN);
+        // Boltzmann pressure is the inverse of the temperature.
+        let mut boltzmann = Boltzmann(2.0);
+        assert_eq!(boltzmann.pressure(), 0.5);
+        boltzmann.set_pressure(0.0);
+        assert!(boltzmann.0.is_finite());
     }
 
     #[test]
-    fn is_even() {
-        assert!(super::is_even(0));
-        assert!(!super::is_even(1));
-        assert!(super::is_even(2));
-        assert!(!super::is_even(3));
+    fn score_transforms() {
+        type Rng = rand::rngs::ThreadRng;
+        let apply = |transform: Transform, mut scores: Vec<f64>| {
+            transform.transform(&mut scores);
+            scores
+        };
+        let nan = f64::NAN;
+        let clamp = Transform::Clamp { min: 0.0, max: 2.0 };
+        assert_eq!(apply(clamp, vec![-1.0, 1.0, 3.0]), [0.0, 1.0, 2.0]);
+        let shifted = apply(Transform::ShiftToPositive, vec![-1.0, 1.0, nan]);
+        assert_eq!(shifted[..2], [0.0, 2.0]);
+        assert!(shifted[2].is_nan());
+        let ranks = apply(Transform::Rank, vec![5.0, -1.0, 5.0, nan, 0.0]);
+        assert_eq!(ranks[..3], [3.5, 1.0, 3.5]);
+        assert!(ranks[3].is_nan());
+        assert_eq!(ranks[4], 2.0);
+        assert_eq!(apply(Transform::ZScore, vec![1.0, 3.0]), [-1.0, 1.0]);
+        assert_eq!(apply(Transform::ZScore, vec![4.0, 4.0]), [0.0, 0.0]);
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let winsorized = apply(Transform::Winsorize(0.2), scores.clone());
+        assert_eq!(winsorized[..3], [2.0, 2.0, 2.0]);
+        assert_eq!(winsorized[7..], [7.0, 7.0, 7.0]);
+        assert_eq!(apply(Transform::Log, vec![0.0]), [0.0]);
+        // Proportional selection of the ranks is linear ranking.
+        let pipeline = Pipeline::new(
+            vec![Transform::Winsorize(0.2), Transform::Rank],
+            Proportional,
+        );
+        let pdf = MateSelection::<Rng>::pdf(&pipeline, vec![-100.0, 1.0, 2.0, 3.0, 1e9]);
+        assert_eq!(pdf, [0.1, 0.1, 0.2, 0.3, 0.3]);
+        let invalid = Pipeline::new(vec![Transform::Winsorize(0.7)], Proportional);
+        assert!(MateSelection::<Rng>::validate(&invalid).is_err());
     }
 
     #[test]
-    fn no_data() {
-        let rng = &mut rand::thread_rng();
-        let pairs = Proportional.pairs(rng, 0, vec![]);
-        assert!(pairs.is_empty());
+    fn floor() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores = vec![0.0, 1.0, 2.0, 5.0];
+        let floor = Floor::new(0.05, Best(1));
+        let pdf = MateSelection::<Rng>::pdf(&floor, scores.clone());
+        assert!(pdf[..3].iter().all(|&p| (p - 0.05).abs() < 1e-12));
+        assert!((pdf[3] - 0.85).abs() < 1e-12);
+        let mut counts = [0; 4];
+        for i in floor.select(rng, 10_000, scores.clone()) {
+            counts[i] += 1;
+        }
+        assert!(counts[..3].iter().all(|c| (499..=501).contains(c)));
+        assert!((8497..=8503).contains(&counts[3]));
+        // Uniform if the floor can not be satisfied.
+        let uniform = Floor::new(0.5, Best(1));
+        let pdf = MateSelection::<Rng>::pdf(&uniform, scores.clone());
+        assert_eq!(pdf, [0.25; 4]);
+        // The inner method has all zero weights, so it is sampled uniformly.
+        let zero = Floor::new(0.1, Proportional);
+        let scores = vec![-1.0, -2.0, -3.0];
+        let pdf = MateSelection::<Rng>::pdf(&zero, scores.clone());
+        assert!(pdf.iter().all(|&p| (p - 1.0 / 3.0).abs() < 1e-12));
+        let sampler = MateSelection::<Rng>::compile(&zero, scores.clone());
+        assert!(sampler.select(rng, 10).iter().all(|&i| i < 3));
+        assert!(zero.try_select(rng, 10, scores.clone()).is_ok());
+        let capped = Capped {
+            cap: 2,
+            inner: zero,
+        };
+        let mut counts = [0; 3];
+        for i in capped.select(rng, 6, scores) {
+            counts[i] += 1;
+        }
+        assert_eq!(counts, [2; 3]);
+        let invalid = Floor::new(1.5, Best(1));
+        assert!(MateSelection::<Rng>::validate(&invalid).is_err());
+    }
 
-        let pairs = Proportional.pairs(rng, 0, vec![1.0, 2.0, 3.0]);
-        assert!(pairs.is_empty());
+    #[test]
+    fn noisy() {
+        let rng = &mut rand::rng();
+        let estimate = NoisyScore::from_samples(&[1.0, 3.0]);
+        assert_eq!(estimate.mean, 2.0);
+        assert_eq!(estimate.stderr, 1.0);
+        let scores = [
+            NoisyScore::from_samples(&[5.0]),
+            NoisyScore::from_samples(&[4.0, 6.0, 2.0, 8.0]),
+            NoisyScore::from_samples(&[4.5, 4.5, 4.5]),
+            NoisyScore::from_samples(&[]),
+        ];
+        // The lucky evaluation is not trusted over the consistent ones.
+        let noisy = Noisy::new(NoiseHandling::LowerBound(2.0), Best(1));
+        let lower = noisy.scores(rng, &scores);
+        assert!(lower[3].is_nan());
+        assert_eq!(noisy.select(rng, 3, &scores[..3]), [0, 0, 0]);
+        let mut scores = scores;
+        scores[0].stderr = 1.0;
+        assert_eq!(noisy.select(rng, 3, &scores[..3]), [2, 2, 2]);
+        // Resampling only varies the uncertain scores.
+        let noisy = Noisy::new(NoiseHandling::Resample, Proportional);
+        let resampled = noisy.scores(rng, &scores);
+        assert_eq!(resampled[2], 4.5);
+        assert_ne!(resampled[1], 5.0);
+        assert_eq!(noisy.pairs(rng, 10, &scores).len(), 10);
+        let invalid = Noisy::new(NoiseHandling::LowerBound(f64::NAN), Proportional);
+        assert!(invalid.validate::<rand::rngs::ThreadRng>().is_err());
     }
 
     #[test]
-    fn truncate_top_one() {
-        let rng = &mut rand::thread_rng();
-        // Truncate all but the single best individual.
-        let algo = Percentile(0.99);
-        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
-        let pairs = algo.pairs(rng, 1, weights);
-        assert!(pairs == [[99, 99]]);
+    fn age_decay() {
+        type Rng = rand::rngs::ThreadRng;
+        let decay = AgeDecay::new(vec![0, 2, 3, 5], 2, 0.5);
+        let mut scores = vec![8.0; 4];
+        decay.transform(&mut scores);
+        assert_eq!(scores, [8.0, 8.0, 4.0, 1.0]);
+        // Combine with other types of transforms.
+        let transforms: Vec<Box<dyn ScoreTransform>> =
+            vec![Box::new(Transform::ShiftToPositive), Box::new(decay)];
+        let pipeline = Pipeline::new(transforms, Proportional);
+        let pdf = MateSelection::<Rng>::pdf(&pipeline, vec![-1.0, 1.0, 3.0, 31.0]);
+        assert_eq!(pdf, [0.0, 0.25, 0.25, 0.5]);
+        let invalid = AgeDecay::new(vec![], 0, 1.5);
+        assert!(ScoreTransform::validate(&invalid).is_err());
     }
 
     #[test]
-    fn truncate_top_two() {
-        let rng = &mut rand::thread_rng();
-        // Truncate all but the best two individuals.
-        let algo = Percentile(0.98);
-        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
-        let pairs = algo.pairs(rng, 1, weights);
-        assert!(pairs == [[98, 99]] || pairs == [[99, 98]]);
+    fn population() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let mut population = Population::new(vec![3.0, 1.0, f64::NAN, 2.0]);
+        assert_eq!(population.ranking(), [0, 3, 1, 2]);
+        let method = RankedLinear(1.0);
+        let weights = population.weights::<Rng, _>(&method).to_vec();
+        assert_eq!(
+            weights,
+            MateSelection::<Rng>::sample_weight(&method, population.scores().to_vec())
+        );
+        // Replace the best individual with a new worst individual.
+        let child = Metadata {
+            species: 1,
+            ..Metadata::default()
+        };
+        population.replace(0, 0.0, child);
+        assert_eq!(population.ranking(), [3, 1, 0, 2]);
+        let expected = argsort_best(population.scores());
+        assert_eq!(population.ranking(), expected);
+        let weights = population.weights::<Rng, _>(&method).to_vec();
+        assert_ne!(
+            weights,
+            MateSelection::<Rng>::sample_weight(&method, vec![3.0, 1.0, f64::NAN, 2.0])
+        );
+        // Changing the selection method requires invalidating the weights.
+        let best = Best(1);
+        assert_eq!(population.weights::<Rng, _>(&best), weights);
+        population.invalidate_weights();
+        let expected = MateSelection::<Rng>::sample_weight(&best, population.scores().to_vec());
+        assert_eq!(population.weights::<Rng, _>(&best), expected);
+        assert_ne!(expected, weights);
+        population.replace(2, -1.0, Metadata::default());
+        population.push(5.0, Metadata::default());
+        population.increment_ages();
+        assert_eq!(population.ranking(), [4, 3, 1, 0, 2]);
+        assert_eq!(population.metadata()[0].species, 1);
+        assert!(population.metadata().iter().all(|m| m.age == 1));
+        // The truncation method never chooses the worst individuals.
+        for [a, b] in population.pairs(&Best(2), rng, 10) {
+            assert!([a, b].iter().all(|x| [3, 4].contains(x)));
+        }
     }
 
     #[test]
-    fn truncate_none() {
-        let rng = &mut rand::thread_rng();
-        // Truncate none of the individuals.
-        let algo = Percentile(0.0);
-        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
-        let pairs = algo.pairs(rng, 50, weights);
-        let selected = flatten_and_sort(&pairs);
-        assert_eq!(selected, (0..100).collect::<Vec<_>>());
+    fn hall_of_fame() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let mut hall = HallOfFame::new(3, 1.0);
+        hall.update(&[5.0, 1.0, f64::NAN, 3.0], &["a", "b", "c", "d"]);
+        hall.update(&[6.0, 0.0], &["a", "e"]);
+        assert_eq!(hall.members(), [(6.0, "a"), (3.0, "d"), (1.0, "b")]);
+        // Only the members of the hall of fame have positive scores.
+        let scores = [0.0; 5];
+        let pairs = hall.pairs::<Rng, _>(&Proportional, rng, 20, &scores);
+        assert_eq!(pairs.len(), 20);
+        assert!(pairs
+            .iter()
+            .flatten()
+            .all(|p| matches!(p, Parent::HallOfFame("a" | "b" | "d"))));
+        // With zero weight, only the current population is selected.
+        hall.weight = 0.0;
+        let pairs = hall.pairs::<Rng, _>(&Random, rng, 20, &scores);
+        assert!(pairs
+            .iter()
+            .flatten()
+            .all(|p| matches!(p, Parent::Population(0..5))));
     }
 
     #[test]
-    fn truncate_all() {
-        let rng = &mut rand::thread_rng();
-        // Truncating all individuals should actually just return the single
-        // best individual. This situation happens when building the starting
-        // population.
-        let algo = Percentile(0.999_999_999); // Technically less than one.
-        let weights: Vec<f64> = (0..100).map(|x| x as f64 / 100.0).collect();
-        let pairs = algo.pairs(rng, 1, weights);
-        assert!(pairs == [[99, 99]]);
+    fn fallback() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let fallback = Fallback(vec![
+            MateSelectionKind::Normalized(Normalized(3.0)),
+            MateSelectionKind::Best(Best(2)),
+        ]);
+        // Nobody is three standard deviations above the mean.
+        let scores = vec![1.0, 2.0, 3.0, 4.0];
+        assert_eq!(
+            MateSelection::<Rng>::pdf(&fallback, scores.clone()),
+            [0.0, 0.0, 0.5, 0.5]
+        );
+        let selected = MateSelection::<Rng>::select(&fallback, rng, 10, scores);
+        assert!(selected.iter().all(|&x| x >= 2));
+        // The first method is used when it has eligible individuals.
+        let mut scores = vec![0.0; 100];
+        scores[7] = 100.0;
+        let active = fallback.active::<Rng>(&scores);
+        assert_eq!(active, &fallback.0[0]);
+        let empty: Fallback<Random> = Fallback(vec![]);
+        assert!(MateSelection::<Rng>::validate(&empty).is_err());
     }
 
     #[test]
-    fn all_equal_to_the_best() {
-        let rng = &mut rand::thread_rng();
-        Best(3).select(rng, 1, vec![4.0, 4.0, 4.0, 4.0]);
+    fn mixture() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let mixture = Mixture(vec![
+            (0.9, MateSelectionKind::Best(Best(1))),
+            (0.1, MateSelectionKind::Random(Random)),
+        ]);
+        let pdf = MateSelection::<Rng>::pdf(&mixture, scores.clone());
+        assert!((pdf[9] - 0.91).abs() < 1e-12);
+        assert!((pdf[0] - 0.01).abs() < 1e-12);
+        let selected = MateSelection::<Rng>::select(&mixture, rng, 100, scores.clone());
+        assert_eq!(selected.len(), 100);
+        let best = selected.iter().filter(|&&x| x == 9).count();
+        assert!((90..=92).contains(&best));
+        let pairs = MateSelection::<Rng>::pairs(&mixture, rng, 5, scores);
+        assert_eq!(pairs.len(), 5);
+        // Proportional selection has all zero weights for negative scores.
+        let zero = Mixture(vec![
+            (0.5, MateSelectionKind::Proportional(Proportional)),
+            (0.5, MateSelectionKind::Best(Best(1))),
+        ]);
+        let pdf = MateSelection::<Rng>::pdf(&zero, vec![-1.0, -2.0, -3.0]);
+        let expected = [2.0 / 3.0, 1.0 / 6.0, 1.0 / 6.0];
+        assert!(pdf.iter().zip(expected).all(|(p, e)| (p - e).abs() < 1e-12));
+        let invalid = Mixture(vec![(0.0, Random), (0.0, Random)]);
+        assert!(MateSelection::<Rng>::validate(&invalid).is_err());
+        let invalid = Mixture(vec![(-1.0, Random)]);
+        assert!(MateSelection::<Rng>::validate(&invalid).is_err());
     }
 
     #[test]
-    fn propotional() {
-        let rng = &mut rand::thread_rng();
-        // All scores are equal, proportional should select all of the items.
-        let weights = vec![1.0; 10];
-        let algo = Proportional;
-        let selected = flatten_and_sort(&algo.pairs(rng, 5, weights));
-        assert_eq!(selected, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    fn scheduled() {
+        let linear = Schedule::Linear {
+            start: 2.0,
+            end: 6.0,
+            generations: 4,
+        };
+        let tournament = Scheduled::new(Tournament(2), linear);
+        let sizes: Vec<usize> = (0..6).map(|g| tournament.at(g).0).collect();
+        assert_eq!(sizes, [2, 3, 4, 5, 6, 6]);
+        let exponential = Schedule::Exponential {
+            start: 1.0,
+            rate: 2.0,
+        };
+        assert_eq!(exponential.value(3), 8.0);
+        // The pressure of Boltzmann selection is the inverse temperature.
+        assert_eq!(Scheduled::new(Boltzmann(1.0), exponential).at(2).0, 0.25);
+        let steps = Schedule::Step(vec![(0, 0.1), (10, 0.5), (20, 2.0)]);
+        let percentile = Scheduled::new(Percentile(0.0), steps);
+        assert_eq!(percentile.at(9).0, 0.1);
+        assert_eq!(percentile.at(10).0, 0.5);
+        // Clamped to the bounds of the percentile.
+        assert_eq!(percentile.at(25).0, 1.0);
+        assert_eq!(Schedule::Custom(|g| g as f64 * 0.5).value(3), 1.5);
     }
 
     #[test]
-    fn propotional_outlier() {
-        let rng = &mut rand::thread_rng();
-        // Index 0 is an outlier. Proportional selection should allow the
-        // outlier to dominate the sample. The other items should not be selected.
-        let weights = vec![1000_000_000_000_000.0, 1.0, 1.0, 1.0];
-        let algo = Proportional;
-        let selected = flatten_and_sort(&algo.pairs(rng, 10, weights));
-        let inliers: Vec<_> = selected.iter().filter(|&idx| *idx != 0).collect();
-        assert!(inliers.is_empty());
+    fn generation() {
+        let rng = &mut rand::rng();
+        let config = SelectionConfig::new("tournament(2)".parse().unwrap())
+            .with_pairs(10)
+            .with_elitism(1);
+        let mut generation = Generation::new(config, PlusSelection);
+        let mut population: Vec<f64> = (0..20).map(|_| rng.random_range(0.0..1.0)).collect();
+        let mut scores = population.clone();
+        let first_best = scores.iter().copied().fold(f64::MIN, f64::max);
+        for _ in 0..20 {
+            let step = generation.step(
+                rng,
+                &mut population,
+                &mut scores,
+                |_, a, b| (a + b) / 2.0,
+                |rng, child| *child += rng.random_range(-0.1..0.1),
+                |child| *child,
+            );
+            assert_eq!(step.pairs.len(), 10);
+            assert_eq!(step.survivors.len(), 20);
+            assert_eq!(population, scores);
+        }
+        assert_eq!(generation.generation, 20);
+        let best = scores.iter().copied().fold(f64::MIN, f64::max);
+        assert!(best >= first_best);
     }
 
     #[test]
-    fn propotional_negative() {
-        let rng = &mut rand::thread_rng();
-        // One score is extremely negative and another is NAN.
-        // Proportional should ignore them.
-        let mut weights = vec![1.0; 12];
-        weights[5] = -100.0;
-        weights[6] = f64::NAN;
-        let algo = Proportional;
-        let selected = flatten_and_sort(&algo.pairs(rng, 5, weights));
-        assert_eq!(selected, [0, 1, 2, 3, 4, 7, 8, 9, 10, 11]);
+    fn survivor_selection() {
+        let rng = &mut rand::rng();
+        let survivors = |method: &dyn SurvivorSelection, rng: &mut rand::rngs::ThreadRng| {
+            method.survivors(rng, &[5.0, 1.0, f64::NAN, 3.0], &[4.0, 0.0, 2.0])
+        };
+        assert_eq!(survivors(&PlusSelection, rng), [0, 3, 4, 6]);
+        assert_eq!(survivors(&ReplaceWorst, rng), [0, 4, 5, 6]);
+        assert_eq!(survivors(&AgeBased, rng), [3, 4, 5, 6]);
+        let offspring = [4.0, 0.0, 2.0, 6.0, 1.0];
+        let survivors = CommaSelection.survivors(rng, &[5.0, 1.0, 3.0], &offspring);
+        assert_eq!(survivors, [3, 5, 6]);
+        // With many opponents the best individuals win the most.
+        let parents: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let offspring: Vec<f64> = (10..20).map(|x| x as f64).collect();
+        let survivors = RoundRobin(100).survivors(rng, &parents, &offspring);
+        assert_eq!(survivors.len(), 10);
+        assert!(survivors.is_sorted());
+        assert!(survivors.contains(&19) && !survivors.contains(&0));
     }
 
     #[test]
-    fn normalized() {
-        let rng = &mut rand::thread_rng();
-        // Normalize can deal with negative scores, it does not care about their absolute values.
-        let weights = vec![-20.0, -12.0, -11.0, -10.5, -10.0, -9.5, -9.0, -8.0, 0.0];
-        const MEAN_IDX: usize = 4;
-        const MAX_IDX: usize = 8;
-        let cutoff = -0.01;
-        let algo = Normalized(cutoff);
-        let selected = flatten_and_sort(&algo.pairs(rng, 2, weights));
-        // Only scores greater than the mean should have been selected.
-        assert!(selected.iter().all(|&x| x >= MEAN_IDX));
-        // The sample should contain the highest score, but not be dominated by it.
-        assert!(selected.contains(&MAX_IDX));
-        assert!(!selected.iter().all(|&x| x == MAX_IDX));
+    fn pedigree() {
+        let mut pedigree = Pedigree::new(4);
+        // Two pairs of full siblings.
+        assert_eq!(
+            pedigree.record(&[[0, 1], [0, 1], [2, 3], [2, 3]]),
+            [4, 5, 6, 7]
+        );
+        assert_eq!(pedigree.parents(5), Some([0, 1]));
+        assert_eq!(pedigree.parents(0), None);
+        assert_eq!(pedigree.coancestry(4, 5), 0.25);
+        assert_eq!(pedigree.coancestry(4, 6), 0.0);
+        assert_eq!(pedigree.common_ancestors(4, 5), [0, 1]);
+        assert!(pedigree.kinship(0.25).is_related(0, 1));
+        assert!(!pedigree.kinship(0.25).is_related(1, 2));
+        // Mate the siblings, and a pair of unrelated individuals.
+        assert_eq!(pedigree.record(&[[0, 1], [1, 2]]), [8, 9]);
+        assert_eq!(pedigree.len(), 10);
+        assert_eq!(pedigree.current(), [8, 9]);
+        assert_eq!(pedigree.inbreeding(8), 0.25);
+        assert_eq!(pedigree.inbreeding(9), 0.0);
+        assert_eq!(pedigree.ancestors(9), [0, 1, 2, 3, 5, 6]);
+        assert_eq!(pedigree.common_ancestors(8, 9), [0, 1, 5]);
+        assert_eq!(pedigree.coancestry(8, 8), 0.625);
     }
 
     #[test]
-    fn ranked_linear() {
-        let rng = &mut rand::thread_rng();
-        // Index 0 is an outlier.
-        // Ranking the scores should prevent the outlier from dominating.
-        let weights = vec![1000_000_000_000_000.0, 1.0, 1.0, 1.0];
+    fn selection_curve() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores = vec![1.0, 3.0, 2.0, 0.0];
+        let curve = MateSelection::<Rng>::selection_curve(&Best(2), scores);
+        assert_eq!(curve.by_rank(), [(1, 0.5), (2, 0.5), (3, 0.0), (4, 0.0)]);
+        assert_eq!(
+            curve.by_score(),
+            [(3.0, 0.5), (2.0, 0.5), (1.0, 0.0), (0.0, 0.0)]
+        );
+        assert_eq!(curve.points[0].index, 1);
+        assert_eq!(
+            curve.to_csv(),
+            "rank,index,score,probability\n1,1,3,0.5\n2,2,2,0.5\n3,0,1,0\n4,3,0,0\n"
+        );
+    }
 
-        // No selection pressure, should select all four scores.
-        let algo = RankedLinear(0.0);
-        let selected = flatten_and_sort(&algo.pairs(rng, 2, weights));
-        assert_eq!(selected, vec![0, 1, 2, 3]);
+    #[test]
+    fn self_test() {
+        use rand::{rngs::StdRng, SeedableRng};
+        // Critical values for a significance level of 0.05.
+        assert!((verify::gamma_q(0.5, 3.841 / 2.0) - 0.05).abs() < 1e-4);
+        assert!((verify::gamma_q(5.0, 18.307 / 2.0) - 0.05).abs() < 1e-4);
+        let rng = &mut StdRng::seed_from_u64(5);
+        let scores: Vec<f64> = (0..20).map(|x| x as f64).collect();
+        let result = MateSelection::<StdRng>::self_test(&Proportional, rng, 10_000, &scores);
+        assert_eq!(result.degrees_of_freedom, 18);
+        assert!(result.p_value > 0.001, "{result:?}");
+        let result = MateSelection::<StdRng>::self_test(&Tournament(3), rng, 10_000, &scores);
+        assert!(result.p_value > 0.001, "{result:?}");
+        // Claims to be proportional, but selects uniformly.
+        let broken = Custom::new(|scores: &[f64]| scores.to_vec()).with_select(
+            |rng: &mut dyn rand::Rng, amount, scores: &[f64]| {
+                (0..amount)
+                    .map(|_| rng.random_range(1..scores.len()))
+                    .collect()
+            },
+        );
+        let result = MateSelection::<StdRng>::self_test(&broken, rng, 10_000, &scores);
+        assert!(result.p_value < 1e-6, "{result:?}");
     }
 
-    /// Finds those off-by-one errors.
     #[test]
-    fn ranked_linear_single() {
-        let rng = &mut rand::thread_rng();
-        let weights = vec![4.0];
-        let algo = RankedLinear(0.5);
-        let selected = flatten_and_sort(&algo.pairs(rng, 1, weights));
-        assert_eq!(selected, vec![0, 0]);
+    fn loss_of_diversity() {
+        type Rng = rand::rngs::ThreadRng;
+        let scores: Vec<f64> = (0..1000).map(|x| x as f64).collect();
+        let loss = |method: &dyn MateSelection<Rng>| method.loss_of_diversity(1000, scores.clone());
+        assert_eq!(loss(&Random), 0.0);
+        assert!((loss(&Best(100)) - 0.9).abs() < 1e-9);
+        // Blickle & Thiele: linear ranking without offspring for the worst.
+        assert!((loss(&RankedLinear(1.0)) - 0.25).abs() < 0.01);
+        // Binary tournaments: the average of `exp(-2x)` for x in [0, 1].
+        let expected = (1.0 - (-2.0_f64).exp()) / 2.0;
+        assert!((loss(&Tournament(2)) - expected).abs() < 0.01);
+        assert!(loss(&Tournament(4)) > loss(&Tournament(2)));
+        assert_eq!(loss(&Random), loss(&Inverse(Random)));
+        assert_eq!(
+            MateSelection::<Rng>::loss_of_diversity(&Random, 10, vec![]),
+            0.0
+        );
     }
 
     #[test]
-    fn ranked_linear_outlier() {
-        let rng = &mut rand::thread_rng();
-        // Index 0 is an outlier.
-        // Ranking the scores should prevent the outlier from dominating.
-        let mut weights = vec![1000_000_000_000_000.0];
-        weights.append(&mut vec![1.0; 1000]);
-        // With selection pressure, the outlier still should not dominate the sampling.
-        let algo = RankedLinear(1.0);
-        let selected = flatten_and_sort(&algo.pairs(rng, 10, weights));
-        let inliers: Vec<_> = selected.iter().filter(|&idx| *idx != 0).collect();
-        assert!(!inliers.is_empty());
+    fn takeover_time() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        // The approximations agree with the simulations.
+        for size in [2, 4] {
+            let analytic = MateSelection::<Rng>::takeover_time(&Tournament(size), rng, 100);
+            let simulated = takeover::simulate_takeover(&Tournament(size), rng, 100);
+            let (analytic, simulated) = (analytic.unwrap(), simulated.unwrap());
+            assert!((analytic - simulated).abs() < 0.25 * simulated);
+        }
+        assert_eq!(
+            MateSelection::<Rng>::takeover_time(&Best(1), rng, 100),
+            Some(1.0)
+        );
+        assert_eq!(
+            MateSelection::<Rng>::takeover_time(&Best(10), rng, 100),
+            Some(2.0)
+        );
+        assert_eq!(
+            MateSelection::<Rng>::takeover_time(&Percentile(0.9), rng, 100),
+            Some(2.0)
+        );
+        assert_eq!(
+            MateSelection::<Rng>::takeover_time(&Proportional, rng, 1),
+            Some(0.0)
+        );
+        // Weaker selection pressure takes longer.
+        let kind: MateSelectionKind = "ranked_linear(1)".parse().unwrap();
+        let ranked = MateSelection::<Rng>::takeover_time(&kind, rng, 100).unwrap();
+        let proportional = MateSelection::<Rng>::takeover_time(&Proportional, rng, 100).unwrap();
+        assert!(ranked < proportional);
     }
 
     #[test]
-    fn ranked_exponential() {
-        let rng = &mut rand::thread_rng();
-        let test_cases = [
-            (1, 1, 2, 99), // Test selecting with one single weight does not crash.
-            (3, 1, 4, 1),
-            (100, 10, 100, 5),
-            (1000, 10, 100, 5),
-            (10_000, 100, 10_000, 20),
-            (10_000, 1000, 10_000, 50),
-        ];
-        for (num, median, sample, tolerance) in test_cases {
-            let weights: Vec<f64> = (0..num).map(|x| x as f64).collect();
-            let algo = RankedExponential(median);
-            assert_eq!(sample, (sample / 2) * 2); // Sample count needs to be even for this to work.
-            let selected = flatten_and_sort(&algo.pairs(rng, sample / 2, weights));
-            dbg!(&selected);
-            // Count how many elements are from the top ranked individuals.
-            let top_count_actual = selected
-                .iter()
-                .filter(|&&idx| idx >= (num - median))
-                .count();
-            let top_count_desired = sample / 2;
-            dbg!(num, median, sample, tolerance);
-            dbg!(top_count_actual, top_count_desired);
-            assert!((top_count_actual as i64 - top_count_desired as i64).abs() <= tolerance);
-            assert!(top_count_actual > 0);
+    fn constrained() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores = vec![5.0, 1.0, 9.0, 3.0];
+        let violations = [0.0, 0.0, 2.0, 0.5];
+        assert_eq!(
+            feasibility_scores(scores.clone(), &violations),
+            [4.0, 3.0, 1.0, 2.0]
+        );
+        // Infeasible individuals never tie with feasible ones, regardless of
+        // the magnitude of the scores.
+        let ranks = feasibility_scores(vec![1e6, 2e6, 5e6, f64::NAN], &[0.0, 0.0, 1e-12, 0.0]);
+        assert_eq!(ranks[..3], [2.0, 3.0, 1.0]);
+        assert!(ranks[3].is_nan());
+        let algo = Constrained {
+            violations: Some(vec![1e-9, 0.0]),
+            inner: Best(1),
+        };
+        for _ in 0..10 {
+            assert_eq!(algo.select(rng, 1, vec![1e9, 1e9]), [1]);
         }
+        let algo = Constrained {
+            violations: Some(vec![0.0, 0.0, 1.0]),
+            inner: Proportional,
+        };
+        let pdf = MateSelection::<Rng>::pdf(&algo, vec![1000.0, 1001.0, 5000.0]);
+        assert_eq!(pdf, [1.0 / 3.0, 0.5, 1.0 / 6.0]);
+        let mut algo = Constrained::new(Best(1));
+        assert_eq!(algo.select(rng, 1, scores.clone()), [2]);
+        algo.set_violations(violations.to_vec());
+        assert_eq!(algo.select(rng, 1, scores.clone()), [0]);
+        let algo = Constrained {
+            violations: Some(violations.to_vec()),
+            inner: RankedLinear(1.0),
+        };
+        let pdf = MateSelection::<Rng>::pdf(&algo, scores);
+        assert!(pdf[0] > pdf[1] && pdf[1] > pdf[3] && pdf[3] > pdf[2]);
     }
 
     /// Check that this avoids mating individuals with themselves.
     #[test]
     fn pairs() {
-        let rng = &mut rand::thread_rng();
+        let rng = &mut rand::rng();
         // N is the population size.
         // P is the number of mating pairs.
         // R is the percent of the pairs that are duplicates.
@@ -940,13 +7333,393 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pairs_unrelated() {
+        let rng = &mut rand::rng();
+        // Two families of siblings.
+        let kinship = Kinship::Genealogy {
+            parents: vec![vec![[0, 1], [0, 1], [2, 3], [2, 3]]],
+            degree: 1,
+        };
+        assert!(kinship.is_related(0, 1));
+        assert!(!kinship.is_related(1, 2));
+        for _ in 0..10 {
+            let pairs = Random.pairs_unrelated(rng, 20, vec![1.0; 4], &kinship);
+            assert!(pairs.iter().all(|[a, b]| (a < &2) != (b < &2)));
+        }
+        // First cousins are related in the second degree.
+        let parents = vec![vec![[0, 1], [0, 1], [2, 3], [2, 3]], vec![[0, 2], [1, 3]]];
+        let cousins = |degree| Kinship::Genealogy {
+            parents: parents.clone(),
+            degree,
+        };
+        assert!(!cousins(1).is_related(0, 1));
+        assert!(cousins(2).is_related(0, 1));
+        let kinship = cousins(1);
+        let relation = kinship.relation(2);
+        for (a, b) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert_eq!(kinship.is_related(a, b), relation(a, b));
+        }
+        // Explicit relationships.
+        let kinship = Kinship::Matrix {
+            matrix: vec![
+                vec![1.0, 0.5, 0.0],
+                vec![0.5, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+            ],
+            threshold: 0.25,
+        };
+        assert!(kinship.is_related(0, 1));
+        assert!(!kinship.is_related(1, 2));
+    }
+
+    #[test]
+    fn assortative() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        let policy = PairingPolicy::Assortative {
+            strength: 1.0,
+            traits: None,
+        };
+        let pairs = Random.pairs_with(rng, 50, scores.clone(), &policy);
+        assert!(pairs.iter().all(|[a, b]| a.abs_diff(*b) == 1));
+        // Pair by a user supplied trait.
+        let traits: Vec<f64> = (0..100).map(|x| (x % 2) as f64).collect();
+        let policy = PairingPolicy::Assortative {
+            strength: 1.0,
+            traits: Some(traits),
+        };
+        let pairs = Random.pairs_with(rng, 50, scores.clone(), &policy);
+        assert!(pairs.iter().all(|[a, b]| a % 2 == b % 2 && a != b));
+        // Without any strength, the pairs are random.
+        let policy = PairingPolicy::Assortative {
+            strength: 0.0,
+            traits: None,
+        };
+        let pairs = Random.pairs_with(rng, 50, scores.clone(), &policy);
+        assert!(pairs.iter().any(|[a, b]| a.abs_diff(*b) > 1));
+        // Repeated parents are separated without moving them far apart.
+        let mean_distance = |pairs: Vec<[usize; 2]>| {
+            pairs.iter().map(|[a, b]| a.abs_diff(*b)).sum::<usize>() as f64 / pairs.len() as f64
+        };
+        let assortative = PairingPolicy::Assortative {
+            strength: 1.0,
+            traits: None,
+        };
+        let random = PairingPolicy::Assortative {
+            strength: 0.0,
+            traits: None,
+        };
+        let methods: [&dyn MateSelection<rand::rngs::ThreadRng>; 2] =
+            [&Proportional, &Tournament(3)];
+        for method in methods {
+            let pairs = assortative.pair(rng, 100, method, scores.clone());
+            assert!(pairs.iter().filter(|[a, b]| a == b).count() <= 1);
+            let near = mean_distance(pairs);
+            let far = mean_distance(random.pair(rng, 100, method, scores.clone()));
+            assert!(near < 5.0 && near < far / 4.0, "{near} {far}");
+        }
+    }
+
+    #[test]
+    fn disassortative() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        let policy = PairingPolicy::Disassortative { traits: None };
+        let pairs = Random.pairs_with(rng, 50, scores.clone(), &policy);
+        assert!(pairs.iter().all(|[a, b]| a.abs_diff(*b) == 50));
+        // Pair by a distance function.
+        let location = |x: usize| (x % 10) as f64;
+        let distance = |a, b| (location(a) - location(b)).abs();
+        let pairs = Random.pairs_dissimilar(rng, 50, scores, &distance);
+        assert_eq!(pairs.len(), 50);
+        let total: f64 = pairs.iter().map(|&[a, b]| distance(a, b)).sum();
+        assert!(total > 50.0 * 4.5);
+    }
+
+    #[test]
+    fn scalarized() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let objectives = vec![vec![1.0, 6.0], vec![2.0, 4.0], vec![3.0, 2.0]];
+        let algo = Scalarized::new(Scalarize::Objective(1), Best(1));
+        let pairs = algo.pairs(rng, 5, objectives.clone());
+        assert!(pairs.iter().all(|&pair| pair == [0, 0]));
+        let algo = Scalarized::new(Scalarize::Sum, Proportional);
+        let pdf = MultiObjectiveSelection::<Rng>::pdf(&algo, objectives);
+        for (a, b) in pdf.iter().zip([7.0 / 18.0, 6.0 / 18.0, 5.0 / 18.0]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        // Weighted sum of the normalized objectives.
+        let scalarize = Scalarize::WeightedSum(vec![1.0, 0.5]);
+        let objectives = vec![vec![0.0, 100.0], vec![5.0, 300.0], vec![10.0, 200.0]];
+        assert_eq!(scalarize.scores(&objectives), [0.0, 1.0, 1.25]);
+        // Invalid (NaN) objectives are excluded from the ideal point.
+        let scalarize = Scalarize::Tchebycheff(vec![1.0, 2.0]);
+        let objectives = vec![vec![4.0, 1.0], vec![9.0, f64::NAN], vec![2.0, 3.0]];
+        let scores = scalarize.scores(&objectives);
+        assert_eq!(scores[0], -4.0);
+        assert!(scores[1].is_nan());
+        assert_eq!(scores[2], -2.0);
+    }
+
+    #[test]
+    fn pareto_rank() {
+        type Rng = rand::rngs::ThreadRng;
+        let objectives = vec![
+            vec![1.0, 4.0],
+            vec![2.0, 2.0],
+            vec![4.0, 1.0],
+            vec![1.0, 1.0],
+            vec![0.0, 0.0],
+        ];
+        assert_eq!(pareto_fronts(&objectives), [0, 0, 0, 1, 2]);
+        let pdf = MultiObjectiveSelection::<Rng>::pdf(&ParetoRank, objectives);
+        assert_eq!(pdf, [0.25, 0.25, 0.25, 1.0 / 6.0, 1.0 / 12.0]);
+        // Invalid (NaN) objectives are dominated by every valid individual.
+        let objectives = vec![
+            vec![1.0, f64::NAN],
+            vec![0.0, 0.0],
+            vec![2.0, 1.0],
+            vec![f64::NAN, f64::NAN],
+        ];
+        assert_eq!(pareto_fronts(&objectives), [2, 1, 0, 2]);
+    }
+
+    #[test]
+    fn moead() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let algo = Moead::uniform(2, 9, 3, 1.0);
+        assert_eq!(algo.len(), 10);
+        assert_eq!(algo.weights[3], [3.0 / 9.0, 6.0 / 9.0]);
+        assert_eq!(
+            flatten_and_sort(&[[algo.neighbors[3][1], algo.neighbors[3][2]]]),
+            [2, 4]
+        );
+        // Always mate within the neighborhood.
+        let objectives: Vec<Vec<f64>> = (0..10).map(|x| vec![x as f64, 9.0 - x as f64]).collect();
+        let pairs = algo.pairs(rng, 20, objectives.clone());
+        for (k, [a, b]) in pairs.into_iter().enumerate() {
+            assert!(algo.neighbors[k % 10].contains(&a));
+            assert!(algo.neighbors[k % 10].contains(&b));
+            assert_ne!(a, b);
+        }
+        let pdf = MultiObjectiveSelection::<Rng>::pdf(&algo, objectives.clone());
+        assert!((pdf.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(pdf[0] < pdf[5]);
+        // The best individual for each subproblem.
+        let scores = algo.tchebycheff(0, &objectives);
+        assert_eq!(argsort_best(&scores)[0], 0);
+        let scores = algo.tchebycheff(9, &objectives);
+        assert_eq!(argsort_best(&scores)[0], 9);
+    }
+
+    #[test]
+    fn pairing_strategies() {
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        // Random pairing preserves the selection method's own pairing.
+        let algo = Islands::new(Random, 2);
+        let pairs = algo.pairs_with(rng, 20, scores.clone(), &RandomPairing);
+        assert_eq!(pairs.len(), 20);
+        assert!(pairs.iter().all(|[a, b]| (a < &5) == (b < &5)));
+        // Kinship is a pairing strategy.
+        let kinship = Kinship::Matrix {
+            matrix: (0..10)
+                .map(|a| (0..10).map(|b| (a % 2 == b % 2) as u8 as f64).collect())
+                .collect(),
+            threshold: 0.5,
+        };
+        let pairs = Random.pairs_with(rng, 20, scores.clone(), &kinship);
+        assert!(pairs.iter().all(|[a, b]| a % 2 != b % 2));
+        // Pair by a distance function.
+        let pairing = DistancePairing(|a: usize, b: usize| a.abs_diff(b) as f64);
+        let pairs = Random.pairs_with(rng, 5, scores, &pairing);
+        assert_eq!(pairs.len(), 5);
+    }
+
+    #[test]
+    fn islands() {
+        type Rng = rand::rngs::ThreadRng;
+        let rng = &mut rand::rng();
+        let scores: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let algo = Islands::new(Best(1), 3).with_migration(5, 0.5, MigrationPolicy::Best);
+        assert_eq!(algo.islands(10), [0..3, 3..6, 6..10]);
+        assert_eq!(algo.island_of(10, 4), 1);
+        // Each island mates its own best individual.
+        let plan = algo.plan(rng, 0, 10, scores.clone());
+        assert_eq!(
+            plan.pairs.iter().map(Vec::len).collect::<Vec<_>>(),
+            [3, 3, 4]
+        );
+        assert!(plan.pairs[0].iter().all(|&pair| pair == [2, 2]));
+        assert!(plan.pairs[1].iter().all(|&pair| pair == [5, 5]));
+        assert!(plan.pairs[2].iter().all(|&pair| pair == [9, 9]));
+        // The best half of each island migrates around the ring.
+        let individuals: Vec<_> = plan
+            .migrations
+            .iter()
+            .map(|m| (m.individual, m.from, m.to))
+            .collect();
+        assert_eq!(
+            individuals,
+            [
+                (2, 0, 1),
+                (1, 0, 1),
+                (5, 1, 2),
+                (4, 1, 2),
+                (9, 2, 0),
+                (8, 2, 0)
+            ]
+        );
+        // No migrations between the intervals.
+        assert!(algo.migrations(rng, 3, &scores).is_empty());
+        // The probability is divided between the islands.
+        let pdf = MateSelection::<Rng>::pdf(&algo, scores);
+        assert!((pdf[2] - 0.3).abs() < 1e-12);
+        assert!((pdf[9] - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cellular() {
+        let rng = &mut rand::rng();
+        let grid = Grid {
+            width: 4,
+            radius: 1,
+            shape: GridShape::VonNeumann,
+        };
+        assert_eq!(grid.neighbors(0, 16), [1, 3, 4, 12]);
+        let moore = Grid {
+            shape: GridShape::Moore,
+            ..grid
+        };
+        assert_eq!(moore.neighbors(5, 16), [0, 1, 2, 4, 6, 8, 9, 10]);
+        // Every cell mates with its best neighbor.
+        let scores: Vec<f64> = (0..16).map(|x| x as f64).collect();
+        let algo = Cellular::new(Best(1), grid);
+        let pairs = algo.pairs(rng, 16, scores.clone());
+        let mut cells: Vec<_> = pairs.iter().map(|[cell, _]| *cell).collect();
+        cells.sort_unstable();
+        assert_eq!(cells, (0..16).collect::<Vec<_>>());
+        for [cell, mate] in pairs {
+            let best = *grid.neighbors(cell, 16).iter().max().unwrap();
+            assert_eq!(mate, best);
+        }
+        // Arbitrary neighborhoods, here a ring.
+        let ring = |x: usize| vec![(x + 1) % 16, (x + 15) % 16];
+        let algo = Cellular::new(Random, ring);
+        let pairs = algo.pairs(rng, 32, scores);
+        assert!(pairs.iter().all(|[a, b]| ring(*a).contains(b)));
+    }
+
+    #[test]
+    fn groups() {
+        let rng = &mut rand::rng();
+        let groups = Random.groups(rng, 10, 3, vec![1.0; 6]);
+        assert_eq!(groups.len(), 10);
+        for group in &groups {
+            assert_eq!(group.len(), 3);
+            assert!(group[0] != group[1] && group[1] != group[2] && group[0] != group[2]);
+        }
+        // Not enough individuals to fill a group without repeats.
+        let groups = Random.groups(rng, 2, 4, vec![1.0; 3]);
+        assert!(groups.iter().all(|group| group.len() == 4));
+        assert!(Random.groups(rng, 0, 3, vec![1.0; 3]).is_empty());
+    }
+
+    #[test]
+    fn monogamous() {
+        let rng = &mut rand::rng();
+        let scores = vec![100.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0];
+        let pairs = Proportional.pairs_with(rng, 3, scores.clone(), &PairingPolicy::Monogamous);
+        let mut parents = flatten_and_sort(&pairs);
+        parents.dedup();
+        assert_eq!(parents.len(), 6);
+        assert_eq!(parents, [0, 1, 2, 3, 4, 5]);
+        // Not enough eligible individuals, everyone mates twice.
+        let pairs = Proportional.pairs_with(rng, 6, scores, &PairingPolicy::Monogamous);
+        let parents = flatten_and_sort(&pairs);
+        assert_eq!(parents, [0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5]);
+        assert!(pairs.iter().all(|[a, b]| a != b));
+    }
+
+    #[test]
+    fn capped() {
+        let rng = &mut rand::rng();
+        let mut scores = vec![1.0; 20];
+        scores[0] = 1e9;
+        let algo = Capped {
+            cap: 4,
+            inner: Proportional,
+        };
+        let pairs = algo.pairs(rng, 20, scores);
+        let parents = flatten_and_sort(&pairs);
+        assert_eq!(parents.iter().filter(|&&x| x == 0).count(), 4);
+        for x in 1..20 {
+            assert!(parents.iter().filter(|&&p| p == x).count() <= 4);
+        }
+        // Not enough eligible individuals, the cap is raised.
+        let algo = Capped {
+            cap: 1,
+            inner: Best(2),
+        };
+        let selected = algo.select(rng, 6, vec![1.0, 2.0, 3.0]);
+        assert_eq!(selected.iter().filter(|&&x| x == 2).count(), 3);
+        assert_eq!(selected.iter().filter(|&&x| x == 1).count(), 3);
+    }
+
+    #[test]
+    fn two_populations() {
+        let rng = &mut rand::rng();
+        let pairs =
+            pairs_two_populations(rng, 6, &Best(1), vec![1.0, 3.0, 2.0], &Random, vec![1.0; 6]);
+        assert_eq!(pairs.len(), 6);
+        assert!(pairs.iter().all(|[a, _]| *a == 1));
+        let mut b: Vec<_> = pairs.iter().map(|[_, b]| *b).collect();
+        b.sort_unstable();
+        assert_eq!(b, [0, 1, 2, 3, 4, 5]);
+        let pairs = Random.pairs_two_populations(rng, 10, vec![1.0; 2], vec![1.0; 20]);
+        assert!(pairs.iter().all(|[a, b]| *a < 2 && *b < 20));
+    }
+
+    #[test]
+    fn unique_pairs() {
+        let rng = &mut rand::rng();
+        let pairs = Random.pairs_with(rng, 10, vec![1.0; 5], &PairingPolicy::Unique);
+        assert_eq!(pairs.len(), 10);
+        let mut unordered: Vec<_> = pairs.iter().map(|&[a, b]| [a.min(b), a.max(b)]).collect();
+        unordered.sort_unstable();
+        unordered.dedup();
+        assert_eq!(unordered.len(), 10);
+        // There is only one possible pair.
+        let pairs = Best(2).pairs_with(rng, 5, vec![1.0, 2.0, 3.0], &PairingPolicy::Unique);
+        assert_eq!(pairs.len(), 1);
+    }
+
+    #[test]
+    fn coverage() {
+        let rng = &mut rand::rng();
+        let mut scores = vec![1.0; 10];
+        scores[0] = 1e9;
+        scores[9] = 0.0;
+        let pairs = Proportional.pairs_with(rng, 6, scores.clone(), &PairingPolicy::Coverage);
+        let mut parents = flatten_and_sort(&pairs);
+        assert_eq!(parents.iter().filter(|&&x| x == 0).count(), 4);
+        parents.dedup();
+        assert_eq!(parents, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        // Too few pairs to cover everyone.
+        let pairs = Proportional.pairs_with(rng, 2, scores, &PairingPolicy::Coverage);
+        assert_eq!(flatten_and_sort(&pairs), [0, 0, 0, 0]);
+    }
+
     /// Example of the trait used as an argument.
     #[test]
     fn argument() {
         type Rng = rand::rngs::ThreadRng;
 
         fn foobar(select: &dyn MateSelection<Rng>) {
-            let rng = &mut rand::thread_rng();
+            let rng = &mut rand::rng();
             select.select(rng, 0, vec![]);
         }
 
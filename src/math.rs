@@ -0,0 +1,362 @@
+//! Float functions for builds without the standard library
+//!
+//! The core library does not provide the transcendental functions, because
+//! they depend on the platform's math library. These are ports of the musl
+//! implementations, except for [powf] which trades some accuracy for size.
+
+// Keep the constants exactly as they are written in musl.
+#![allow(clippy::excessive_precision)]
+
+/// The float methods which are missing from the core library.
+///
+/// Import this trait in modules which use these methods, so that the same
+/// code compiles with and without std.
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) trait Float {
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn cos(self) -> Self;
+}
+
+#[cfg(not(any(feature = "std", test)))]
+impl Float for f64 {
+    fn floor(self) -> f64 {
+        floor(self)
+    }
+    fn round(self) -> f64 {
+        round(self)
+    }
+    fn sqrt(self) -> f64 {
+        sqrt(self)
+    }
+    fn powi(self, n: i32) -> f64 {
+        powi(self, n)
+    }
+    fn powf(self, n: f64) -> f64 {
+        powf(self, n)
+    }
+    fn exp(self) -> f64 {
+        exp(self)
+    }
+    fn ln(self) -> f64 {
+        ln(self)
+    }
+    fn ln_1p(self) -> f64 {
+        ln_1p(self)
+    }
+    fn cos(self) -> f64 {
+        cos(self)
+    }
+}
+
+/// Every f64 with a magnitude of at least 2^52 is an integer.
+const TWO_52: f64 = 4503599627370496.0;
+
+/// Round towards zero.
+fn trunc(x: f64) -> f64 {
+    if x.abs() < TWO_52 {
+        (x as i64 as f64).copysign(x)
+    } else {
+        x
+    }
+}
+
+pub(crate) fn floor(x: f64) -> f64 {
+    let t = trunc(x);
+    if t > x {
+        t - 1.0
+    } else {
+        t
+    }
+}
+
+/// Round half way cases away from zero.
+pub(crate) fn round(x: f64) -> f64 {
+    let t = trunc(x);
+    // The fractional part is exact, because the integer part fits in the mantissa.
+    if (x - t).abs() >= 0.5 {
+        t + 1.0_f64.copysign(x)
+    } else {
+        t
+    }
+}
+
+/// Multiply by 2^n, without overflowing in the intermediate steps.
+fn scalbn(mut x: f64, mut n: i32) -> f64 {
+    let two_1023 = f64::from_bits(0x7fe0000000000000);
+    // 2^-1022 * 2^53, so that the result is not rounded twice.
+    let two_969 = f64::from_bits(0x0360000000000000);
+    if n > 1023 {
+        x *= two_1023;
+        n -= 1023;
+        if n > 1023 {
+            x *= two_1023;
+            n = (n - 1023).min(1023);
+        }
+    } else if n < -1022 {
+        x *= two_969;
+        n += 1022 - 53;
+        if n < -1022 {
+            x *= two_969;
+            n = (n + 1022 - 53).max(-1022);
+        }
+    }
+    x * f64::from_bits(((0x3ff + n) as u64) << 52)
+}
+
+pub(crate) fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    } else if x == 0.0 || x == f64::INFINITY {
+        return x;
+    }
+    // Scale subnormals into the normal range, where the initial guess works.
+    let two_108 = f64::from_bits(0x46b0000000000000);
+    let two_54 = f64::from_bits(0x4350000000000000);
+    let (x, scale) = if x < f64::MIN_POSITIVE {
+        (x * two_108, 1.0 / two_54)
+    } else {
+        (x, 1.0)
+    };
+    // Halve the exponent for the initial guess, then refine it with Newton's method.
+    let mut y = f64::from_bits((x.to_bits() >> 1) + (0x3ff << 51));
+    for _ in 0..6 {
+        y = 0.5 * (y + x / y);
+    }
+    // Newton's method can end one unit in the last place away from the
+    // correctly rounded root, so check the neighbors.
+    for candidate in [
+        f64::from_bits(y.to_bits() - 1),
+        f64::from_bits(y.to_bits() + 1),
+    ] {
+        if (candidate * candidate - x).abs() < (y * y - x).abs() {
+            y = candidate;
+        }
+    }
+    y * scale
+}
+
+pub(crate) fn powi(mut x: f64, n: i32) -> f64 {
+    let mut power = n.unsigned_abs();
+    let mut result = 1.0;
+    while power > 0 {
+        if power & 1 == 1 {
+            result *= x;
+        }
+        x *= x;
+        power >>= 1;
+    }
+    if n < 0 {
+        1.0 / result
+    } else {
+        result
+    }
+}
+
+/// Raise to a float power, by way of the logarithm.
+///
+/// The relative error grows with the magnitude of `n * ln(x)`, up to a few
+/// parts in 10^13 for results near the limits of the float range.
+pub(crate) fn powf(x: f64, n: f64) -> f64 {
+    let odd = trunc(n) == n && n.abs() < TWO_52 && (n as i64) % 2 != 0;
+    if n == 0.0 || x == 1.0 {
+        1.0
+    } else if x.is_nan() || n.is_nan() {
+        f64::NAN
+    } else if n.is_infinite() {
+        if x.abs() == 1.0 {
+            1.0
+        } else if (x.abs() > 1.0) == (n > 0.0) {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    } else if x == 0.0 || x.is_infinite() {
+        let magnitude = if (x == 0.0) == (n < 0.0) {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        if odd {
+            magnitude.copysign(x)
+        } else {
+            magnitude
+        }
+    } else if x < 0.0 {
+        if trunc(n) != n {
+            f64::NAN
+        } else if odd {
+            -powf(-x, n)
+        } else {
+            powf(-x, n)
+        }
+    } else {
+        exp(n * ln(x))
+    }
+}
+
+const LN2_HI: f64 = 6.93147180369123816490e-01;
+const LN2_LO: f64 = 1.90821492927058770002e-10;
+
+pub(crate) fn exp(x: f64) -> f64 {
+    const P1: f64 = 1.66666666666666019037e-01;
+    const P2: f64 = -2.77777777770155933842e-03;
+    const P3: f64 = 6.61375632143793436117e-05;
+    const P4: f64 = -1.65339022054652515390e-06;
+    const P5: f64 = 4.13813679705723846039e-08;
+    if x.is_nan() {
+        return x;
+    } else if x > 709.782712893383973096 {
+        return f64::INFINITY;
+    } else if x < -745.13321910194110842 {
+        return 0.0;
+    } else if x.abs() < f64::from_bits(0x3e30000000000000) {
+        // exp(x) = 1 + x, to within rounding, for |x| < 2^-28.
+        return 1.0 + x;
+    }
+    // Reduce the argument: x = k * ln(2) + r, with |r| <= ln(2) / 2.
+    let k = round(x * core::f64::consts::LOG2_E);
+    let hi = x - k * LN2_HI;
+    let lo = k * LN2_LO;
+    let r = hi - lo;
+    let rr = r * r;
+    let c = r - rr * (P1 + rr * (P2 + rr * (P3 + rr * (P4 + rr * P5))));
+    let y = 1.0 + (r * c / (2.0 - c) - lo + hi);
+    scalbn(y, k as i32)
+}
+
+pub(crate) fn ln(x: f64) -> f64 {
+    const LG1: f64 = 6.666666666666735130e-01;
+    const LG2: f64 = 3.999999999940941908e-01;
+    const LG3: f64 = 2.857142874366239149e-01;
+    const LG4: f64 = 2.222219843214978396e-01;
+    const LG5: f64 = 1.818357216161805012e-01;
+    const LG6: f64 = 1.531383769920937332e-01;
+    const LG7: f64 = 1.479819860511658591e-01;
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x == f64::INFINITY {
+        return x;
+    }
+    let mut x = x;
+    let mut k = 0;
+    if x < f64::MIN_POSITIVE {
+        // Scale subnormals into the normal range.
+        k -= 54;
+        x *= f64::from_bits(0x4350000000000000);
+    }
+    // Reduce the argument: x = 2^k * (1 + f), with sqrt(2)/2 < 1 + f < sqrt(2).
+    let bits = x.to_bits();
+    let hx = (bits >> 32) as u32 + (0x3ff00000 - 0x3fe6a09e);
+    k += (hx >> 20) as i32 - 0x3ff;
+    let hx = (hx & 0x000fffff) + 0x3fe6a09e;
+    let x = f64::from_bits(((hx as u64) << 32) | (bits & 0xffffffff));
+    let f = x - 1.0;
+    let hfsq = 0.5 * f * f;
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let w = z * z;
+    let t1 = w * (LG2 + w * (LG4 + w * LG6));
+    let t2 = z * (LG1 + w * (LG3 + w * (LG5 + w * LG7)));
+    let r = t2 + t1;
+    let dk = k as f64;
+    s * (hfsq + r) + dk * LN2_LO - hfsq + f + dk * LN2_HI
+}
+
+/// Natural logarithm of `1 + x`, accurate for small `x`.
+pub(crate) fn ln_1p(x: f64) -> f64 {
+    let u = 1.0 + x;
+    if u == 1.0 || u == f64::INFINITY {
+        x
+    } else if u <= 0.0 {
+        ln(u)
+    } else {
+        // Correct for the rounding error in computing u.
+        ln(u) * (x / (u - 1.0))
+    }
+}
+
+/// Cosine, with the argument reduced by Cody and Waite's method.
+///
+/// The reduction is accurate for |x| < 2^20 * pi / 2, which covers every use
+/// in this crate.
+pub(crate) fn cos(x: f64) -> f64 {
+    const PIO2_1: f64 = 1.57079632673412561417e+00;
+    const PIO2_1T: f64 = 6.07710050650619224932e-11;
+    const PIO2_2: f64 = 6.07710050630396597660e-11;
+    const PIO2_2T: f64 = 2.02226624879595063154e-21;
+    const PIO2_3: f64 = 2.02226624871116645580e-21;
+    const PIO2_3T: f64 = 8.47842766036889956997e-32;
+    if !x.is_finite() {
+        return f64::NAN;
+    } else if x.abs() <= core::f64::consts::FRAC_PI_4 {
+        return cos_kernel(x, 0.0);
+    }
+    // Reduce the argument: x = n * pi / 2 + (y0 + y1), with |y0 + y1| <= pi / 4.
+    let exponent = |v: f64| ((v.to_bits() >> 52) & 0x7ff) as i32;
+    let n = round(x * core::f64::consts::FRAC_2_PI);
+    let mut r = x - n * PIO2_1;
+    let mut w = n * PIO2_1T;
+    let mut y0 = r - w;
+    // Refine the reduction when it cancels out many bits.
+    if exponent(x) - exponent(y0) > 16 {
+        let t = r;
+        w = n * PIO2_2;
+        r = t - w;
+        w = n * PIO2_2T - ((t - r) - w);
+        y0 = r - w;
+        if exponent(x) - exponent(y0) > 49 {
+            let t = r;
+            w = n * PIO2_3;
+            r = t - w;
+            w = n * PIO2_3T - ((t - r) - w);
+            y0 = r - w;
+        }
+    }
+    let y1 = (r - y0) - w;
+    match (n as i64) & 3 {
+        0 => cos_kernel(y0, y1),
+        1 => -sin_kernel(y0, y1),
+        2 => -cos_kernel(y0, y1),
+        _ => sin_kernel(y0, y1),
+    }
+}
+
+/// Cosine of `x + y`, for |x + y| <= pi / 4 where `y` is the tail of `x`.
+fn cos_kernel(x: f64, y: f64) -> f64 {
+    const C1: f64 = 4.16666666666666019037e-02;
+    const C2: f64 = -1.38888888888741095749e-03;
+    const C3: f64 = 2.48015872894767294178e-05;
+    const C4: f64 = -2.75573143513906633035e-07;
+    const C5: f64 = 2.08757232129817482790e-09;
+    const C6: f64 = -1.13596475577881948265e-11;
+    let z = x * x;
+    let w = z * z;
+    let r = z * (C1 + z * (C2 + z * C3)) + w * w * (C4 + z * (C5 + z * C6));
+    let hz = 0.5 * z;
+    let w = 1.0 - hz;
+    w + (((1.0 - w) - hz) + (z * r - x * y))
+}
+
+/// Sine of `x + y`, for |x + y| <= pi / 4 where `y` is the tail of `x`.
+fn sin_kernel(x: f64, y: f64) -> f64 {
+    const S1: f64 = -1.66666666666666324348e-01;
+    const S2: f64 = 8.33333333332248946124e-03;
+    const S3: f64 = -1.98412698298579493134e-04;
+    const S4: f64 = 2.75573137070700676789e-06;
+    const S5: f64 = -2.50507602534068634195e-08;
+    const S6: f64 = 1.58969099521155010221e-10;
+    let z = x * x;
+    let w = z * z;
+    let r = S2 + z * (S3 + z * S4) + z * w * (S5 + z * S6);
+    let v = z * x;
+    x - ((z * (0.5 * y - v * r) - y) - v * S1)
+}
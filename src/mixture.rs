@@ -0,0 +1,84 @@
+//! Draw each parent from one of several selection methods
+
+use crate::{expect_valid, sus, uniform_if_zero, MateSelection, SelectionError};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Mixture of several selection methods. Each parent is selected by one of
+/// the methods, chosen with probability proportional to its weight. For
+/// example, mixing a small weight of [Random](crate::Random) selection into a
+/// method with strong selection pressure gives every individual a chance to
+/// mate.
+///
+/// The number of parents from each method is decided by stochastic universal
+/// sampling of the weights, and then the parents are shuffled together. The
+/// probability distribution function is the weighted average of the methods'
+/// probability distribution functions. A method which gives every individual a
+/// weight of zero contributes the uniform distribution.
+///
+/// Argument "**methods**" is a list of `(weight, method)` entries. The weights
+/// must be finite and non-negative, and at least one must be positive.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Mixture<M>(pub Vec<(f64, M)>);
+
+impl<M> Mixture<M> {
+    fn weights(&self) -> Vec<f64> {
+        self.0.iter().map(|(weight, _)| *weight).collect()
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for Mixture<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        if self.0.iter().any(|(w, _)| !(w.is_finite() && *w >= 0.0)) {
+            return Err(SelectionError::argument(
+                "weight",
+                "is negative or not finite",
+            ));
+        }
+        if !self.0.iter().any(|(w, _)| *w > 0.0) {
+            return Err(SelectionError::argument("weight", "are all zero"));
+        }
+        self.0.iter().try_for_each(|(_, method)| method.validate())
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.select_slice(rng, amount, &scores)
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        expect_valid(MateSelection::<R>::validate(self));
+        let mut counts = vec![0; self.0.len()];
+        for method in sus::choose_multiple_weighted(rng, amount, &self.weights()) {
+            counts[method] += 1;
+        }
+        let mut selected = Vec::with_capacity(amount);
+        for ((_, method), count) in self.0.iter().zip(counts) {
+            if count > 0 {
+                selected.append(&mut method.select_slice(rng, count, scores));
+            }
+        }
+        selected.shuffle(rng);
+        selected
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        expect_valid(MateSelection::<R>::validate(self));
+        let total: f64 = self.weights().iter().sum();
+        let mut pdf = vec![0.0; scores.len()];
+        for (weight, method) in &self.0 {
+            if *weight > 0.0 {
+                let mut p = method.pdf(scores.clone());
+                uniform_if_zero(&mut p);
+                let sum: f64 = p.iter().sum();
+                for (x, p) in pdf.iter_mut().zip(p) {
+                    *x += weight / total * p / sum;
+                }
+            }
+        }
+        pdf
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let pdf = MateSelection::<R>::pdf(self, scores.to_vec());
+        scores.copy_from_slice(&pdf);
+    }
+}
@@ -0,0 +1,205 @@
+//! Multi-objective evolutionary algorithm based on decomposition
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::multi_objective::num_objectives;
+use crate::{MultiObjectiveSelection, Scalarize};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+/// Mating selection for MOEA/D, as described by Zhang & Li (2007).
+///
+/// MOEA/D decomposes a multi-objective problem into many single objective
+/// subproblems, one for each weight vector. Each individual of the population
+/// is the current solution to one subproblem: individual `i` belongs to
+/// subproblem `i`, and so the population size must equal the number of weight
+/// vectors. Subproblems with similar weight vectors are neighbors, and the
+/// parents for a subproblem are chosen from its neighborhood.
+///
+/// The k-th pair (or parent) is chosen for subproblem `k` modulo the number of
+/// subproblems. With probability "delta" both parents are chosen uniformly at
+/// random from the subproblem's neighborhood, otherwise they're chosen from the
+/// whole population.
+///
+/// Use [Moead::tchebycheff] to compare the offspring of a subproblem with its
+/// current solution.
+///
+/// Argument "**weights**" contains the weight vector of each subproblem.
+///
+/// Argument "**neighbors**" contains the neighborhood of each subproblem, as
+/// indices into the list of weight vectors, sorted from nearest to farthest.
+///
+/// Argument "**delta**" is the probability of mating within the neighborhood,
+/// in the range [0, 1].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Moead {
+    pub weights: Vec<Vec<f64>>,
+    pub neighbors: Vec<Vec<usize>>,
+    pub delta: f64,
+}
+
+impl Moead {
+    /// Argument "neighborhood" is the number of nearest weight vectors in each
+    /// subproblem's neighborhood, including the subproblem itself.
+    pub fn new(weights: Vec<Vec<f64>>, neighborhood: usize, delta: f64) -> Self {
+        num_objectives(&weights);
+        assert!(
+            neighborhood > 0,
+            "argument \"neighborhood\" is less than one"
+        );
+        let neighbors = weights
+            .iter()
+            .map(|a| {
+                let distance: Vec<f64> = weights
+                    .iter()
+                    .map(|b| a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum())
+                    .collect();
+                let mut order: Vec<usize> = (0..weights.len()).collect();
+                order.sort_by(|&i, &j| distance[i].total_cmp(&distance[j]));
+                order.truncate(neighborhood);
+                order
+            })
+            .collect();
+        Self {
+            weights,
+            neighbors,
+            delta,
+        }
+    }
+
+    /// Generate evenly spread weight vectors, as described by Das & Dennis
+    /// (1998). Every weight vector sums to one and each weight is a multiple of
+    /// `1 / divisions`.
+    ///
+    /// There are `binomial(divisions + num_objectives - 1, num_objectives - 1)`
+    /// weight vectors.
+    pub fn uniform(
+        num_objectives: usize,
+        divisions: usize,
+        neighborhood: usize,
+        delta: f64,
+    ) -> Self {
+        assert!(
+            num_objectives > 0,
+            "argument \"num_objectives\" is less than one"
+        );
+        assert!(divisions > 0, "argument \"divisions\" is less than one");
+        let mut weights = vec![];
+        simplex_lattice(num_objectives, divisions, &mut vec![], &mut weights);
+        let weights = weights
+            .into_iter()
+            .map(|w| w.into_iter().map(|x| x as f64 / divisions as f64).collect())
+            .collect();
+        Self::new(weights, neighborhood, delta)
+    }
+
+    /// Number of subproblems, which is also the required population size.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Evaluate every individual on the given subproblem with the Tchebycheff
+    /// approach. The reference point is the best value of each objective in the
+    /// population. Greater values are better.
+    pub fn tchebycheff(&self, subproblem: usize, objectives: &[Vec<f64>]) -> Vec<f64> {
+        Scalarize::Tchebycheff(self.weights[subproblem].clone()).scores(objectives)
+    }
+
+    fn check(&self, population: usize) {
+        assert!(
+            (0.0..=1.0).contains(&self.delta),
+            "argument \"delta\" is out of bounds [0, 1]"
+        );
+        assert_eq!(
+            population,
+            self.len(),
+            "population size does not match the number of subproblems"
+        );
+    }
+
+    /// Choose the pool of candidate parents for a subproblem.
+    fn pool<R: Rng + ?Sized>(&self, rng: &mut R, subproblem: usize) -> Option<&[usize]> {
+        if rng.random_bool(self.delta) {
+            Some(&self.neighbors[subproblem])
+        } else {
+            None
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> MultiObjectiveSelection<R> for Moead {
+    fn pairs(&self, rng: &mut R, amount: usize, objectives: Vec<Vec<f64>>) -> Vec<[usize; 2]> {
+        self.check(objectives.len());
+        (0..amount)
+            .map(|k| {
+                let subproblem = k % self.len();
+                let (a, b) = match self.pool(rng, subproblem) {
+                    Some(pool) if pool.len() >= 2 => {
+                        let pair = rand::seq::index::sample(rng, pool.len(), 2);
+                        (pool[pair.index(0)], pool[pair.index(1)])
+                    }
+                    Some(pool) => (pool[0], pool[0]),
+                    None if self.len() >= 2 => {
+                        let pair = rand::seq::index::sample(rng, self.len(), 2);
+                        (pair.index(0), pair.index(1))
+                    }
+                    None => (0, 0),
+                };
+                [a, b]
+            })
+            .collect()
+    }
+
+    fn select(&self, rng: &mut R, amount: usize, objectives: Vec<Vec<f64>>) -> Vec<usize> {
+        self.check(objectives.len());
+        (0..amount)
+            .map(|k| {
+                let subproblem = k % self.len();
+                match self.pool(rng, subproblem) {
+                    Some(pool) => pool[rng.random_range(0..pool.len())],
+                    None => rng.random_range(0..self.len()),
+                }
+            })
+            .collect()
+    }
+
+    /// The probability of choosing each individual, averaged over all of the
+    /// subproblems.
+    fn sample_weight(&self, objectives: Vec<Vec<f64>>) -> Vec<f64> {
+        self.check(objectives.len());
+        let population = self.len();
+        let mut weights = vec![(1.0 - self.delta) / population as f64; population];
+        for pool in &self.neighbors {
+            for &individual in pool {
+                weights[individual] += self.delta / (pool.len() * population) as f64;
+            }
+        }
+        weights
+    }
+}
+
+/// Find every way of dividing "remainder" into the given number of parts.
+fn simplex_lattice(
+    parts: usize,
+    remainder: usize,
+    prefix: &mut Vec<usize>,
+    output: &mut Vec<Vec<usize>>,
+) {
+    if parts == 1 {
+        let mut weights = prefix.clone();
+        weights.push(remainder);
+        output.push(weights);
+        return;
+    }
+    for x in 0..=remainder {
+        prefix.push(x);
+        simplex_lattice(parts - 1, remainder - x, prefix, output);
+        prefix.pop();
+    }
+}
@@ -0,0 +1,297 @@
+//! Selection methods for multi-objective optimization
+
+use crate::{reduce_repeats, sus, vec_to_pairs, MateSelection};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Multi-objective mate selection algorithms randomly select pairs of
+/// individuals from a population, where each individual is scored by several
+/// objectives instead of a single reproductive fitness.
+///
+/// The objectives are given as a matrix with one row for each individual and
+/// one column for each objective, `objectives[individual][objective]`.
+/// Every objective is maximized: a greater value is better.
+pub trait MultiObjectiveSelection<R: Rng + ?Sized = dyn Rng>: core::fmt::Debug {
+    /// Choose multiple weighted pairs
+    ///
+    /// * Argument `amount` is the number of pairs to return.
+    ///
+    /// * Argument "objectives" is the matrix of objective values.
+    ///
+    /// * Returns a list of pairs of parents to mate together.
+    ///   The parents are specified as indices into the objectives matrix.
+    fn pairs(&self, rng: &mut R, amount: usize, objectives: Vec<Vec<f64>>) -> Vec<[usize; 2]> {
+        let mut pairs = self.select(rng, amount * 2, objectives);
+
+        reduce_repeats(&mut pairs);
+
+        vec_to_pairs(pairs)
+    }
+
+    /// Choose multiple weighted
+    fn select(&self, rng: &mut R, amount: usize, objectives: Vec<Vec<f64>>) -> Vec<usize> {
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!objectives.is_empty());
+        }
+
+        let weights = self.sample_weight(objectives);
+
+        sus::choose_multiple_weighted(rng, amount, &weights)
+    }
+
+    /// Probability distribution function
+    fn pdf(&self, objectives: Vec<Vec<f64>>) -> Vec<f64> {
+        let mut pdf = self.sample_weight(objectives);
+        // Normalize the sum to one.
+        let sum: f64 = pdf.iter().sum();
+        let div_sum = 1.0 / sum;
+        for x in pdf.iter_mut() {
+            *x *= div_sum;
+        }
+        pdf
+    }
+
+    /// Transform the objective values into sampling weights.
+    /// The sampling weights do **not** need to sum to one.
+    fn sample_weight(&self, objectives: Vec<Vec<f64>>) -> Vec<f64>;
+}
+
+/// Methods for combining several objectives into a single score.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Scalarize {
+    /// Use only the given objective and ignore all of the others.
+    Objective(usize),
+
+    /// Add up all of the objectives.
+    Sum,
+
+    /// Weighted sum of the objectives. Each objective is first rescaled to the
+    /// range [0, 1] across the population, so that the weights are not skewed
+    /// by the magnitudes of the objectives.
+    ///
+    /// Argument "**weights**" contains the importance of each objective.
+    WeightedSum(Vec<f64>),
+
+    /// Weighted Tchebycheff distance to the ideal point, which is the best
+    /// value of each objective in the population. The distance is negated so
+    /// that greater scores are better.
+    ///
+    /// >   `score(i) = -max(weight(j) * (ideal(j) - objective(i, j)) for j in objectives)`
+    ///
+    /// Individuals with any invalid (NaN) objectives are given invalid scores,
+    /// and are not part of the ideal point.
+    ///
+    /// Argument "**weights**" contains the importance of each objective.
+    Tchebycheff(Vec<f64>),
+}
+
+impl Scalarize {
+    /// Combine the objectives of each individual into a single score.
+    pub fn scores(&self, objectives: &[Vec<f64>]) -> Vec<f64> {
+        let num_objectives = num_objectives(objectives);
+        match self {
+            Self::Objective(objective) => {
+                assert!(
+                    *objective < num_objectives,
+                    "argument \"objective\" is out of bounds"
+                );
+                objectives.iter().map(|row| row[*objective]).collect()
+            }
+            Self::Sum => objectives.iter().map(|row| row.iter().sum()).collect(),
+            Self::WeightedSum(weights) => {
+                assert_eq!(
+                    weights.len(),
+                    num_objectives,
+                    "argument \"weights\" has the wrong length"
+                );
+                let mut scores = vec![0.0; objectives.len()];
+                for (objective, &weight) in weights.iter().enumerate() {
+                    let column: Vec<f64> = objectives.iter().map(|row| row[objective]).collect();
+                    for (score, value) in scores.iter_mut().zip(normalize(&column)) {
+                        *score += weight * value;
+                    }
+                }
+                scores
+            }
+            Self::Tchebycheff(weights) => {
+                assert_eq!(
+                    weights.len(),
+                    num_objectives,
+                    "argument \"weights\" has the wrong length"
+                );
+                // The ideal point is found from the individuals whose
+                // objectives are all valid.
+                let is_valid = |row: &&Vec<f64>| !row.iter().any(|x| x.is_nan());
+                let ideal: Vec<f64> = (0..num_objectives)
+                    .map(|j| {
+                        objectives
+                            .iter()
+                            .filter(is_valid)
+                            .map(|row| row[j])
+                            .fold(f64::NEG_INFINITY, f64::max)
+                    })
+                    .collect();
+                objectives
+                    .iter()
+                    .map(|row| {
+                        if !is_valid(&row) {
+                            return f64::NAN;
+                        }
+                        let distance = (0..num_objectives)
+                            .map(|j| weights[j] * (ideal[j] - row[j]))
+                            .fold(0.0, f64::max);
+                        -distance
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Reuse a single objective selection method for multi-objective optimization.
+/// The objectives are scalarized into a single score and then the inner
+/// method selects from those scores.
+///
+/// Argument "**scalarize**" is the method for combining the objectives.
+///
+/// Argument "**inner**" is the method for selecting from the combined scores.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Scalarized<M> {
+    pub scalarize: Scalarize,
+    pub inner: M,
+}
+
+impl<M> Scalarized<M> {
+    pub fn new(scalarize: Scalarize, inner: M) -> Self {
+        Self { scalarize, inner }
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MultiObjectiveSelection<R> for Scalarized<M> {
+    fn pairs(&self, rng: &mut R, amount: usize, objectives: Vec<Vec<f64>>) -> Vec<[usize; 2]> {
+        let scores = self.scalarize.scores(&objectives);
+        self.inner.pairs(rng, amount, scores)
+    }
+
+    fn select(&self, rng: &mut R, amount: usize, objectives: Vec<Vec<f64>>) -> Vec<usize> {
+        let scores = self.scalarize.scores(&objectives);
+        self.inner.select(rng, amount, scores)
+    }
+
+    fn sample_weight(&self, objectives: Vec<Vec<f64>>) -> Vec<f64> {
+        let scores = self.scalarize.scores(&objectives);
+        self.inner.sample_weight(scores)
+    }
+}
+
+/// Select parents with a probability that is proportional to their Pareto
+/// rank. This is a simple alternative to the crowding and niching methods of
+/// more sophisticated multi-objective algorithms.
+///
+/// The population is sorted into non-dominated fronts (see [pareto_fronts]).
+/// Individuals on the first front are weighted by the number of fronts, and
+/// each subsequent front has one less weight, so that the last front has a
+/// weight of one.
+///
+/// >   `weight(i) = num_fronts - front(i)`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ParetoRank;
+
+impl<R: Rng + ?Sized> MultiObjectiveSelection<R> for ParetoRank {
+    fn sample_weight(&self, objectives: Vec<Vec<f64>>) -> Vec<f64> {
+        let fronts = pareto_fronts(&objectives);
+        let num_fronts = fronts.iter().max().map_or(0, |&front| front + 1);
+        fronts
+            .into_iter()
+            .map(|front| (num_fronts - front) as f64)
+            .collect()
+    }
+}
+
+/// Sort the population into non-dominated fronts, as described by Deb et al.
+/// (2002). Individual "a" dominates individual "b" if "a" is at least as good
+/// as "b" in every objective and is better in at least one objective.
+/// Individuals with any invalid (NaN) objectives are dominated by every valid
+/// individual.
+///
+/// Returns the front of each individual. The first front (zero) contains the
+/// individuals which are not dominated by anyone, the next front contains the
+/// individuals which are only dominated by the first front, and so on.
+pub fn pareto_fronts(objectives: &[Vec<f64>]) -> Vec<usize> {
+    num_objectives(objectives);
+    let population = objectives.len();
+    // For each individual, the individuals which it dominates and the number
+    // of individuals which dominate it.
+    let mut dominates = vec![vec![]; population];
+    let mut num_dominators = vec![0_usize; population];
+    for a in 0..population {
+        for b in (a + 1)..population {
+            if dominance(&objectives[a], &objectives[b]) {
+                dominates[a].push(b);
+                num_dominators[b] += 1;
+            } else if dominance(&objectives[b], &objectives[a]) {
+                dominates[b].push(a);
+                num_dominators[a] += 1;
+            }
+        }
+    }
+    let mut fronts = vec![0; population];
+    let mut current: Vec<usize> = (0..population)
+        .filter(|&i| num_dominators[i] == 0)
+        .collect();
+    let mut front = 0;
+    while !current.is_empty() {
+        let mut next = vec![];
+        for &a in &current {
+            fronts[a] = front;
+            for &b in &dominates[a] {
+                num_dominators[b] -= 1;
+                if num_dominators[b] == 0 {
+                    next.push(b);
+                }
+            }
+        }
+        current = next;
+        front += 1;
+    }
+    fronts
+}
+
+/// Check if individual "a" dominates individual "b". Individuals with any
+/// invalid (NaN) objectives are dominated by every valid individual, and do
+/// not dominate anyone.
+fn dominance(a: &[f64], b: &[f64]) -> bool {
+    let is_valid = |row: &[f64]| !row.iter().any(|x| x.is_nan());
+    match (is_valid(a), is_valid(b)) {
+        (true, true) => a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y),
+        (a_valid, b_valid) => a_valid && !b_valid,
+    }
+}
+
+/// Rescale the values to the range [0, 1]. If all of the values are the same
+/// then they are all set to zero.
+fn normalize(values: &[f64]) -> Vec<f64> {
+    let finite = values.iter().copied().filter(|x| x.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    if range.is_nan() || range <= 0.0 {
+        return vec![0.0; values.len()];
+    }
+    values.iter().map(|x| (x - min) / range).collect()
+}
+
+/// Check that every individual has the same number of objectives, and return
+/// that number.
+pub(crate) fn num_objectives(objectives: &[Vec<f64>]) -> usize {
+    let num = objectives.first().map_or(0, Vec::len);
+    assert!(
+        objectives.iter().all(|row| row.len() == num),
+        "argument \"objectives\" has rows of different lengths"
+    );
+    num
+}
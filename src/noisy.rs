@@ -0,0 +1,130 @@
+//! Selection from stochastic fitness evaluations
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{expect_valid, MateSelection, SelectionError};
+use alloc::vec::Vec;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+/// Estimate of the score of an individual, from repeated noisy evaluations.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq)]
+pub struct NoisyScore {
+    /// Mean of the evaluations.
+    pub mean: f64,
+    /// Standard error of the mean.
+    pub stderr: f64,
+    /// Number of evaluations. Individuals which have not been evaluated are
+    /// treated as invalid (NaN) scores.
+    pub evaluations: usize,
+}
+
+impl NoisyScore {
+    /// Estimate the score from a list of evaluations. A single evaluation has
+    /// a standard error of zero.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let stderr = if samples.len() > 1 {
+            let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (variance / n).sqrt()
+        } else {
+            0.0
+        };
+        Self {
+            mean,
+            stderr,
+            evaluations: samples.len(),
+        }
+    }
+}
+
+/// How to reduce a [NoisyScore] to a single score.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum NoiseHandling {
+    /// Rank by the lower confidence bound, `mean - z * stderr`, which favors
+    /// the individuals whose scores are known with more certainty. The
+    /// argument is the number of standard errors, `z`.
+    LowerBound(f64),
+
+    /// Draw each score from a normal distribution with the estimated mean and
+    /// standard error, so that each individual is selected in proportion to
+    /// how likely it is to actually be good.
+    Resample,
+}
+
+/// Select from noisy scores, by first reducing each score to a single value
+/// and then selecting from those values with the inner method.
+///
+/// Argument "**handling**" decides how the scores are reduced, see [NoiseHandling].
+///
+/// Argument "**inner**" is the method for selecting parents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Noisy<M> {
+    pub handling: NoiseHandling,
+    pub inner: M,
+}
+
+impl<M> Noisy<M> {
+    pub fn new(handling: NoiseHandling, inner: M) -> Self {
+        Self { handling, inner }
+    }
+
+    /// Check that the arguments of this selection method are valid.
+    pub fn validate<R: Rng + ?Sized>(&self) -> Result<(), SelectionError>
+    where
+        M: MateSelection<R>,
+    {
+        if let NoiseHandling::LowerBound(z) = self.handling {
+            if !z.is_finite() {
+                return Err(SelectionError::argument("z", "is not finite"));
+            }
+        }
+        self.inner.validate()
+    }
+
+    /// Reduce the noisy scores to single scores, see [NoiseHandling].
+    pub fn scores<R: Rng + ?Sized>(&self, rng: &mut R, scores: &[NoisyScore]) -> Vec<f64> {
+        scores
+            .iter()
+            .map(|score| {
+                if score.evaluations == 0 {
+                    return f64::NAN;
+                }
+                match self.handling {
+                    NoiseHandling::LowerBound(z) => score.mean - z * score.stderr,
+                    NoiseHandling::Resample => score.mean + score.stderr * standard_normal(rng),
+                }
+            })
+            .collect()
+    }
+
+    /// Choose multiple weighted. See [MateSelection::select].
+    pub fn select<R>(&self, rng: &mut R, amount: usize, scores: &[NoisyScore]) -> Vec<usize>
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R>,
+    {
+        expect_valid(self.validate());
+        let scores = self.scores(rng, scores);
+        self.inner.select(rng, amount, scores)
+    }
+
+    /// Choose multiple weighted pairs. See [MateSelection::pairs].
+    pub fn pairs<R>(&self, rng: &mut R, amount: usize, scores: &[NoisyScore]) -> Vec<[usize; 2]>
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R>,
+    {
+        expect_valid(self.validate());
+        let scores = self.scores(rng, scores);
+        self.inner.pairs(rng, amount, scores)
+    }
+}
+
+/// Box-Muller transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u = 1.0 - rng.random::<f64>();
+    let v = rng.random::<f64>();
+    (-2.0 * u.ln()).sqrt() * (core::f64::consts::TAU * v).cos()
+}
@@ -0,0 +1,208 @@
+//! Selection with scores of any ordered type
+
+use crate::{
+    cmp_score, reduce_repeats, sus, vec_to_pairs, Best, Elitist, Inverse, MateSelection,
+    Percentile, ProbabilisticTournament, Random, RankedExponential, RankedExponentialBase,
+    RankedGaussian, RankedGeometric, RankedLinear, TieBreaking, Tournament, UnbiasedTournament,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Selection methods which only depend on the order of the scores, and not on
+/// their values. These methods accept scores of any ordered type, such as
+/// integers, tuples, or custom keys, without converting them to floating point
+/// numbers.
+///
+/// The scores are replaced by their ranks, which are exactly representable
+/// and preserve the order and ties of the original scores.
+pub trait RankSelection<R: Rng + ?Sized = dyn Rng>: MateSelection<R> {
+    /// Choose multiple weighted pairs. See [MateSelection::pairs].
+    fn pairs_ord<T: Ord>(&self, rng: &mut R, amount: usize, scores: &[T]) -> Vec<[usize; 2]> {
+        self.pairs(rng, amount, ranks(scores))
+    }
+
+    /// Choose multiple weighted. See [MateSelection::select].
+    fn select_ord<T: Ord>(&self, rng: &mut R, amount: usize, scores: &[T]) -> Vec<usize> {
+        self.select(rng, amount, ranks(scores))
+    }
+
+    /// Probability distribution function. See [MateSelection::pdf].
+    fn pdf_ord<T: Ord>(&self, scores: &[T]) -> Vec<f64> {
+        self.pdf(ranks(scores))
+    }
+
+    /// Choose multiple weighted pairs from a population which is already
+    /// sorted. See [MateSelection::pairs] and [Ranking].
+    fn pairs_ranked(&self, rng: &mut R, amount: usize, ranking: &Ranking) -> Vec<[usize; 2]> {
+        let mut pairs = self.select_ranked(rng, amount * 2, ranking);
+        reduce_repeats(&mut pairs);
+        vec_to_pairs(pairs)
+    }
+
+    /// Choose multiple weighted from a population which is already sorted.
+    /// See [MateSelection::select] and [Ranking].
+    fn select_ranked(&self, rng: &mut R, amount: usize, ranking: &Ranking) -> Vec<usize> {
+        self.select(rng, amount, ranking.ranks())
+    }
+
+    /// Sampling weights of a population which is already sorted.
+    /// See [MateSelection::sample_weight] and [Ranking].
+    fn sample_weight_ranked(&self, ranking: &Ranking) -> Vec<f64> {
+        self.sample_weight(ranking.ranks())
+    }
+}
+
+/// Implement the ranked methods using their weights, without sorting again.
+macro_rules! rank_weights {
+    ($method:ty) => {
+        impl<R: Rng + ?Sized> RankSelection<R> for $method {
+            fn select_ranked(&self, rng: &mut R, amount: usize, ranking: &Ranking) -> Vec<usize> {
+                if amount == 0 {
+                    return vec![];
+                } else {
+                    assert!(!ranking.is_empty());
+                }
+                let weights = RankSelection::<R>::sample_weight_ranked(self, ranking);
+                sus::choose_multiple_weighted(rng, amount, &weights)
+            }
+            fn sample_weight_ranked(&self, ranking: &Ranking) -> Vec<f64> {
+                let mut weights = vec![0.0; ranking.len()];
+                self.rank_weights(ranking.order(), &mut weights);
+                weights
+            }
+        }
+    };
+}
+
+impl<R: Rng + ?Sized> RankSelection<R> for Random {}
+impl<R: Rng + ?Sized> RankSelection<R> for Best {}
+impl<R: Rng + ?Sized> RankSelection<R> for Percentile {}
+rank_weights!(RankedLinear);
+rank_weights!(RankedExponential);
+rank_weights!(RankedExponentialBase);
+rank_weights!(RankedGeometric);
+rank_weights!(RankedGaussian);
+impl<R: Rng + ?Sized> RankSelection<R> for Tournament {}
+impl<R: Rng + ?Sized> RankSelection<R> for ProbabilisticTournament {}
+impl<R: Rng + ?Sized> RankSelection<R> for UnbiasedTournament {}
+impl<R: Rng + ?Sized, M: RankSelection<R>> RankSelection<R> for Inverse<M> {}
+impl<R: Rng + ?Sized, M: RankSelection<R>> RankSelection<R> for Elitist<M> {}
+impl<R: Rng + ?Sized, M: RankSelection<R>> RankSelection<R> for TieBreaking<M> {}
+
+/// The sorted order of a population's scores, which is updated incrementally
+/// as the scores change. See [RankSelection::select_ranked].
+///
+/// In steady-state evolution only a few individuals are replaced at each step.
+/// Updating the order of `n` individuals after `k` of them change takes
+/// `O(n + k log k)` time, instead of sorting the whole population again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Ranking {
+    scores: Vec<f64>,
+    /// Indices into the scores, sorted from the worst score to the best, with
+    /// invalid (NaN) scores first.
+    order: Vec<usize>,
+}
+
+impl Ranking {
+    /// Sort the scores.
+    pub fn new(scores: Vec<f64>) -> Self {
+        let mut order: Vec<usize> = (0..scores.len()).collect();
+        order.sort_unstable_by(|&a, &b| cmp_score(scores[a], scores[b]));
+        Self { scores, order }
+    }
+
+    pub fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+
+    /// Indices into the scores, sorted from the worst score to the best, with
+    /// invalid (NaN) scores first.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Number of individuals in the population.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Change the scores of some individuals and restore the sorted order.
+    ///
+    /// Argument "changes" is a list of `(index, score)` entries. If an index
+    /// is repeated, then its last score is kept.
+    pub fn update(&mut self, changes: &[(usize, f64)]) {
+        let mut changed = vec![false; self.scores.len()];
+        for &(index, score) in changes {
+            assert!(
+                index < self.scores.len(),
+                "index out of bounds: the population is {} but the index is {index}",
+                self.scores.len()
+            );
+            self.scores[index] = score;
+            changed[index] = true;
+        }
+        // Remove the changed individuals from the order, and sort them separately.
+        let scores = &self.scores;
+        let mut moved: Vec<usize> = changes.iter().map(|&(index, _)| index).collect();
+        moved.sort_unstable();
+        moved.dedup();
+        moved.sort_unstable_by(|&a, &b| cmp_score(scores[a], scores[b]));
+        self.order.retain(|&index| !changed[index]);
+        // Merge the two sorted lists.
+        let mut merged = Vec::with_capacity(scores.len());
+        let mut rest = self.order.iter().peekable();
+        for index in moved {
+            while let Some(&&other) = rest.peek() {
+                if cmp_score(scores[other], scores[index]).is_gt() {
+                    break;
+                }
+                merged.push(other);
+                rest.next();
+            }
+            merged.push(index);
+        }
+        merged.extend(rest);
+        self.order = merged;
+    }
+
+    /// Replace each score with its rank, counting up from zero for the worst
+    /// score. Equal scores have the same rank. Invalid (NaN) scores remain
+    /// invalid.
+    pub fn ranks(&self) -> Vec<f64> {
+        let mut ranks = vec![f64::NAN; self.scores.len()];
+        let valid = self
+            .order
+            .partition_point(|&index| self.scores[index].is_nan());
+        let order = &self.order[valid..];
+        let mut rank = 0;
+        for (position, &index) in order.iter().enumerate() {
+            if position > 0 && self.scores[order[position - 1]] != self.scores[index] {
+                rank += 1;
+            }
+            ranks[index] = rank as f64;
+        }
+        ranks
+    }
+}
+
+/// Replace each score with its rank, counting up from zero for the worst
+/// score. Equal scores have the same rank.
+fn ranks<T: Ord>(scores: &[T]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_unstable_by(|&a, &b| scores[a].cmp(&scores[b]));
+    let mut ranks = vec![0.0; scores.len()];
+    let mut rank = 0;
+    for (position, &index) in order.iter().enumerate() {
+        if position > 0 && scores[order[position - 1]] != scores[index] {
+            rank += 1;
+        }
+        ranks[index] = rank as f64;
+    }
+    ranks
+}
@@ -0,0 +1,321 @@
+//! Policies for deciding which of the selected parents mate together
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{cmp_score, reduce_repeats, reduce_repeats_local, vec_to_pairs, MateSelection};
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+/// Choosing mates is a pipeline of two stages: first the selection method
+/// chooses the parents, and then the pairing strategy assigns them partners.
+/// Any pairing strategy can be combined with any selection method.
+///
+/// See [MateSelection::pairs_with](crate::MateSelection::pairs_with).
+pub trait Pair<R: Rng + ?Sized = dyn Rng>: core::fmt::Debug {
+    /// Choose multiple pairs of parents.
+    ///
+    /// * Argument `amount` is the number of pairs to return.
+    ///
+    /// * Argument "selection" is the method for choosing the parents.
+    ///
+    /// * Argument "scores" is a list containing the reproductive fitness of each individual.
+    fn pair(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        selection: &dyn MateSelection<R>,
+        scores: Vec<f64>,
+    ) -> Vec<[usize; 2]>;
+}
+
+/// Pair the selected parents together at random, avoiding mating an
+/// individual with itself. This is the default behavior of
+/// [MateSelection::pairs](crate::MateSelection::pairs).
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+pub struct RandomPairing;
+
+impl<R: Rng + ?Sized> Pair<R> for RandomPairing {
+    fn pair(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        selection: &dyn MateSelection<R>,
+        scores: Vec<f64>,
+    ) -> Vec<[usize; 2]> {
+        selection.pairs(rng, amount, scores)
+    }
+}
+
+/// Pair dissimilar parents together, as measured by a distance function.
+///
+/// Argument "**distance**" is a callback which measures the dissimilarity
+/// between two individuals, specified as indices into the scores list.
+/// The selected parents are matched greedily so as to maximize the distance
+/// between mates.
+///
+/// See also [MateSelection::pairs_dissimilar](crate::MateSelection::pairs_dissimilar).
+#[derive(Copy, Clone)]
+pub struct DistancePairing<F>(pub F);
+
+impl<F> core::fmt::Debug for DistancePairing<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DistancePairing").finish_non_exhaustive()
+    }
+}
+
+impl<R, F> Pair<R> for DistancePairing<F>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize, usize) -> f64,
+{
+    fn pair(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        selection: &dyn MateSelection<R>,
+        scores: Vec<f64>,
+    ) -> Vec<[usize; 2]> {
+        let parents = selection.select(rng, amount * 2, scores);
+        pair_dissimilar(rng, parents, &self.0)
+    }
+}
+
+/// After the parents are selected, the pairing policy decides which parents
+/// mate together.
+///
+/// See [MateSelection::pairs_with](crate::MateSelection::pairs_with).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum PairingPolicy {
+    /// Pair the parents together at random. This is the default behavior of
+    /// [MateSelection::pairs](crate::MateSelection::pairs).
+    #[default]
+    Random,
+
+    /// Positive assortative mating pairs parents which are similar to each
+    /// other.
+    ///
+    /// Argument "**strength**" is the fraction of the pairs which are formed
+    /// assortatively, the remaining pairs are formed at random. Must be in the
+    /// range [0, 1].
+    ///
+    /// Argument "**traits**" is the value which parents are compared by. If
+    /// missing then the parents are compared by their scores.
+    Assortative {
+        strength: f64,
+        traits: Option<Vec<f64>>,
+    },
+
+    /// Negative assortative mating pairs parents which are dissimilar to each
+    /// other. The parents are sorted by their traits and the lower half is
+    /// matched with the upper half, which maximizes the total difference
+    /// between mates.
+    ///
+    /// Argument "**traits**" is the value which parents are compared by. If
+    /// missing then the parents are compared by their scores.
+    ///
+    /// See also [MateSelection::pairs_dissimilar](crate::MateSelection::pairs_dissimilar)
+    /// for comparing parents with a distance function.
+    Disassortative { traits: Option<Vec<f64>> },
+
+    /// Every individual mates at most once. The parents are selected without
+    /// replacement, so every pair is disjoint from every other pair.
+    ///
+    /// If there are not enough eligible individuals (with a sampling weight
+    /// greater than zero) then the eligible individuals are used as evenly as
+    /// possible: everyone mates once before anyone mates twice.
+    Monogamous,
+
+    /// Every pair is unique, the same two individuals never mate together more
+    /// than once. Duplicate pairs are discarded and redrawn.
+    ///
+    /// If there are too few distinct pairs to choose from, then this gives up
+    /// after several consecutive attempts fail to find a new pair, and returns
+    /// fewer pairs than were requested.
+    Unique,
+
+    /// Every eligible individual (with a sampling weight greater than zero)
+    /// mates at least once, and then the remaining parents are selected as
+    /// usual. If there are too few pairs for every eligible individual to mate
+    /// then the parents are selected as usual.
+    Coverage,
+}
+
+impl<R: Rng + ?Sized> Pair<R> for PairingPolicy {
+    fn pair(
+        &self,
+        rng: &mut R,
+        amount: usize,
+        selection: &dyn MateSelection<R>,
+        scores: Vec<f64>,
+    ) -> Vec<[usize; 2]> {
+        match self {
+            Self::Random => RandomPairing.pair(rng, amount, selection, scores),
+            Self::Assortative { strength, traits } => {
+                assert!(
+                    (0.0..=1.0).contains(strength),
+                    "argument \"strength\" is out of bounds [0, 1]"
+                );
+                let traits = traits.as_deref().unwrap_or(&scores);
+                assert_eq!(
+                    traits.len(),
+                    scores.len(),
+                    "argument \"traits\" has the wrong length"
+                );
+                let mut parents = selection.select(rng, amount * 2, scores.clone());
+                // Choose a random subset of the parents to pair assortatively.
+                parents.shuffle(rng);
+                let num_pairs = parents.len() / 2;
+                let num_assortative = (strength * num_pairs as f64).round() as usize;
+                let (assortative, random) = parents.split_at_mut(2 * num_assortative);
+                // Sort the parents by their traits and then pair neighbors
+                // together. Break up repeated pairs by swapping with the
+                // nearest neighboring pair.
+                assortative.sort_by(|&a, &b| cmp_score(traits[a], traits[b]));
+                reduce_repeats_local(assortative);
+                reduce_repeats(random);
+                let mut pairs = vec_to_pairs(parents);
+                pairs.shuffle(rng);
+                pairs
+            }
+            Self::Disassortative { traits } => {
+                let traits = traits.as_deref().unwrap_or(&scores);
+                assert_eq!(
+                    traits.len(),
+                    scores.len(),
+                    "argument \"traits\" has the wrong length"
+                );
+                let mut parents = selection.select(rng, amount * 2, scores.clone());
+                // Shuffle first so that ties are broken randomly.
+                parents.shuffle(rng);
+                parents.sort_by(|&a, &b| cmp_score(traits[a], traits[b]));
+                // Interleave the lower and upper halves, so that each parent
+                // in the lower half is paired with its counterpart in the upper half.
+                let half = parents.len() / 2;
+                let mut data = Vec::with_capacity(parents.len());
+                for (&low, &high) in parents[..half].iter().zip(&parents[half..]) {
+                    data.push(low);
+                    data.push(high);
+                }
+                reduce_repeats_local(&mut data);
+                let mut pairs = vec_to_pairs(data);
+                pairs.shuffle(rng);
+                pairs
+            }
+            Self::Monogamous => {
+                let weights = selection.sample_weight(scores);
+                pair_monogamous(rng, amount, &weights)
+            }
+            Self::Unique => pair_unique(rng, amount, |rng, amount| {
+                selection.pairs(rng, amount, scores.clone())
+            }),
+            Self::Coverage => {
+                let weights = selection.sample_weight(scores.clone());
+                let mut parents: Vec<usize> =
+                    (0..weights.len()).filter(|&i| weights[i] > 0.0).collect();
+                if parents.is_empty() {
+                    parents.extend(0..weights.len());
+                }
+                if parents.len() > 2 * amount {
+                    parents.clear();
+                }
+                let remainder = 2 * amount - parents.len();
+                parents.append(&mut selection.select(rng, remainder, scores));
+                parents.shuffle(rng);
+                reduce_repeats(&mut parents);
+                vec_to_pairs(parents)
+            }
+        }
+    }
+}
+
+/// Select pairs of parents without replacement, as evenly as possible.
+fn pair_monogamous<R: Rng + ?Sized>(
+    rng: &mut R,
+    amount: usize,
+    weights: &[f64],
+) -> Vec<[usize; 2]> {
+    let mut eligible: Vec<usize> = (0..weights.len()).filter(|&i| weights[i] > 0.0).collect();
+    let uniform = eligible.is_empty();
+    if uniform {
+        eligible.extend(0..weights.len());
+    }
+    assert!(
+        amount == 0 || !eligible.is_empty(),
+        "no data: can not choose from empty set"
+    );
+    let mut parents = Vec::with_capacity(2 * amount);
+    while parents.len() < 2 * amount {
+        // Weighted random sampling without replacement, as described by
+        // Efraimidis & Spirakis (2006). Sort by "ln(u) / weight", descending.
+        let mut keys: Vec<(f64, usize)> = eligible
+            .iter()
+            .map(|&i| {
+                let weight = if uniform { 1.0 } else { weights[i] };
+                (rng.random::<f64>().ln() / weight, i)
+            })
+            .collect();
+        keys.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+        let num = keys.len().min(2 * amount - parents.len());
+        parents.extend(keys[..num].iter().map(|&(_, i)| i));
+    }
+    reduce_repeats(&mut parents);
+    vec_to_pairs(parents)
+}
+
+/// Draw pairs until there are enough distinct pairs, or until giving up.
+///
+/// Argument "draw" is a callback which returns the given number of new pairs.
+fn pair_unique<R, D>(rng: &mut R, amount: usize, mut draw: D) -> Vec<[usize; 2]>
+where
+    R: Rng + ?Sized,
+    D: FnMut(&mut R, usize) -> Vec<[usize; 2]>,
+{
+    const MAX_ATTEMPTS: usize = 10;
+    let mut pairs = Vec::with_capacity(amount);
+    let mut seen = alloc::collections::BTreeSet::new();
+    // Give up after several consecutive attempts which find no new pairs.
+    let mut attempts = 0;
+    while pairs.len() < amount && attempts < MAX_ATTEMPTS {
+        attempts += 1;
+        for [a, b] in draw(rng, amount) {
+            if pairs.len() < amount && seen.insert([a.min(b), a.max(b)]) {
+                pairs.push([a, b]);
+                attempts = 0;
+            }
+        }
+    }
+    pairs
+}
+
+/// Greedy maximal-dissimilarity matching.
+///
+/// Repeatedly choose a random unpaired parent and pair it with the most
+/// distant unpaired parent. This takes quadratic time.
+pub(crate) fn pair_dissimilar<R: Rng + ?Sized>(
+    rng: &mut R,
+    mut parents: Vec<usize>,
+    distance: &dyn Fn(usize, usize) -> f64,
+) -> Vec<[usize; 2]> {
+    parents.shuffle(rng);
+    let mut pairs = Vec::with_capacity(parents.len() / 2);
+    while let Some(parent) = parents.pop() {
+        let Some((position, _)) = parents
+            .iter()
+            .map(|&mate| {
+                if mate == parent {
+                    f64::NEG_INFINITY
+                } else {
+                    distance(parent, mate)
+                }
+            })
+            .enumerate()
+            .max_by(|(_, a), (_, b)| cmp_score(*a, *b))
+        else {
+            break;
+        };
+        pairs.push([parent, parents.swap_remove(position)]);
+    }
+    pairs
+}
@@ -0,0 +1,90 @@
+//! Multi-threaded sorting and batch processing
+
+use core::cmp::Ordering;
+
+/// Populations smaller than this are sorted on a single thread.
+const MIN_PARALLEL: usize = 1 << 16;
+
+/// Stable sort, which uses all of the available threads for large inputs.
+///
+/// The data is split into one chunk per thread, the chunks are sorted
+/// concurrently, and then the sorted chunks are merged together pairwise.
+pub(crate) fn sort_by<T, F>(data: &mut [T], compare: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if data.len() < MIN_PARALLEL || threads < 2 {
+        data.sort_by(compare);
+        return;
+    }
+    let compare = &compare;
+    let chunk_size = data.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        for chunk in data.chunks_mut(chunk_size) {
+            scope.spawn(move || chunk.sort_by(compare));
+        }
+    });
+    let mut buffer = data.to_vec();
+    let mut width = chunk_size;
+    while width < data.len() {
+        std::thread::scope(|scope| {
+            for (src, dst) in data.chunks(2 * width).zip(buffer.chunks_mut(2 * width)) {
+                scope.spawn(move || merge(src, width, dst, compare));
+            }
+        });
+        data.copy_from_slice(&buffer);
+        width *= 2;
+    }
+}
+
+/// Merge the two sorted halves of the source, split at the midpoint, into the
+/// destination. Ties are taken from the left half first, to keep it stable.
+fn merge<T, F>(src: &[T], mid: usize, dst: &mut [T], compare: &F)
+where
+    T: Copy,
+    F: Fn(&T, &T) -> Ordering,
+{
+    let (left, right) = src.split_at(mid.min(src.len()));
+    let (mut i, mut j) = (0, 0);
+    for out in dst.iter_mut() {
+        if j == right.len() || (i < left.len() && compare(&right[j], &left[i]).is_ge()) {
+            *out = left[i];
+            i += 1;
+        } else {
+            *out = right[j];
+            j += 1;
+        }
+    }
+}
+
+/// Apply the function to every item, using all of the available threads. The
+/// results are in the same order as the items.
+pub(crate) fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if items.len() < 2 || threads < 2 {
+        return items.iter().map(f).collect();
+    }
+    let f = &f;
+    let chunk_size = items.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<U>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,163 @@
+//! Family tree of every individual, for answering ancestry queries
+
+use crate::Kinship;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// Records the parents of every individual, across all generations.
+///
+/// Every individual is given a stable ID, which is never reused. The founders
+/// of the first generation have IDs `0..population`, and the offspring are
+/// numbered in the order in which they are recorded, so the parents always
+/// have smaller IDs than their children.
+///
+/// The current population is the most recently recorded generation, and the
+/// pairs which are selected from it are indices into it, as returned by
+/// [MateSelection::pairs](crate::MateSelection::pairs).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Pedigree {
+    /// Parents of every individual, indexed by ID, or None for founders.
+    parents: Vec<Option<[usize; 2]>>,
+    /// IDs of the current population.
+    current: Vec<usize>,
+}
+
+impl Pedigree {
+    /// Start with a population of unrelated founders.
+    pub fn new(population: usize) -> Self {
+        Self {
+            parents: vec![None; population],
+            current: (0..population).collect(),
+        }
+    }
+
+    /// Total number of individuals, in all generations.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// IDs of the current population, in the same order as its scores.
+    pub fn current(&self) -> &[usize] {
+        &self.current
+    }
+
+    /// Record the next generation. Each pair of parents has one child, and the
+    /// children become the current population in the same order as the pairs.
+    ///
+    /// Returns the IDs of the children.
+    pub fn record(&mut self, pairs: &[[usize; 2]]) -> &[usize] {
+        let population = self.current.len();
+        let mut next = Vec::with_capacity(pairs.len());
+        for &[a, b] in pairs {
+            assert!(
+                a < population && b < population,
+                "index out of bounds: parents [{a}, {b}] of population {population}"
+            );
+            next.push(self.parents.len());
+            self.parents.push(Some([self.current[a], self.current[b]]));
+        }
+        self.current = next;
+        &self.current
+    }
+
+    /// The IDs of the parents of an individual, or None if it is a founder.
+    pub fn parents(&self, id: usize) -> Option<[usize; 2]> {
+        self.parents[id]
+    }
+
+    /// IDs of every ancestor of an individual, sorted.
+    pub fn ancestors(&self, id: usize) -> Vec<usize> {
+        let mut found = vec![];
+        let mut frontier = vec![id];
+        while let Some(child) = frontier.pop() {
+            if let Some(parents) = self.parents[child] {
+                for parent in parents {
+                    if !found.contains(&parent) {
+                        found.push(parent);
+                        frontier.push(parent);
+                    }
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// IDs of the ancestors which two individuals have in common, sorted.
+    pub fn common_ancestors(&self, a: usize, b: usize) -> Vec<usize> {
+        let b = self.ancestors(b);
+        self.ancestors(a)
+            .into_iter()
+            .filter(|x| b.binary_search(x).is_ok())
+            .collect()
+    }
+
+    /// Coefficient of coancestry, which is the probability that two alleles,
+    /// taken at random one from each individual, are identical by descent.
+    /// The founders are assumed to be unrelated and not inbred.
+    pub fn coancestry(&self, a: usize, b: usize) -> f64 {
+        self.coancestry_memo(a, b, &mut BTreeMap::new())
+    }
+
+    /// Coefficient of inbreeding, which is the coancestry of the parents.
+    pub fn inbreeding(&self, id: usize) -> f64 {
+        self.inbreeding_memo(id, &mut BTreeMap::new())
+    }
+
+    fn inbreeding_memo(&self, id: usize, memo: &mut BTreeMap<(usize, usize), f64>) -> f64 {
+        match self.parents[id] {
+            Some([a, b]) => self.coancestry_memo(a, b, memo),
+            None => 0.0,
+        }
+    }
+
+    fn coancestry_memo(&self, a: usize, b: usize, memo: &mut BTreeMap<(usize, usize), f64>) -> f64 {
+        // Recurse through the parents of the younger individual, which can
+        // not be an ancestor of the older one.
+        let (young, old) = (a.max(b), a.min(b));
+        if let Some(&value) = memo.get(&(young, old)) {
+            return value;
+        }
+        let value = if young == old {
+            0.5 * (1.0 + self.inbreeding_memo(young, memo))
+        } else {
+            match self.parents[young] {
+                Some([p, q]) => {
+                    0.5 * (self.coancestry_memo(p, old, memo) + self.coancestry_memo(q, old, memo))
+                }
+                None => 0.0,
+            }
+        };
+        memo.insert((young, old), value);
+        value
+    }
+
+    /// Relationships between the individuals of the current population, for
+    /// preventing inbreeding. See [MateSelection::pairs_unrelated](crate::MateSelection::pairs_unrelated).
+    ///
+    /// The coefficient of relationship is twice the coancestry, so it is one
+    /// half for siblings and one eighth for first cousins.
+    ///
+    /// Argument "**threshold**" is the largest coefficient of relationship
+    /// which is permitted to mate.
+    pub fn kinship(&self, threshold: f64) -> Kinship {
+        let memo = &mut BTreeMap::new();
+        let matrix = self
+            .current
+            .iter()
+            .map(|&a| {
+                self.current
+                    .iter()
+                    .map(|&b| 2.0 * self.coancestry_memo(a, b, memo))
+                    .collect()
+            })
+            .collect();
+        Kinship::Matrix { matrix, threshold }
+    }
+}
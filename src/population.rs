@@ -0,0 +1,190 @@
+//! Scores and metadata of a population, with cached rankings and weights
+
+use crate::{argsort_best, cmp_score, reduce_repeats, sus, vec_to_pairs, MateSelection};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Per-individual information which is not part of its score.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// Number of generations which the individual has survived.
+    pub age: usize,
+    /// Caller defined species or niche.
+    pub species: usize,
+    pub sex: Sex,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Sex {
+    #[default]
+    Unspecified,
+    Female,
+    Male,
+}
+
+/// Scores and metadata of a population, which caches the data derived from
+/// the scores so that it does not need to be recomputed for every selection.
+///
+/// This is useful for steady-state genetic algorithms, which replace a few
+/// individuals at a time and then choose parents from mostly the same scores.
+/// The ranking is updated incrementally when individuals are replaced, and the
+/// sampling weights are only recomputed when the scores change, or when the
+/// cache is invalidated with [Population::invalidate_weights].
+///
+/// The parents are chosen from the cached sampling weights with stochastic
+/// universal sampling, as by the default [MateSelection::select]. This does
+/// not respect any restrictions on which individuals can mate together, such
+/// as [Islands](crate::Islands) or [Cellular](crate::Cellular), nor the
+/// guarantees of [Elitist](crate::Elitist) and [Capped](crate::Capped).
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Population {
+    scores: Vec<f64>,
+    metadata: Vec<Metadata>,
+    /// Indices from best to worst, with invalid (NaN) scores last.
+    #[serde(skip)]
+    ranking: Option<Vec<usize>>,
+    /// Sampling weights.
+    #[serde(skip)]
+    weights: Option<Vec<f64>>,
+}
+
+impl Population {
+    /// All individuals start with the default metadata.
+    pub fn new(scores: Vec<f64>) -> Self {
+        let metadata = vec![Metadata::default(); scores.len()];
+        Self::with_metadata(scores, metadata)
+    }
+
+    pub fn with_metadata(scores: Vec<f64>, metadata: Vec<Metadata>) -> Self {
+        assert_eq!(
+            scores.len(),
+            metadata.len(),
+            "scores and metadata have different lengths"
+        );
+        Self {
+            scores,
+            metadata,
+            ranking: None,
+            weights: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+
+    pub fn metadata(&self) -> &[Metadata] {
+        &self.metadata
+    }
+
+    /// Modifying the metadata does not invalidate any of the cached data.
+    pub fn metadata_mut(&mut self) -> &mut [Metadata] {
+        &mut self.metadata
+    }
+
+    /// Indices of the individuals, sorted from best to worst, with invalid
+    /// (NaN) scores last.
+    pub fn ranking(&mut self) -> &[usize] {
+        self.ranking
+            .get_or_insert_with(|| argsort_best(&self.scores))
+    }
+
+    /// Sampling weights of the individuals, see [MateSelection::sample_weight].
+    /// The weights are cached until the scores change. The cache does not know
+    /// which selection method computed the weights, so call
+    /// [Population::invalidate_weights] after changing the selection method or
+    /// any of its arguments.
+    pub fn weights<R, M>(&mut self, method: &M) -> &[f64]
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        self.weights
+            .get_or_insert_with(|| method.sample_weight_slice(&self.scores))
+    }
+
+    /// Discard the cached sampling weights, so that they are recomputed the
+    /// next time they are needed.
+    pub fn invalidate_weights(&mut self) {
+        self.weights = None;
+    }
+
+    /// Replace an individual with a new one, and update the ranking.
+    pub fn replace(&mut self, index: usize, score: f64, metadata: Metadata) {
+        self.unrank(index);
+        self.scores[index] = score;
+        self.metadata[index] = metadata;
+        self.rank(index);
+        self.invalidate_weights();
+    }
+
+    /// Add a new individual to the end of the population.
+    pub fn push(&mut self, score: f64, metadata: Metadata) {
+        self.scores.push(score);
+        self.metadata.push(metadata);
+        self.rank(self.scores.len() - 1);
+        self.invalidate_weights();
+    }
+
+    /// Increment the age of every individual.
+    pub fn increment_ages(&mut self) {
+        for metadata in &mut self.metadata {
+            metadata.age += 1;
+        }
+    }
+
+    /// Remove an individual from the cached ranking.
+    fn unrank(&mut self, index: usize) {
+        if let Some(ranking) = &mut self.ranking {
+            let position = ranking.iter().position(|&i| i == index).unwrap();
+            ranking.remove(position);
+        }
+    }
+
+    /// Insert an individual into the cached ranking, after any equal scores.
+    fn rank(&mut self, index: usize) {
+        if let Some(ranking) = &mut self.ranking {
+            let score = self.scores[index];
+            let position = ranking.partition_point(|&i| cmp_score(self.scores[i], score).is_ge());
+            ranking.insert(position, index);
+        }
+    }
+
+    /// Choose multiple weighted individuals, from the cached sampling weights.
+    /// See [MateSelection::select].
+    pub fn select<R, M>(&mut self, method: &M, rng: &mut R, amount: usize) -> Vec<usize>
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!self.is_empty());
+        }
+        let weights = self.weights(method);
+        sus::choose_multiple_weighted(rng, amount, weights)
+    }
+
+    /// Choose multiple weighted pairs, from the cached sampling weights.
+    /// See [MateSelection::pairs].
+    pub fn pairs<R, M>(&mut self, method: &M, rng: &mut R, amount: usize) -> Vec<[usize; 2]>
+    where
+        R: Rng + ?Sized,
+        M: MateSelection<R> + ?Sized,
+    {
+        let mut parents = self.select(method, rng, amount * 2);
+        reduce_repeats(&mut parents);
+        vec_to_pairs(parents)
+    }
+}
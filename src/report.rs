@@ -0,0 +1,103 @@
+//! Diagnostics for the scores, before selecting from them
+
+use crate::{MateSelection, SelectionError};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+
+/// Summary of a list of scores, and of the sampling weights which a selection
+/// method assigns to them. See [MateSelection::validate_scores].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreReport {
+    /// Number of scores.
+    pub population: usize,
+    /// Number of scores which are less than zero.
+    pub negative: usize,
+    /// Number of scores which are infinite or NaN.
+    pub non_finite: usize,
+    /// Number of scores which are equal to an earlier score.
+    pub duplicates: usize,
+    /// Number of individuals with a positive sampling weight.
+    pub eligible: usize,
+    /// Problems which would make the selection fail or be degenerate.
+    pub warnings: Vec<ScoreWarning>,
+}
+
+/// A problem with the scores, see [ScoreReport].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScoreWarning {
+    /// All of the scores are equal, so there is no selection pressure.
+    AllEqual,
+
+    /// None of the individuals have a positive sampling weight, so every
+    /// individual is equally likely to be selected.
+    NoneEligible,
+
+    /// Selection would fail with this error.
+    Error(SelectionError),
+}
+
+impl ScoreReport {
+    /// Check if selection would fail.
+    pub fn is_err(&self) -> bool {
+        self.warnings
+            .iter()
+            .any(|w| matches!(w, ScoreWarning::Error(_)))
+    }
+}
+
+impl core::fmt::Display for ScoreWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::AllEqual => write!(f, "all of the scores are equal"),
+            Self::NoneEligible => write!(f, "none of the individuals are eligible to mate"),
+            Self::Error(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+pub(crate) fn score_report<R, M>(method: &M, scores: &[f64]) -> ScoreReport
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R> + ?Sized,
+{
+    let mut sorted = scores.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    let distinct = sorted.chunk_by(|a, b| a.total_cmp(b).is_eq()).count();
+    let mut report = ScoreReport {
+        population: scores.len(),
+        negative: scores.iter().filter(|&&x| x < 0.0).count(),
+        non_finite: scores.iter().filter(|x| !x.is_finite()).count(),
+        duplicates: scores.len() - distinct,
+        eligible: 0,
+        warnings: vec![],
+    };
+    if let Err(error) = method.validate() {
+        report.warnings.push(ScoreWarning::Error(error));
+        return report;
+    }
+    if scores.is_empty() {
+        report
+            .warnings
+            .push(ScoreWarning::Error(SelectionError::EmptyPopulation));
+        return report;
+    }
+    if distinct == 1 && scores.len() > 1 {
+        report.warnings.push(ScoreWarning::AllEqual);
+    }
+    let weights = method.sample_weight_slice(scores);
+    if let Some(index) = weights.iter().position(|w| !(w.is_finite() && *w >= 0.0)) {
+        report
+            .warnings
+            .push(ScoreWarning::Error(SelectionError::InvalidWeight {
+                index,
+                weight: weights[index],
+            }));
+        return report;
+    }
+    report.eligible = weights.iter().filter(|&&w| w > 0.0).count();
+    if report.eligible == 0 {
+        report.warnings.push(ScoreWarning::NoneEligible);
+    }
+    report
+}
@@ -0,0 +1,118 @@
+//! Precompiled sampler for choosing many small batches from the same scores
+
+use crate::{reduce_repeats, vec_to_pairs};
+use alloc::vec::Vec;
+use rand::distr::Distribution;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+/// Sampling weights which are compiled into an alias table, so that each
+/// individual can be chosen in constant time. See [MateSelection::compile](crate::MateSelection::compile).
+///
+/// Building the table takes time proportional to the size of the population,
+/// and then it can be sampled from repeatedly. This is useful for steady-state
+/// genetic algorithms, which choose many small batches of parents from the
+/// same scores.
+///
+/// Each individual is chosen independently, using Vose's alias method. The
+/// probability distribution is the same as that of the selection method.
+///
+/// This implements rand's [Distribution] trait, so single parents can also be
+/// drawn with `rng.sample(&sampler)` or `sampler.sample_iter(rng)`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Sampler {
+    /// Probability of keeping each column of the table, instead of taking its alias.
+    keep: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl Sampler {
+    /// Argument "weights" is the sampling weight of each individual, see
+    /// [MateSelection::sample_weight](crate::MateSelection::sample_weight).
+    /// All weights must be finite and greater than or equal to zero. If all of
+    /// the weights are zero then each individual is equally likely to be
+    /// selected.
+    pub fn new(weights: Vec<f64>) -> Self {
+        let mut max_weight: f64 = 0.0;
+        for (i, &w) in weights.iter().enumerate() {
+            assert!(
+                w.is_finite() && w >= 0.0,
+                "invalid weight: weights[{i}] = {w} (must be finite and >= 0)"
+            );
+            max_weight = max_weight.max(w);
+        }
+        let num = weights.len();
+        let mut keep = weights;
+        if max_weight == 0.0 {
+            keep.fill(1.0);
+        } else {
+            // Rescale so the largest weight is one, to avoid overflowing the sum.
+            for x in keep.iter_mut() {
+                *x /= max_weight;
+            }
+        }
+        // Scale the weights so that their average is one.
+        let sum: f64 = keep.iter().sum();
+        for x in keep.iter_mut() {
+            *x *= num as f64 / sum;
+        }
+        let mut alias: Vec<usize> = (0..num).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..num).partition(|&i| keep[i] < 1.0);
+        while !small.is_empty() && !large.is_empty() {
+            let s = small.pop().unwrap();
+            let l = large[large.len() - 1];
+            // Fill the rest of the small column with the large individual.
+            alias[s] = l;
+            keep[l] -= 1.0 - keep[s];
+            if keep[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Any remaining columns are full, up to rounding errors.
+        for i in small.into_iter().chain(large) {
+            keep[i] = 1.0;
+        }
+        Self { keep, alias }
+    }
+
+    /// Number of individuals in the population.
+    pub fn len(&self) -> usize {
+        self.keep.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keep.is_empty()
+    }
+
+    /// Choose a single weighted individual.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        assert!(!self.is_empty(), "no data: can not choose from empty set");
+        let column = rng.random_range(0..self.keep.len());
+        if rng.random::<f64>() < self.keep[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+
+    /// Choose multiple weighted individuals, with repetition.
+    pub fn select<R: Rng + ?Sized>(&self, rng: &mut R, amount: usize) -> Vec<usize> {
+        (0..amount).map(|_| self.sample(rng)).collect()
+    }
+
+    /// Choose multiple weighted pairs. Individuals are not mated with
+    /// themselves, unless there are too few eligible individuals.
+    pub fn pairs<R: Rng + ?Sized>(&self, rng: &mut R, amount: usize) -> Vec<[usize; 2]> {
+        let mut parents = self.select(rng, amount * 2);
+        reduce_repeats(&mut parents);
+        vec_to_pairs(parents)
+    }
+}
+
+impl Distribution<usize> for Sampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        Sampler::sample(self, rng)
+    }
+}
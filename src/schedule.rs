@@ -0,0 +1,96 @@
+//! Parameters which change with the generation number
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::Pressure;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// A value which depends on the generation number, which starts at zero.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Schedule {
+    /// The same value in every generation.
+    Constant(f64),
+
+    /// Change linearly from the start value to the end value over the given
+    /// number of generations, and then stay at the end value.
+    Linear {
+        start: f64,
+        end: f64,
+        generations: usize,
+    },
+
+    /// Multiply the start value by the rate in every generation.
+    Exponential { start: f64, rate: f64 },
+
+    /// Piecewise constant, as a list of `(generation, value)` entries sorted
+    /// by generation. Each value takes effect at its generation and lasts
+    /// until the next entry. Before the first entry, the first value is used.
+    Step(Vec<(usize, f64)>),
+
+    /// Custom function of the generation number. This can not be serialized.
+    #[serde(skip)]
+    Custom(fn(usize) -> f64),
+}
+
+impl Schedule {
+    /// The value at the given generation.
+    pub fn value(&self, generation: usize) -> f64 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Linear {
+                start,
+                end,
+                generations,
+            } => {
+                if generation >= *generations {
+                    *end
+                } else {
+                    let t = generation as f64 / *generations as f64;
+                    start + (end - start) * t
+                }
+            }
+            Self::Exponential { start, rate } => start * rate.powf(generation as f64),
+            Self::Step(steps) => {
+                assert!(!steps.is_empty(), "step schedule is empty");
+                steps
+                    .iter()
+                    .take_while(|(start, _)| *start <= generation)
+                    .last()
+                    .unwrap_or(&steps[0])
+                    .1
+            }
+            Self::Custom(function) => function(generation),
+        }
+    }
+}
+
+/// Selection method whose pressure parameter follows a schedule, see
+/// [Pressure]. Call [Scheduled::at] with the generation number to get the
+/// selection method for that generation. Values outside of the parameter's
+/// bounds are clamped to the bounds.
+///
+/// Argument "**inner**" is the method for selecting parents. Its pressure
+/// parameter is replaced by the schedule.
+///
+/// Argument "**schedule**" is the value of the pressure parameter in each
+/// generation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Scheduled<M> {
+    pub inner: M,
+    pub schedule: Schedule,
+}
+
+impl<M: Pressure + Clone> Scheduled<M> {
+    pub fn new(inner: M, schedule: Schedule) -> Self {
+        Self { inner, schedule }
+    }
+
+    /// The selection method for the given generation.
+    pub fn at(&self, generation: usize) -> M {
+        let mut method = self.inner.clone();
+        let (min, max) = method.pressure_bounds();
+        method.set_pressure(self.schedule.value(generation).clamp(min, max));
+        method
+    }
+}
@@ -0,0 +1,212 @@
+//! Reproducible selection with a seeded random number generator
+
+use crate::MateSelection;
+use alloc::vec::Vec;
+use rand::rngs::{ChaCha12Rng, StdRng};
+use rand::{Rng, RngExt, SeedableRng};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Bundle a selection method with its own seeded random number generator, so
+/// that the results are reproducible. Two selectors with the same seed produce
+/// the same sequence of results when given the same sequence of calls.
+///
+/// Argument "**inner**" is the method for selecting parents.
+///
+/// Argument "**seed**" initializes the random number generator.
+#[derive(Debug)]
+pub struct Seeded<M> {
+    pub inner: M,
+    rng: StdRng,
+}
+
+impl<M: MateSelection<StdRng>> Seeded<M> {
+    pub fn new(inner: M, seed: u64) -> Self {
+        Self {
+            inner,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Restart the random number generator from the given seed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Choose multiple weighted pairs. See [MateSelection::pairs].
+    pub fn pairs(&mut self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        self.inner.pairs(&mut self.rng, amount, scores)
+    }
+
+    /// Choose multiple weighted. See [MateSelection::select].
+    pub fn select(&mut self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.inner.select(&mut self.rng, amount, scores)
+    }
+
+    /// Probability distribution function. See [MateSelection::pdf].
+    pub fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(scores)
+    }
+
+    /// Get the random number generator, for use with the other methods of the
+    /// selection method.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+/// Bundle a selection method with its own seeded random number generator,
+/// which can be saved and restored with serde. This allows a long run to be
+/// checkpointed and later resumed, producing the same sequence of results as
+/// if it had never stopped.
+///
+/// The random number generator is ChaCha with 12 rounds, the same algorithm
+/// as [StdRng], but unlike [StdRng] it is guaranteed to produce the same
+/// values in future versions of this library. Its state is saved as the seed,
+/// the stream, and the position within the stream.
+///
+/// Argument "**inner**" is the method for selecting parents.
+///
+/// Argument "**seed**" initializes the random number generator.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SelectionSession<M> {
+    pub inner: M,
+    #[serde(serialize_with = "save_rng", deserialize_with = "load_rng")]
+    rng: ChaCha12Rng,
+}
+
+impl<M: MateSelection<ChaCha12Rng>> SelectionSession<M> {
+    pub fn new(inner: M, seed: u64) -> Self {
+        Self {
+            inner,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Restart the random number generator from the given seed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+    }
+
+    /// Choose multiple weighted pairs. See [MateSelection::pairs].
+    pub fn pairs(&mut self, amount: usize, scores: Vec<f64>) -> Vec<[usize; 2]> {
+        self.inner.pairs(&mut self.rng, amount, scores)
+    }
+
+    /// Choose multiple weighted. See [MateSelection::select].
+    pub fn select(&mut self, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.inner.select(&mut self.rng, amount, scores)
+    }
+
+    /// Probability distribution function. See [MateSelection::pdf].
+    pub fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(scores)
+    }
+
+    /// Get the random number generator, for use with the other methods of the
+    /// selection method.
+    pub fn rng(&mut self) -> &mut ChaCha12Rng {
+        &mut self.rng
+    }
+}
+
+impl<M: Clone> Clone for SelectionSession<M> {
+    /// The random number generator is copied through its saved state.
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            rng: RngState::from(&self.rng).into(),
+        }
+    }
+}
+
+/// Saved state of a [ChaCha12Rng].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+struct RngState {
+    seed: [u8; 32],
+    stream: u64,
+    /// Offset from the start of the stream, in 32-bit words.
+    word_pos: u128,
+}
+
+impl From<&ChaCha12Rng> for RngState {
+    fn from(rng: &ChaCha12Rng) -> Self {
+        Self {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
+        }
+    }
+}
+
+impl From<RngState> for ChaCha12Rng {
+    fn from(state: RngState) -> Self {
+        let mut rng = ChaCha12Rng::from_seed(state.seed);
+        // Setting the stream resets the position, so it must be done first.
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        rng
+    }
+}
+
+fn save_rng<S: Serializer>(rng: &ChaCha12Rng, serializer: S) -> Result<S::Ok, S::Error> {
+    RngState::from(rng).serialize(serializer)
+}
+
+fn load_rng<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ChaCha12Rng, D::Error> {
+    RngState::deserialize(deserializer).map(ChaCha12Rng::from)
+}
+
+/// Choose multiple weighted pairs with a seeded random number generator, so
+/// that the results are reproducible. See [MateSelection::pairs].
+pub fn pairs_seeded<M: MateSelection<StdRng>>(
+    method: &M,
+    seed: u64,
+    amount: usize,
+    scores: Vec<f64>,
+) -> Vec<[usize; 2]> {
+    method.pairs(&mut StdRng::seed_from_u64(seed), amount, scores)
+}
+
+/// Choose multiple weighted with a seeded random number generator, so that the
+/// results are reproducible. See [MateSelection::select].
+pub fn select_seeded<M: MateSelection<StdRng>>(
+    method: &M,
+    seed: u64,
+    amount: usize,
+    scores: Vec<f64>,
+) -> Vec<usize> {
+    method.select(&mut StdRng::seed_from_u64(seed), amount, scores)
+}
+
+/// Choose multiple weighted pairs from many independent populations in one
+/// call, for example from islands or from concurrent experiments.
+/// See [MateSelection::pairs].
+///
+/// Argument "requests" is a list of `(amount, scores)` entries, one for each
+/// population. Returns the pairs for each population, in the same order.
+///
+/// Every population gets its own random number generator, seeded from the
+/// given generator. The results are reproducible and do not depend on the
+/// order in which the populations are processed. With the "parallel" feature
+/// the populations are processed concurrently using multiple threads.
+pub fn pairs_batch<R, M>(
+    method: &M,
+    rng: &mut R,
+    requests: &[(usize, &[f64])],
+) -> Vec<Vec<[usize; 2]>>
+where
+    R: Rng + ?Sized,
+    M: MateSelection<StdRng> + Sync + ?Sized,
+{
+    let jobs: Vec<(usize, &[f64], u64)> = requests
+        .iter()
+        .map(|&(amount, scores)| (amount, scores, rng.random()))
+        .collect();
+    let run = |&(amount, scores, seed): &(usize, &[f64], u64)| {
+        method.pairs_slice(&mut StdRng::seed_from_u64(seed), amount, scores)
+    };
+    #[cfg(feature = "parallel")]
+    return crate::parallel::map(&jobs, run);
+    #[cfg(not(feature = "parallel"))]
+    jobs.iter().map(run).collect()
+}
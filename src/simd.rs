@@ -0,0 +1,22 @@
+//! Vectorized reductions over large populations
+//!
+//! Floating point addition is not associative, so the compiler will not
+//! vectorize a plain sum. Instead these kernels keep several independent
+//! partial sums, one per lane, which the compiler can map onto SIMD registers.
+//! The results differ from a sequential sum by rounding errors.
+
+const LANES: usize = 8;
+
+/// Apply a function to every value and sum the results.
+#[inline]
+pub(crate) fn sum_map(data: &[f64], f: impl Fn(f64) -> f64) -> f64 {
+    let chunks = data.chunks_exact(LANES);
+    let tail: f64 = chunks.remainder().iter().map(|&x| f(x)).sum();
+    let mut lanes = [0.0; LANES];
+    for chunk in chunks {
+        for (lane, &x) in lanes.iter_mut().zip(chunk) {
+            *lane += f(x);
+        }
+    }
+    lanes.iter().sum::<f64>() + tail
+}
@@ -0,0 +1,85 @@
+//! Sampling weights for heavily truncated populations
+
+use crate::sus;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The sampling weights of only the eligible individuals, see
+/// [MateSelection::sample_weight_sparse](crate::MateSelection::sample_weight_sparse).
+///
+/// This is more compact than a dense vector of weights when most of the
+/// individuals can not be selected, for example with the truncation methods.
+/// If none of the individuals have a positive weight, then every individual
+/// is eligible with an equal weight.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SparseWeights {
+    /// Number of individuals in the whole population.
+    pub population: usize,
+    /// Indices of the eligible individuals into the scores list.
+    pub index: Vec<usize>,
+    /// Sampling weight of each eligible individual.
+    pub weights: Vec<f64>,
+}
+
+impl SparseWeights {
+    /// Discard the individuals with zero weight.
+    pub fn from_dense(weights: &[f64]) -> Self {
+        let mut index = vec![];
+        let mut sparse = vec![];
+        for (i, &w) in weights.iter().enumerate() {
+            assert!(
+                w.is_finite() && w >= 0.0,
+                "invalid weight: weights[{i}] = {w} (must be finite and >= 0)"
+            );
+            if w > 0.0 {
+                index.push(i);
+                sparse.push(w);
+            }
+        }
+        if index.is_empty() {
+            Self::uniform(weights.len(), (0..weights.len()).collect())
+        } else {
+            Self {
+                population: weights.len(),
+                index,
+                weights: sparse,
+            }
+        }
+    }
+
+    /// Give every one of the given individuals an equal weight.
+    pub(crate) fn uniform(population: usize, index: Vec<usize>) -> Self {
+        Self {
+            population,
+            weights: vec![1.0; index.len()],
+            index,
+        }
+    }
+
+    /// Number of eligible individuals.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Expand into a dense vector with a weight for every individual.
+    pub fn to_dense(&self) -> Vec<f64> {
+        let mut dense = vec![0.0; self.population];
+        for (&i, &w) in self.index.iter().zip(&self.weights) {
+            dense[i] = w;
+        }
+        dense
+    }
+
+    /// Choose multiple weighted individuals with stochastic universal
+    /// sampling. This only visits the eligible individuals.
+    pub fn select<R: Rng + ?Sized>(&self, rng: &mut R, amount: usize) -> Vec<usize> {
+        let sample = sus::choose_multiple_weighted(rng, amount, &self.weights);
+        sample.into_iter().map(|s| self.index[s]).collect()
+    }
+}
@@ -0,0 +1,161 @@
+//! Stochastic universal sampling over populations which do not fit in memory
+
+use rand::{Rng, RngExt};
+
+const NO_DATA: &str = "no data: can not choose from empty set";
+
+/// Choose multiple weighted individuals with stochastic universal sampling,
+/// reading the weights in chunks.
+///
+/// Argument "chunks" returns an iterator over the sampling weights, in chunks
+/// of any size, for example from a memory mapped file or a database cursor.
+/// It is called once to sum the weights and again to place the pointers, so
+/// it must yield the same weights in the same order every time. All weights
+/// must be finite and greater than or equal to zero. If all of the weights
+/// are zero then each individual is equally likely to be selected.
+///
+/// Returns an iterator over the selected indices, in ascending order. Only
+/// one chunk is held in memory at a time. Unlike
+/// [stochastic_universal_sampling::choose_multiple_weighted], the individuals
+/// are not shuffled before sampling, and the selected individuals are not
+/// shuffled afterwards.
+pub fn choose_multiple_streaming<R, F, I, C>(
+    rng: &mut R,
+    amount: usize,
+    chunks: F,
+) -> StreamingSample<I::IntoIter>
+where
+    R: Rng + ?Sized,
+    F: Fn() -> I,
+    I: IntoIterator<Item = C>,
+    C: AsRef<[f64]>,
+{
+    // First pass, find the total weight, rescaled so the largest weight is
+    // one. This avoids overflowing the sum, and underflowing the spacing
+    // between the pointers.
+    let mut population = 0;
+    let mut total = 0.0;
+    let mut max_weight: f64 = 0.0;
+    for chunk in chunks() {
+        for &w in chunk.as_ref() {
+            assert!(
+                w.is_finite() && w >= 0.0,
+                "invalid weight: weights[{population}] = {w} (must be finite and >= 0)"
+            );
+            if w > max_weight {
+                total = total * (max_weight / w) + 1.0;
+                max_weight = w;
+            } else if w > 0.0 {
+                total += w / max_weight;
+            }
+            population += 1;
+        }
+    }
+    if amount > 0 {
+        assert!(population > 0, "{NO_DATA}");
+    }
+    let max_weight = if max_weight == 0.0 {
+        // All of the weights are zero, treat them as equal.
+        total = population as f64;
+        None
+    } else {
+        Some(max_weight)
+    };
+    let spacing = total / amount as f64;
+    StreamingSample {
+        chunks: chunks().into_iter(),
+        chunk: None,
+        position: 0,
+        index: 0,
+        last_eligible: None,
+        cumulative: 0.0,
+        max_weight,
+        spacing,
+        offset: rng.random::<f64>(),
+        emitted: 0,
+        amount,
+    }
+}
+
+/// Iterator over the indices chosen by [choose_multiple_streaming].
+#[derive(Debug)]
+pub struct StreamingSample<I: Iterator> {
+    chunks: I,
+    chunk: Option<I::Item>,
+    /// Position of the next weight within the current chunk.
+    position: usize,
+    /// Index of the next weight within the whole population.
+    index: usize,
+    last_eligible: Option<usize>,
+    /// Total weight of the individuals before the next one.
+    cumulative: f64,
+    /// Divisor for the weights, or None if every weight is one.
+    max_weight: Option<f64>,
+    spacing: f64,
+    offset: f64,
+    emitted: usize,
+    amount: usize,
+}
+
+impl<I, C> StreamingSample<I>
+where
+    I: Iterator<Item = C>,
+    C: AsRef<[f64]>,
+{
+    /// Read the next weight in the population.
+    fn next_weight(&mut self) -> Option<f64> {
+        loop {
+            if let Some(chunk) = &self.chunk {
+                if let Some(&w) = chunk.as_ref().get(self.position) {
+                    self.position += 1;
+                    return Some(self.max_weight.map_or(1.0, |max| w / max));
+                }
+            }
+            self.chunk = Some(self.chunks.next()?);
+            self.position = 0;
+        }
+    }
+}
+
+impl<I, C> Iterator for StreamingSample<I>
+where
+    I: Iterator<Item = C>,
+    C: AsRef<[f64]>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.emitted == self.amount {
+            return None;
+        }
+        let pointer = (self.emitted as f64 + self.offset) * self.spacing;
+        // The current individual spans the weights from the cumulative total
+        // up to the start of the next individual.
+        while self.cumulative <= pointer {
+            let Some(w) = self.next_weight() else {
+                // Rounding errors can push the last pointers past the end.
+                self.emitted += 1;
+                return self.last_eligible;
+            };
+            self.index += 1;
+            self.cumulative += w;
+            if w > 0.0 {
+                self.last_eligible = Some(self.index - 1);
+            }
+        }
+        self.emitted += 1;
+        self.last_eligible
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.amount - self.emitted;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<I, C> ExactSizeIterator for StreamingSample<I>
+where
+    I: Iterator<Item = C>,
+    C: AsRef<[f64]>,
+{
+}
@@ -0,0 +1,146 @@
+//! Survivor selection, which chooses the next generation from the parents and
+//! their offspring
+
+use crate::{argsort_best, cmp_score};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+/// Common interface for the survivor selection methods.
+///
+/// The survivors are chosen from the parents and the offspring together. They
+/// are returned as indices into the combined list, where the parents come
+/// first: index `i` is `parents[i]` if `i < parents.len()`, and otherwise it
+/// is `offspring[i - parents.len()]`. The size of the population stays the
+/// same, so `parents.len()` survivors are returned, in ascending order.
+///
+/// Higher scores are better, and invalid (NaN) scores are worse than all
+/// others.
+pub trait SurvivorSelection<R: Rng + ?Sized = dyn Rng>: core::fmt::Debug {
+    /// Choose the individuals which survive into the next generation.
+    fn survivors(&self, rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize>;
+}
+
+impl<R: Rng + ?Sized, S: SurvivorSelection<R> + ?Sized> SurvivorSelection<R> for &S {
+    fn survivors(&self, rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        (**self).survivors(rng, parents, offspring)
+    }
+}
+
+impl<R: Rng + ?Sized, S: SurvivorSelection<R> + ?Sized> SurvivorSelection<R> for Box<S> {
+    fn survivors(&self, rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        (**self).survivors(rng, parents, offspring)
+    }
+}
+
+/// The (μ + λ) strategy keeps the best individuals from the parents and the
+/// offspring together, so the best individual is never lost.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct PlusSelection;
+
+/// The (μ, λ) strategy keeps the best of the offspring and discards all of the
+/// parents. There must be at least as many offspring as parents.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct CommaSelection;
+
+/// Steady state replacement, where every offspring replaces one of the worst
+/// parents, regardless of whether the offspring is better. If there are more
+/// offspring than parents then only the best offspring are kept.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ReplaceWorst;
+
+/// Round robin tournament replacement, as in evolutionary programming. Every
+/// parent and offspring competes against a number of opponents, chosen with
+/// uniform random probability, and scores a win for each opponent which is
+/// not better than it. The individuals with the most wins survive, and ties
+/// are broken by score.
+///
+/// Argument "**opponents**" is the number of opponents of each individual.
+/// More opponents apply more selection pressure.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct RoundRobin(pub usize);
+
+/// Age based replacement, where every offspring replaces one of the oldest
+/// parents, regardless of the scores. The parents must be in order of age,
+/// from oldest to youngest. The survivors are returned in ascending order, so
+/// appending the offspring to the population preserves this order.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct AgeBased;
+
+/// Indices of the best individuals of the combined list, in ascending order.
+fn keep_best(population: usize, parents: &[f64], offspring: &[f64], skip: usize) -> Vec<usize> {
+    let combined: Vec<f64> = parents.iter().chain(offspring).copied().collect();
+    let mut best: Vec<usize> = argsort_best(&combined[skip..])
+        .into_iter()
+        .take(population)
+        .map(|i| i + skip)
+        .collect();
+    best.sort_unstable();
+    best
+}
+
+impl<R: Rng + ?Sized> SurvivorSelection<R> for PlusSelection {
+    fn survivors(&self, _rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        keep_best(parents.len(), parents, offspring, 0)
+    }
+}
+
+impl<R: Rng + ?Sized> SurvivorSelection<R> for CommaSelection {
+    fn survivors(&self, _rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        assert!(
+            offspring.len() >= parents.len(),
+            "too few offspring: {} offspring for {} parents",
+            offspring.len(),
+            parents.len()
+        );
+        keep_best(parents.len(), parents, offspring, parents.len())
+    }
+}
+
+impl<R: Rng + ?Sized> SurvivorSelection<R> for ReplaceWorst {
+    fn survivors(&self, _rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        let population = parents.len();
+        let num_offspring = offspring.len().min(population);
+        let mut survivors = keep_best(population - num_offspring, parents, &[], 0);
+        let children = keep_best(num_offspring, &[], offspring, 0);
+        survivors.extend(children.into_iter().map(|i| i + population));
+        survivors
+    }
+}
+
+impl<R: Rng + ?Sized> SurvivorSelection<R> for RoundRobin {
+    fn survivors(&self, rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        let combined: Vec<f64> = parents.iter().chain(offspring).copied().collect();
+        let num = combined.len();
+        if num == 0 {
+            return vec![];
+        }
+        let wins: Vec<usize> = combined
+            .iter()
+            .map(|&score| {
+                (0..self.0)
+                    .filter(|_| cmp_score(score, combined[rng.random_range(0..num)]).is_ge())
+                    .count()
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..num).collect();
+        order.sort_by(|&a, &b| {
+            wins[b]
+                .cmp(&wins[a])
+                .then(cmp_score(combined[b], combined[a]))
+        });
+        order.truncate(parents.len());
+        order.sort_unstable();
+        order
+    }
+}
+
+impl<R: Rng + ?Sized> SurvivorSelection<R> for AgeBased {
+    fn survivors(&self, _rng: &mut R, parents: &[f64], offspring: &[f64]) -> Vec<usize> {
+        let population = parents.len();
+        let total = population + offspring.len();
+        (total - population..total).collect()
+    }
+}
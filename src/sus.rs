@@ -0,0 +1,46 @@
+//! Stochastic universal sampling, with or without std
+//!
+//! The [stochastic_universal_sampling] crate requires std, so builds without
+//! it use the implementation in [SelectionBuffer] instead.
+
+#[cfg(feature = "std")]
+pub(crate) use stochastic_universal_sampling::{choose_multiple, choose_multiple_weighted};
+
+#[cfg(not(feature = "std"))]
+use crate::{SelectionBuffer, SelectionError};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use rand::prelude::*;
+
+/// Same as [stochastic_universal_sampling::choose_multiple_weighted].
+#[cfg(not(feature = "std"))]
+pub(crate) fn choose_multiple_weighted<R>(rng: &mut R, amount: usize, weights: &[f64]) -> Vec<usize>
+where
+    R: Rng + ?Sized,
+{
+    assert!(
+        amount == 0 || !weights.is_empty(),
+        "{}",
+        SelectionError::EmptyPopulation
+    );
+    let mut output = Vec::with_capacity(amount);
+    SelectionBuffer::new().choose_from_weights(rng, amount, weights, &mut output);
+    output
+}
+
+/// Same as [stochastic_universal_sampling::choose_multiple].
+#[cfg(not(feature = "std"))]
+pub(crate) fn choose_multiple<R>(rng: &mut R, amount: usize, items: usize) -> Vec<usize>
+where
+    R: Rng + ?Sized,
+{
+    assert!(
+        amount == 0 || items > 0,
+        "{}",
+        SelectionError::EmptyPopulation
+    );
+    let mut output = Vec::with_capacity(amount);
+    SelectionBuffer::new().choose_multiple(rng, amount, items, &mut output);
+    output
+}
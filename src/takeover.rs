@@ -0,0 +1,46 @@
+//! Takeover time, by simulating selection without variation
+
+use crate::MateSelection;
+use alloc::vec::Vec;
+use rand::Rng;
+
+/// Number of independent runs to average over.
+const TRIALS: usize = 32;
+
+/// Simulate selection alone, starting from a population with distinct scores
+/// and a single best individual. Every generation the whole population is
+/// replaced by the selected individuals, keeping their scores.
+///
+/// Returns the average number of generations until every individual is a
+/// copy of the best, over the runs in which that happened. Runs in which the
+/// best individual is lost, or which do not finish within ten generations per
+/// individual, are not counted.
+pub(crate) fn simulate_takeover<R, M>(method: &M, rng: &mut R, population: usize) -> Option<f64>
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R> + ?Sized,
+{
+    if population <= 1 {
+        return Some(0.0);
+    }
+    let best = (population - 1) as f64;
+    let max_generations = 10 * population;
+    let mut total = 0;
+    let mut finished = 0;
+    for _ in 0..TRIALS {
+        let mut scores: Vec<f64> = (0..population).map(|x| x as f64).collect();
+        for generation in 1..=max_generations {
+            let selected = method.select_slice(rng, population, &scores);
+            scores = selected.iter().map(|&i| scores[i]).collect();
+            let copies = scores.iter().filter(|&&x| x == best).count();
+            if copies == population {
+                total += generation;
+                finished += 1;
+                break;
+            } else if copies == 0 {
+                break;
+            }
+        }
+    }
+    (finished > 0).then(|| total as f64 / finished as f64)
+}
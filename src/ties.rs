@@ -0,0 +1,121 @@
+//! Fair treatment of individuals with equal scores
+
+use crate::{sus, MateSelection, SelectionError};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Decides how individuals with equal scores share their sampling weights.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TiePolicy {
+    /// Every tied individual gets the average weight of the group. For linear
+    /// ranking this is the same as giving them their average rank.
+    #[default]
+    Average,
+
+    /// The weights are shuffled among the tied individuals, independently for
+    /// every selection. On average this is the same as the average policy.
+    Random,
+
+    /// The tied individuals are ordered by their index in the scores list,
+    /// and the individual with the lowest index gets the best weight.
+    Index,
+}
+
+/// Apply a consistent policy to individuals with equal scores. This is
+/// intended for the methods which rank or truncate the population, such as
+/// [RankedLinear](crate::RankedLinear), [RankedExponential](crate::RankedExponential),
+/// [Percentile](crate::Percentile) and [Best](crate::Best). Without this,
+/// the order of tied individuals is arbitrary but not random.
+///
+/// The tied individuals exchange the sampling weights which the inner method
+/// assigned to them, and then the parents are chosen from the adjusted
+/// weights using stochastic universal sampling.
+///
+/// Argument "**policy**" decides how tied individuals share their weights.
+///
+/// Argument "**inner**" is the method for selecting parents.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct TieBreaking<M> {
+    pub policy: TiePolicy,
+    pub inner: M,
+}
+
+impl<M> TieBreaking<M> {
+    pub fn new(policy: TiePolicy, inner: M) -> Self {
+        Self { policy, inner }
+    }
+}
+
+impl<R: Rng + ?Sized, M: MateSelection<R>> MateSelection<R> for TieBreaking<M> {
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        if amount == 0 {
+            return vec![];
+        } else {
+            assert!(!scores.is_empty());
+        }
+        let mut weights = self.inner.sample_weight(scores.clone());
+        for group in tied_groups(&scores) {
+            match self.policy {
+                TiePolicy::Random => shuffle_weights(rng, &mut weights, &group),
+                _ => self.share(&mut weights, &group),
+            }
+        }
+        sus::choose_multiple_weighted(rng, amount, &weights)
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        let groups = tied_groups(scores);
+        self.inner.sample_weight_in_place(scores);
+        for group in groups {
+            self.share(scores, &group);
+        }
+    }
+}
+
+impl<M> TieBreaking<M> {
+    /// Deterministically redistribute the weights within a group of ties.
+    fn share(&self, weights: &mut [f64], group: &[usize]) {
+        match self.policy {
+            TiePolicy::Average | TiePolicy::Random => {
+                let mean = group.iter().map(|&i| weights[i]).sum::<f64>() / group.len() as f64;
+                for &i in group {
+                    weights[i] = mean;
+                }
+            }
+            TiePolicy::Index => {
+                let mut values: Vec<f64> = group.iter().map(|&i| weights[i]).collect();
+                values.sort_unstable_by(|a, b| b.total_cmp(a));
+                // The group is in ascending order of index.
+                for (&i, w) in group.iter().zip(values) {
+                    weights[i] = w;
+                }
+            }
+        }
+    }
+}
+
+fn shuffle_weights<R: Rng + ?Sized>(rng: &mut R, weights: &mut [f64], group: &[usize]) {
+    let mut values: Vec<f64> = group.iter().map(|&i| weights[i]).collect();
+    values.shuffle(rng);
+    for (&i, w) in group.iter().zip(values) {
+        weights[i] = w;
+    }
+}
+
+/// Find the groups of two or more individuals with equal scores. Each group is
+/// in ascending order of index.
+fn tied_groups(scores: &[f64]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    // Stable sort keeps the indices in ascending order within each group.
+    order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+    order
+        .chunk_by(|&a, &b| scores[a].total_cmp(&scores[b]).is_eq())
+        .filter(|group| group.len() > 1)
+        .map(|group| group.to_vec())
+        .collect()
+}
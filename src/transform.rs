@@ -0,0 +1,266 @@
+//! Preprocess the scores before selecting from them
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{expect_valid, MateSelection, SelectionError};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Common interface for transforming the scores before selection.
+///
+/// Transforms modify the scores in place. Invalid (NaN) scores should stay
+/// invalid, so that those individuals are still treated as the worst.
+pub trait ScoreTransform: core::fmt::Debug {
+    fn transform(&self, scores: &mut [f64]);
+
+    /// Check that the arguments of this transform are valid.
+    fn validate(&self) -> Result<(), SelectionError> {
+        Ok(())
+    }
+}
+
+impl<T: ScoreTransform + ?Sized> ScoreTransform for &T {
+    fn transform(&self, scores: &mut [f64]) {
+        (**self).transform(scores)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        (**self).validate()
+    }
+}
+
+impl<T: ScoreTransform + ?Sized> ScoreTransform for Box<T> {
+    fn transform(&self, scores: &mut [f64]) {
+        (**self).transform(scores)
+    }
+    fn validate(&self) -> Result<(), SelectionError> {
+        (**self).validate()
+    }
+}
+
+/// The built in score transforms, which can be serialized.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Transform {
+    /// Limit the scores to the range `[min, max]`.
+    Clamp { min: f64, max: f64 },
+
+    /// Subtract the lowest score, so that the lowest score becomes zero and
+    /// all of the scores are non-negative.
+    ShiftToPositive,
+
+    /// Replace the scores with their ranks, from one for the worst individual
+    /// up to the population size for the best. Tied scores share the average
+    /// of their ranks.
+    Rank,
+
+    /// Standardize the scores to a mean of zero and a standard deviation of
+    /// one. If all of the scores are equal then they all become zero.
+    ZScore,
+
+    /// Limit the scores to the given quantiles, which removes outliers. The
+    /// argument is the fraction of the population at each end which is
+    /// clipped. Must be in the range [0, 0.5].
+    Winsorize(f64),
+
+    /// Natural logarithm of one plus the score. The scores should be greater
+    /// than negative one, otherwise they become invalid.
+    Log,
+}
+
+impl ScoreTransform for Transform {
+    fn validate(&self) -> Result<(), SelectionError> {
+        match *self {
+            Self::Clamp { min, max } if min.partial_cmp(&max).is_none_or(|o| o.is_gt()) => {
+                Err(SelectionError::argument("min", "is greater than max"))
+            }
+            Self::Winsorize(fraction) if !(0.0..=0.5).contains(&fraction) => Err(
+                SelectionError::argument("fraction", "is out of bounds [0, 0.5]"),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn transform(&self, scores: &mut [f64]) {
+        expect_valid(ScoreTransform::validate(self));
+        let valid = || scores.iter().copied().filter(|x| !x.is_nan());
+        match *self {
+            Self::Clamp { min, max } => {
+                for x in scores.iter_mut() {
+                    *x = x.clamp(min, max);
+                }
+            }
+            Self::ShiftToPositive => {
+                let min = valid().fold(f64::INFINITY, f64::min);
+                if min.is_finite() {
+                    for x in scores.iter_mut() {
+                        *x -= min;
+                    }
+                }
+            }
+            Self::Rank => {
+                let mut order: Vec<usize> =
+                    (0..scores.len()).filter(|&i| !scores[i].is_nan()).collect();
+                order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+                let mut ranks = vec![f64::NAN; scores.len()];
+                let mut start = 0;
+                for group in order.chunk_by(|&a, &b| scores[a] == scores[b]) {
+                    let rank = start as f64 + (group.len() + 1) as f64 / 2.0;
+                    for &i in group {
+                        ranks[i] = rank;
+                    }
+                    start += group.len();
+                }
+                scores.copy_from_slice(&ranks);
+            }
+            Self::ZScore => {
+                let num = valid().count() as f64;
+                let mean = valid().sum::<f64>() / num;
+                let std = (valid().map(|x| (x - mean).powi(2)).sum::<f64>() / num).sqrt();
+                for x in scores.iter_mut() {
+                    *x = if std > 0.0 {
+                        (*x - mean) / std
+                    } else {
+                        0.0 * *x
+                    };
+                }
+            }
+            Self::Winsorize(fraction) => {
+                let mut sorted: Vec<f64> = valid().collect();
+                if sorted.is_empty() {
+                    return;
+                }
+                sorted.sort_unstable_by(f64::total_cmp);
+                let clip = (fraction * sorted.len() as f64).floor() as usize;
+                let clip = clip.min((sorted.len() - 1) / 2);
+                let (min, max) = (sorted[clip], sorted[sorted.len() - 1 - clip]);
+                for x in scores.iter_mut() {
+                    *x = x.clamp(min, max);
+                }
+            }
+            Self::Log => {
+                for x in scores.iter_mut() {
+                    *x = x.ln_1p();
+                }
+            }
+        }
+    }
+}
+
+/// Multiply each score by a decay factor based on the age of the individual,
+/// so that long-lived individuals gradually lose their mating priority.
+///
+/// Each score is multiplied by `rate ^ (age - grace)`, for the individuals
+/// which are older than the grace period. The scores should be non-negative,
+/// for example by applying [Transform::ShiftToPositive] beforehand.
+///
+/// Argument "**ages**" is the age of each individual, in generations, see
+/// [Metadata](crate::Metadata).
+///
+/// Argument "**grace**" is the age at which the decay begins.
+///
+/// Argument "**rate**" is the decay factor per generation after the grace
+/// period. Must be in the range [0, 1].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AgeDecay {
+    pub ages: Vec<usize>,
+    pub grace: usize,
+    pub rate: f64,
+}
+
+impl AgeDecay {
+    pub fn new(ages: Vec<usize>, grace: usize, rate: f64) -> Self {
+        Self { ages, grace, rate }
+    }
+}
+
+impl ScoreTransform for AgeDecay {
+    fn validate(&self) -> Result<(), SelectionError> {
+        if (0.0..=1.0).contains(&self.rate) {
+            Ok(())
+        } else {
+            Err(SelectionError::argument("rate", "is out of bounds [0, 1]"))
+        }
+    }
+
+    fn transform(&self, scores: &mut [f64]) {
+        expect_valid(ScoreTransform::validate(self));
+        assert_eq!(
+            scores.len(),
+            self.ages.len(),
+            "scores and ages have different lengths"
+        );
+        for (x, &age) in scores.iter_mut().zip(&self.ages) {
+            let overdue = age.saturating_sub(self.grace);
+            if overdue > 0 {
+                *x *= self.rate.powi(overdue.min(i32::MAX as usize) as i32);
+            }
+        }
+    }
+}
+
+/// Apply a sequence of transforms to the scores, in order, and then select
+/// from the transformed scores with the inner method.
+///
+/// Argument "**transforms**" is the list of transforms, see [Transform]. To
+/// combine different types of transforms, use `Box<dyn ScoreTransform>`.
+///
+/// Argument "**inner**" is the method for selecting parents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Pipeline<M, T = Transform> {
+    pub transforms: Vec<T>,
+    pub inner: M,
+}
+
+impl<M, T: ScoreTransform> Pipeline<M, T> {
+    pub fn new(transforms: Vec<T>, inner: M) -> Self {
+        Self { transforms, inner }
+    }
+
+    /// Apply all of the transforms to the scores.
+    pub fn transform(&self, scores: &mut [f64]) {
+        for transform in &self.transforms {
+            transform.transform(scores);
+        }
+    }
+
+    fn transformed(&self, mut scores: Vec<f64>) -> Vec<f64> {
+        self.transform(&mut scores);
+        scores
+    }
+}
+
+impl<R, M, T> MateSelection<R> for Pipeline<M, T>
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R>,
+    T: ScoreTransform,
+{
+    fn validate(&self) -> Result<(), SelectionError> {
+        self.transforms.iter().try_for_each(|t| t.validate())?;
+        self.inner.validate()
+    }
+    fn select(&self, rng: &mut R, amount: usize, scores: Vec<f64>) -> Vec<usize> {
+        self.inner.select(rng, amount, self.transformed(scores))
+    }
+    fn select_slice(&self, rng: &mut R, amount: usize, scores: &[f64]) -> Vec<usize> {
+        self.inner
+            .select(rng, amount, self.transformed(scores.to_vec()))
+    }
+    fn expected_offspring(&self, amount: usize, scores: Vec<f64>) -> Vec<f64> {
+        self.inner
+            .expected_offspring(amount, self.transformed(scores))
+    }
+    fn loss_of_diversity(&self, amount: usize, scores: Vec<f64>) -> f64 {
+        self.inner
+            .loss_of_diversity(amount, self.transformed(scores))
+    }
+    fn pdf(&self, scores: Vec<f64>) -> Vec<f64> {
+        self.inner.pdf(self.transformed(scores))
+    }
+    fn sample_weight_in_place(&self, scores: &mut [f64]) {
+        self.transform(scores);
+        self.inner.sample_weight_in_place(scores)
+    }
+}
@@ -0,0 +1,237 @@
+//! Check that a selection method samples from the distribution it claims
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::MateSelection;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Observed selection frequencies compared with the expected probabilities,
+/// see [verify_distribution].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpiricalDistribution {
+    /// Expected fraction of the selections which choose each individual.
+    pub expected: Vec<f64>,
+    /// Observed fraction of the selections which chose each individual.
+    pub observed: Vec<f64>,
+    /// Total number of individuals which were selected.
+    pub samples: usize,
+}
+
+impl EmpiricalDistribution {
+    /// Largest absolute difference between the observed and expected
+    /// frequencies of any individual.
+    pub fn max_error(&self) -> f64 {
+        self.expected
+            .iter()
+            .zip(&self.observed)
+            .map(|(e, o)| (o - e).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// Individuals whose observed frequency differs from the expected
+    /// frequency by more than the tolerance, as `(index, expected, observed)`.
+    pub fn outliers(&self, tolerance: f64) -> Vec<(usize, f64, f64)> {
+        self.expected
+            .iter()
+            .zip(&self.observed)
+            .enumerate()
+            .filter(|(_, (e, o))| (*o - *e).abs() > tolerance)
+            .map(|(i, (&e, &o))| (i, e, o))
+            .collect()
+    }
+
+    /// Check that every observed frequency is within the tolerance of its
+    /// expected frequency.
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.max_error() <= tolerance
+    }
+}
+
+/// Run the selection method repeatedly and count how often each individual
+/// is selected, for comparison with the probabilities which it claims. This
+/// is for testing new implementations of [MateSelection].
+///
+/// Argument "seed" initializes the random number generator, so that the
+/// results are reproducible.
+///
+/// Argument "rounds" is the number of times to call
+/// [select](MateSelection::select), and argument "amount" is the number of
+/// individuals to select in each round.
+///
+/// The expected frequencies are the [expected_offspring](MateSelection::expected_offspring)
+/// divided by the amount, which is the [pdf](MateSelection::pdf) for most
+/// selection methods.
+pub fn verify_distribution<M>(
+    method: &M,
+    seed: u64,
+    rounds: usize,
+    amount: usize,
+    scores: &[f64],
+) -> EmpiricalDistribution
+where
+    M: MateSelection<StdRng> + ?Sized,
+{
+    let rng = &mut StdRng::seed_from_u64(seed);
+    let counts = count_selections(method, rng, rounds, amount, scores);
+    let samples = rounds * amount;
+    let mut expected = method.expected_offspring(amount, scores.to_vec());
+    for x in expected.iter_mut() {
+        *x /= amount as f64;
+    }
+    let observed = counts
+        .iter()
+        .map(|&count| count as f64 / samples as f64)
+        .collect();
+    EmpiricalDistribution {
+        expected,
+        observed,
+        samples,
+    }
+}
+
+/// Number of times that each individual was selected.
+fn count_selections<R, M>(
+    method: &M,
+    rng: &mut R,
+    rounds: usize,
+    amount: usize,
+    scores: &[f64],
+) -> Vec<usize>
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R> + ?Sized,
+{
+    let mut counts = vec![0_usize; scores.len()];
+    for _ in 0..rounds {
+        for index in method.select_slice(rng, amount, scores) {
+            counts[index] += 1;
+        }
+    }
+    counts
+}
+
+/// Result of Pearson's chi-squared test, see [MateSelection::self_test].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GoodnessOfFit {
+    /// The chi-squared statistic.
+    pub statistic: f64,
+    /// Number of individuals which can be selected, minus one.
+    pub degrees_of_freedom: usize,
+    /// Probability of a statistic at least this large, if the selection
+    /// method samples from the distribution it claims. Small p-values mean
+    /// that it probably does not.
+    pub p_value: f64,
+}
+
+pub(crate) fn self_test<R, M>(
+    method: &M,
+    rng: &mut R,
+    samples: usize,
+    scores: &[f64],
+) -> GoodnessOfFit
+where
+    R: Rng + ?Sized,
+    M: MateSelection<R> + ?Sized,
+{
+    // Select one individual at a time, so that the samples are independent.
+    let counts = count_selections(method, rng, samples, 1, scores);
+    let expected = method.expected_offspring(1, scores.to_vec());
+    let mut statistic = 0.0;
+    let mut eligible = 0;
+    for (&count, &p) in counts.iter().zip(&expected) {
+        let expected = p * samples as f64;
+        if expected > 0.0 {
+            statistic += (count as f64 - expected).powi(2) / expected;
+            eligible += 1;
+        } else if count > 0 {
+            // Selected an individual which can not be selected.
+            statistic = f64::INFINITY;
+        }
+    }
+    let degrees_of_freedom = eligible.max(1) - 1;
+    let p_value = if statistic == f64::INFINITY {
+        0.0
+    } else if degrees_of_freedom == 0 {
+        1.0
+    } else {
+        gamma_q(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+    };
+    GoodnessOfFit {
+        statistic,
+        degrees_of_freedom,
+        p_value,
+    }
+}
+
+/// Regularized upper incomplete gamma function, `Q(a, x)`.
+///
+/// Uses the series expansion for small x and the continued fraction
+/// otherwise, see Numerical Recipes section 6.2.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    const ITERATIONS: usize = 1000;
+    const EPSILON: f64 = 1e-15;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let log_prefactor = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * log_prefactor.exp()).clamp(0.0, 1.0)
+    } else {
+        // Modified Lentz's method.
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for n in 1..ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (log_prefactor.exp() * h).clamp(0.0, 1.0)
+    }
+}
+
+/// Natural logarithm of the gamma function, using the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+    for (i, c) in COEFFICIENTS.iter().enumerate() {
+        series += c / (x + 1.0 + i as f64);
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}